@@ -1,6 +0,0 @@
-pub mod detect;
-//pub mod add;
-//pub mod list;
-//pub mod launch;
-pub mod links;
-pub mod aliases;
@@ -0,0 +1,75 @@
+//! Standalone browser scan: prints every detected browser and writes
+//! `browsers.json` and `browsers.txt` to the current directory. Uses the same
+//! detection as `quick_tabs detect`, without selecting or saving a browser.
+//!
+//! `--output <PATH>` and `--format {text,json}` write a single list instead, to
+//! the given path (`-` for stdout) or to `browsers.txt`/`browsers.json`.
+
+use std::fs;
+use std::path::PathBuf;
+use clap::{Parser, ValueEnum};
+use quick_tabs::config::AppConfig;
+use quick_tabs::detect::{browsers_json, browsers_text, detect_all, write_outputs, DetectOptions};
+use quick_tabs::error::{ErrorFormat, QuickTabsError};
+use quick_tabs::output::{set_verbosity, Verbosity};
+use quick_tabs::say;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    /// `name = path` lines, as in browsers.txt
+    Text,
+    /// The full browser records, as in browsers.json
+    Json,
+}
+
+/// Scan for installed browsers and write the list to a file
+#[derive(Parser, Debug)]
+#[command(name = "find_browsers", version)]
+struct Cli {
+    /// Where to write the list; `-` prints it to stdout
+    #[arg(long, short, value_name = "PATH")]
+    output: Option<PathBuf>,
+    /// Format of the list; defaults to JSON for a `.json` output path, otherwise text
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(e) = run(cli) {
+        e.report(ErrorFormat::Text);
+        std::process::exit(e.code());
+    }
+}
+
+fn run(cli: Cli) -> Result<(), QuickTabsError> {
+    let to_stdout = cli.output.as_deref().is_some_and(|p| p.as_os_str() == "-");
+    if to_stdout {
+        // Nothing but the list may reach stdout
+        set_verbosity(Verbosity::Quiet);
+    }
+    let options = DetectOptions::from_config(&AppConfig::load_or_default());
+    let found = detect_all(&options);
+
+    if cli.output.is_none() && cli.format.is_none() {
+        return Ok(write_outputs(&found)?);
+    }
+    let is_json_path = cli.output.as_ref().is_some_and(|p| p.extension().is_some_and(|ext| ext == "json"));
+    let format = cli.format.unwrap_or(if is_json_path { Format::Json } else { Format::Text });
+    let contents = match format {
+        Format::Text => browsers_text(&found),
+        Format::Json => browsers_json(&found) + "\n",
+    };
+    let path = cli.output.unwrap_or_else(|| PathBuf::from(match format {
+        Format::Text => "browsers.txt",
+        Format::Json => "browsers.json",
+    }));
+
+    if to_stdout {
+        print!("{}", contents);
+    } else {
+        fs::write(&path, contents)?;
+        say!("📄 Saved full browser list to {}", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,265 @@
+// src/commands/aliases.rs
+use std::collections::HashSet;
+use std::path::Path;
+use crate::commands::config::{rotate_backups, write_atomic};
+use crate::commands::error::QuickTabsError;
+use crate::commands::groups::GroupConfig;
+use crate::commands::links::{matches_filter, BatchOptions, Change, ChangeSet, LinkConfig};
+use crate::commands::resolve::{alias_steps, resolve_target};
+use crate::commands::schema::{self, load_config};
+use crate::commands::url::comparable_url;
+use crate::{esay, say};
+use indexmap::IndexMap;
+use serde::{Serialize, Deserialize};
+use std::io;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AliasConfig {
+    /// Tag -> target, in the order the aliases were added
+    pub aliases: IndexMap<String, String>,
+}
+
+impl AliasConfig {
+    /// Reads the aliases from `path`; see `schema::load_config` for missing, damaged and
+    /// newer files.
+    pub fn load(path: &Path) -> Result<Self, QuickTabsError> {
+        load_config(path, "aliases", "alias", || AliasConfig { aliases: IndexMap::new() })
+    }
+
+    /// Writes the config, keeping the previous file as a backup (see `rotate_backups`).
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = schema::to_json(&self)?;
+        rotate_backups(path)?;
+        write_atomic(path, json)
+    }
+
+    pub fn add_alias(&mut self, tag: String, url: String) -> ChangeSet {
+        // Replacing a target keeps the alias in its place
+        match self.aliases.insert(tag.clone(), url.clone()) {
+            Some(old) => Change::Updated { kind: "alias", tag, old, new: url },
+            None => Change::Added { kind: "alias", tag, value: url },
+        }
+        .into()
+    }
+
+    /// Changes the target of the existing alias `tag`. Unlike `add_alias`, a missing
+    /// alias is an error.
+    pub fn edit_alias(&mut self, tag: &str, url: String) -> Result<ChangeSet, QuickTabsError> {
+        let target = self.aliases.get_mut(tag)
+            .ok_or_else(|| QuickTabsError::NotFound(format!("Alias '{}'", tag)))?;
+        if *target == url {
+            return Ok(ChangeSet::default());
+        }
+        let old = std::mem::replace(target, url.clone());
+        Ok(Change::Updated { kind: "alias", tag: tag.to_string(), old, new: url }.into())
+    }
+
+    pub fn resolve(&self, tag: &str) -> Option<String> {
+        self.aliases.get(tag).cloned()
+    }
+
+    /// Removes the alias `tag`; the change set is empty when there is none.
+    pub fn remove_alias(&mut self, tag: &str) -> ChangeSet {
+        match self.aliases.shift_remove(tag) {
+            Some(url) => Change::Removed { kind: "alias", tag: tag.to_string(), value: url }.into(),
+            None => ChangeSet::default(),
+        }
+    }
+
+    /// Removes every alias.
+    pub fn clear(&mut self) -> ChangeSet {
+        let changes = self.aliases.drain(..)
+            .map(|(tag, url)| Change::Removed { kind: "alias", tag, value: url })
+            .collect();
+        ChangeSet { changes }
+    }
+
+    /// Renames the alias `old` to `new`, keeping its target and position, and points
+    /// aliases that chain through `old` at `new`. Fails if `old` is missing or `new` is taken.
+    pub fn rename_alias(&mut self, old: &str, new: &str) -> Result<ChangeSet, QuickTabsError> {
+        if old != new && self.aliases.contains_key(new) {
+            return Err(QuickTabsError::Other(format!("Alias '{}' already exists", new)));
+        }
+        let (index, _, target) = self.aliases.shift_remove_full(old)
+            .ok_or_else(|| QuickTabsError::NotFound(format!("Alias '{}'", old)))?;
+        self.aliases.shift_insert(index, new.to_string(), target);
+
+        let mut changes = ChangeSet::from(Change::Renamed { kind: "alias", old: old.to_string(), new: new.to_string() });
+        changes.extend(self.retarget(old, new));
+        Ok(changes)
+    }
+
+    /// Points every alias whose target is the tag `old` at `new` instead.
+    pub fn retarget(&mut self, old: &str, new: &str) -> ChangeSet {
+        let mut changes = ChangeSet::default();
+        for (tag, target) in self.aliases.iter_mut().filter(|(_, t)| t.as_str() == old) {
+            *target = new.to_string();
+            changes.extend([Change::Updated { kind: "alias", tag: tag.clone(), old: old.to_string(), new: new.to_string() }]);
+        }
+        changes
+    }
+
+    /// Removes aliases whose URL is already used by an earlier alias, compared with
+    /// `comparable_url` as `LinkConfig::dedupe_urls` does. Returns the tags that were
+    /// dropped.
+    pub fn dedupe_urls(&mut self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let mut removed = vec![];
+        self.aliases.retain(|tag, target| {
+            if seen.insert(comparable_url(target)) {
+                true
+            } else {
+                removed.push(tag.clone());
+                false
+            }
+        });
+        removed
+    }
+
+    pub fn list(&self) {
+        self.list_filtered(None);
+    }
+
+    /// Like `list`, but only the aliases whose tag or target contains `needle` (see `matches_filter`).
+    pub fn list_filtered(&self, needle: Option<&str>) {
+        let shown: Vec<(&String, &String)> = self.aliases.iter()
+            .filter(|(tag, url)| needle.is_none_or(|n| matches_filter(tag, url, n)))
+            .collect();
+        match needle {
+            _ if self.aliases.is_empty() => say!("⚠️ No aliases saved."),
+            Some(n) if shown.is_empty() => say!("⚠️ No aliases match '{}'.", n),
+            _ => {
+                say!("\n✨ Saved aliases:");
+                for (tag, url) in shown {
+                    println!("  [{}] -> {}", tag, url);
+                }
+            }
+        }
+    }
+    
+    /// Like `list`, but shows every hop to the effective target, e.g.
+    /// `[morning] -> group:work -> (3 urls)`.
+    pub fn list_resolved(&self, links: &LinkConfig, groups: &GroupConfig) {
+        if self.aliases.is_empty() {
+            say!("⚠️ No aliases saved.");
+        } else {
+            say!("\n✨ Saved aliases:");
+            for tag in self.aliases.keys() {
+                println!("  [{}] -> {}", tag, alias_steps(tag, self, links, groups).join(" -> "));
+            }
+        }
+    }
+
+    /// The URLs `open-all-aliases` opens, each with its alias: the aliases `batch` picks
+    /// (reordered, then cut to `--count`), each resolved as `launch <alias>` would,
+    /// through alias chains, link tags and `group:` references. Aliases that do not
+    /// resolve are skipped with a warning.
+    pub fn picked_urls(&self, links: &LinkConfig, groups: &GroupConfig, batch: &BatchOptions) -> Vec<(&String, String)> {
+        let tags: Vec<&String> = self.aliases.keys().collect();
+        batch.pick(&tags).into_iter()
+            .flat_map(|tag| {
+                let urls = resolve_target(tag, self, links, groups).unwrap_or_else(|e| {
+                    esay!("⚠️ Skipping alias '{}': {}", tag, e);
+                    vec![]
+                });
+                urls.into_iter().map(move |url| (*tag, url))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aliases() -> AliasConfig {
+        AliasConfig {
+            aliases: IndexMap::from([
+                ("gh".to_string(), "https://github.com".to_string()),
+                ("code".to_string(), "gh".to_string()),
+                ("mail".to_string(), "https://mail.example".to_string()),
+            ]),
+        }
+    }
+
+    #[test]
+    fn rename_alias_keeps_target_and_chains() {
+        let mut aliases = aliases();
+        let changes = aliases.rename_alias("gh", "github").unwrap();
+        assert_eq!(changes.summary(), "1 updated, 1 renamed");
+        assert_eq!(aliases.resolve("github").as_deref(), Some("https://github.com"));
+        assert_eq!(aliases.resolve("gh"), None);
+        assert_eq!(aliases.resolve("code").as_deref(), Some("github"));
+    }
+
+    #[test]
+    fn rename_alias_rejects_missing_and_taken_tags() {
+        let mut aliases = aliases();
+        assert!(matches!(aliases.rename_alias("nope", "x"), Err(QuickTabsError::NotFound(_))));
+        assert!(matches!(aliases.rename_alias("gh", "mail"), Err(QuickTabsError::Other(_))));
+        // Nothing changed
+        assert_eq!(aliases.resolve("gh").as_deref(), Some("https://github.com"));
+        assert_eq!(aliases.resolve("mail").as_deref(), Some("https://mail.example"));
+    }
+
+    #[test]
+    fn open_all_resolves_chains_link_tags_and_groups() {
+        let aliases = AliasConfig {
+            aliases: IndexMap::from([
+                ("web".to_string(), "https://example.org".to_string()),
+                ("board".to_string(), "dash".to_string()),
+                ("morning".to_string(), "group:work".to_string()),
+                ("start".to_string(), "board".to_string()),
+            ]),
+        };
+        let links = LinkConfig { links: vec![
+            crate::commands::links::Link::new("dash", "https://dash.example"),
+            crate::commands::links::Link::new("mail", "https://mail.example"),
+        ] };
+        let mut groups = GroupConfig::default();
+        groups.groups.insert("work".to_string(), vec!["mail".to_string(), "dash".to_string()]);
+
+        let urls: Vec<String> = aliases.picked_urls(&links, &groups, &BatchOptions::default())
+            .into_iter().map(|(_, url)| url).collect();
+        assert_eq!(urls, [
+            "https://example.org", "https://dash.example", "https://mail.example", "https://dash.example", "https://dash.example",
+        ]);
+        // --count picks aliases, each opening all its URLs
+        let batch = BatchOptions { count: Some(1), reverse: true, ..Default::default() };
+        let start = "start".to_string();
+        assert_eq!(aliases.picked_urls(&links, &groups, &batch), [(&start, "https://dash.example".to_string())]);
+    }
+
+    #[test]
+    fn dedupe_keeps_the_earliest_saved_alias() {
+        let mut aliases = AliasConfig {
+            aliases: IndexMap::from([
+                ("zz".to_string(), "https://GitHub.com/".to_string()),
+                ("aa".to_string(), "https://github.com".to_string()),
+                ("mail".to_string(), "https://mail.example".to_string()),
+            ]),
+        };
+        assert_eq!(aliases.dedupe_urls(), ["aa"]);
+        assert_eq!(aliases.aliases.keys().collect::<Vec<_>>(), ["zz", "mail"]);
+    }
+
+    #[test]
+    fn aliases_keep_saved_order() {
+        let path = std::env::temp_dir().join(format!("quick_tabs_alias_order_{}.json", std::process::id()));
+        let mut aliases = aliases();
+        aliases.add_alias("zz".to_string(), "https://zz.example".to_string());
+        aliases.add_alias("aa".to_string(), "https://aa.example".to_string());
+        aliases.remove_alias("code");
+        aliases.rename_alias("gh", "github").unwrap();
+        aliases.save(&path).unwrap();
+
+        let loaded = AliasConfig::load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let tags: Vec<&str> = loaded.aliases.keys().map(String::as_str).collect();
+        assert_eq!(tags, ["github", "mail", "zz", "aa"]);
+
+        // Files written by earlier versions are plain JSON objects and still load
+        let old: AliasConfig = serde_json::from_str(r#"{"aliases":{"b":"https://b.example","a":"b"}}"#).unwrap();
+        assert_eq!(old.aliases.keys().collect::<Vec<_>>(), ["b", "a"]);
+    }
+}
@@ -0,0 +1,114 @@
+// src/commands/blocklist.rs
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::commands::config::{app_config_dir, write_atomic};
+use crate::commands::error::QuickTabsError;
+use crate::commands::glob::glob_match;
+use crate::commands::links::{Change, ChangeSet};
+use crate::commands::url::url_host;
+use crate::esay;
+
+/// URL patterns that must never be opened when safe launching is enforced.
+///
+/// Stored as `blocklist.txt`, one pattern per line (`#` starts a comment). A pattern
+/// containing `/` is a glob over the whole URL (`https://*.example.com/admin*`);
+/// anything else is a domain pattern matched against the host, where `example.com`
+/// also covers its subdomains and globs such as `*.example.*` are allowed.
+#[derive(Debug, Clone, Default)]
+pub struct Blocklist {
+    pub patterns: Vec<String>,
+}
+
+impl Blocklist {
+    pub fn load(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok(data) => Blocklist {
+                patterns: data.lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                    .map(str::to_string)
+                    .collect(),
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Blocklist::default(),
+            Err(e) => {
+                esay!("⚠️ Failed to read blocklist {}: {}", path.display(), e);
+                Blocklist::default()
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut data = self.patterns.join("\n");
+        data.push('\n');
+        write_atomic(path, data)
+    }
+
+    /// Loads the blocklist from the config directory, or an empty one when there is none.
+    pub fn load_or_default() -> Self {
+        blocklist_path().map(|p| Blocklist::load(&p)).unwrap_or_default()
+    }
+
+    /// Adds a pattern; the change set is empty if it was already present.
+    pub fn add(&mut self, pattern: String) -> ChangeSet {
+        if self.patterns.contains(&pattern) {
+            return ChangeSet::default();
+        }
+        self.patterns.push(pattern.clone());
+        Change::Added { kind: "block", tag: pattern, value: String::new() }.into()
+    }
+
+    /// Removes a pattern; the change set is empty if it was not present.
+    pub fn remove(&mut self, pattern: &str) -> ChangeSet {
+        let before = self.patterns.len();
+        self.patterns.retain(|p| p != pattern);
+        if self.patterns.len() == before {
+            return ChangeSet::default();
+        }
+        Change::Removed { kind: "block", tag: pattern.to_string(), value: String::new() }.into()
+    }
+
+    /// Returns the first pattern that blocks `url`, if any.
+    pub fn find_match(&self, url: &str) -> Option<&str> {
+        let host = url_host(url);
+        self.patterns.iter()
+            .find(|p| {
+                if p.contains('/') {
+                    glob_match(p, url)
+                } else {
+                    glob_match(p, host) || glob_match(&format!("*.{}", p), host)
+                }
+            })
+            .map(|p| p.as_str())
+    }
+}
+
+/// Gets the blocklist path (~/.config/quick_tabs/blocklist.txt)
+pub fn blocklist_path() -> Result<PathBuf, QuickTabsError> {
+    Ok(app_config_dir()?.join("blocklist.txt"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocklist(patterns: &[&str]) -> Blocklist {
+        Blocklist { patterns: patterns.iter().map(|p| p.to_string()).collect() }
+    }
+
+    #[test]
+    fn domain_patterns_cover_subdomains() {
+        let list = blocklist(&["facebook.com"]);
+        assert_eq!(list.find_match("https://facebook.com/"), Some("facebook.com"));
+        assert_eq!(list.find_match("https://www.Facebook.com:443/feed"), Some("facebook.com"));
+        assert_eq!(list.find_match("https://notfacebook.com/"), None);
+    }
+
+    #[test]
+    fn url_globs_match_the_whole_url() {
+        let list = blocklist(&["https://*.example.com/admin*", "*.casino.*"]);
+        assert!(list.find_match("https://intranet.example.com/admin/users").is_some());
+        assert!(list.find_match("https://intranet.example.com/docs").is_none());
+        assert!(list.find_match("http://play.casino.net/").is_some());
+    }
+}
@@ -0,0 +1,305 @@
+// src/commands/bookmarks.rs
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+use crate::say;
+
+// --- Data Structures ---
+
+/// A bookmark read from a browser profile, ready to be saved as a link.
+#[derive(Debug, Clone)]
+pub struct ImportedBookmark {
+    pub tag: String,
+    pub url: String,
+    /// Name of the folder the bookmark lives in; mapped to a group on import.
+    pub folder: Option<String>,
+    /// When the bookmark was added or changed (seconds since the Unix epoch)
+    pub modified: Option<u64>,
+    /// When the URL was last visited or opened from the bookmark, if recorded
+    pub last_used: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    /// Chromium-family `Bookmarks` JSON file
+    ChromiumJson,
+    /// Firefox `places.sqlite` database
+    FirefoxPlaces,
+}
+
+// --- Public Entry Point ---
+
+/// Reads all http(s) bookmarks from `browser`'s profile.
+///
+/// Supported browsers: chrome, chromium, edge, brave (JSON `Bookmarks` file) and firefox
+/// (`places.sqlite`, requires the `rusqlite` feature). When the browser has several
+/// profiles, `profile` selects one by directory name; otherwise the user is prompted.
+pub fn import_from_browser(browser: &str, profile: Option<&str>) -> Result<Vec<ImportedBookmark>, String> {
+    let (root, format) = profile_root(browser)
+        .ok_or_else(|| format!("Unsupported browser '{}'. Use chrome, chromium, edge, brave or firefox.", browser))?;
+
+    let profiles = find_profiles(&root, format);
+    if profiles.is_empty() {
+        return Err(format!("No {} profiles with bookmarks found under {}", browser, root.display()));
+    }
+
+    let profile_dir = select_profile(&profiles, profile)?;
+    say!("📚 Reading bookmarks from {}", profile_dir.display());
+
+    let raw = match format {
+        Format::ChromiumJson => read_chromium_bookmarks(&profile_dir.join("Bookmarks"))?,
+        Format::FirefoxPlaces => read_firefox_bookmarks(&profile_dir.join("places.sqlite"))?,
+    };
+
+    Ok(assign_tags(raw))
+}
+
+// --- Profile Discovery ---
+
+/// Returns the directory holding the browser's profiles for the current platform.
+fn profile_root(browser: &str) -> Option<(PathBuf, Format)> {
+    let browser = browser.to_lowercase();
+    let chromium_dir = |linux: &str, macos: &str, windows: &str| -> Option<PathBuf> {
+        if cfg!(target_os = "windows") {
+            dirs::data_local_dir().map(|d| d.join(windows))
+        } else if cfg!(target_os = "macos") {
+            dirs::config_dir().map(|d| d.join(macos))
+        } else {
+            dirs::config_dir().map(|d| d.join(linux))
+        }
+    };
+
+    match browser.as_str() {
+        "chrome" | "google-chrome" => chromium_dir("google-chrome", "Google/Chrome", "Google\\Chrome\\User Data")
+            .map(|p| (p, Format::ChromiumJson)),
+        "chromium" => chromium_dir("chromium", "Chromium", "Chromium\\User Data")
+            .map(|p| (p, Format::ChromiumJson)),
+        "edge" | "msedge" => chromium_dir("microsoft-edge", "Microsoft Edge", "Microsoft\\Edge\\User Data")
+            .map(|p| (p, Format::ChromiumJson)),
+        "brave" => chromium_dir("BraveSoftware/Brave-Browser", "BraveSoftware/Brave-Browser", "BraveSoftware\\Brave-Browser\\User Data")
+            .map(|p| (p, Format::ChromiumJson)),
+        "firefox" => {
+            let root = if cfg!(target_os = "windows") {
+                dirs::config_dir().map(|d| d.join("Mozilla\\Firefox\\Profiles"))
+            } else if cfg!(target_os = "macos") {
+                dirs::config_dir().map(|d| d.join("Firefox/Profiles"))
+            } else {
+                dirs::home_dir().map(|d| d.join(".mozilla/firefox"))
+            };
+            root.map(|p| (p, Format::FirefoxPlaces))
+        }
+        _ => None,
+    }
+}
+
+/// Lists profile directories under `root` that contain a bookmarks store.
+fn find_profiles(root: &Path, format: Format) -> Vec<PathBuf> {
+    let marker = match format {
+        Format::ChromiumJson => "Bookmarks",
+        Format::FirefoxPlaces => "places.sqlite",
+    };
+
+    let mut profiles: Vec<PathBuf> = fs::read_dir(root)
+        .map(|entries| {
+            entries.flatten()
+                .map(|e| e.path())
+                .filter(|p| p.join(marker).is_file())
+                .collect()
+        })
+        .unwrap_or_default();
+    profiles.sort();
+    profiles
+}
+
+fn profile_name(path: &Path) -> String {
+    path.file_name().unwrap_or_default().to_string_lossy().to_string()
+}
+
+fn select_profile(profiles: &[PathBuf], wanted: Option<&str>) -> Result<PathBuf, String> {
+    if let Some(wanted) = wanted {
+        let wanted_lower = wanted.to_lowercase();
+        // Exact match first, then substring (Firefox dirs look like `abcd1234.default-release`)
+        return profiles.iter()
+            .find(|p| profile_name(p).to_lowercase() == wanted_lower)
+            .or_else(|| profiles.iter().find(|p| profile_name(p).to_lowercase().contains(&wanted_lower)))
+            .cloned()
+            .ok_or_else(|| {
+                let names: Vec<String> = profiles.iter().map(|p| profile_name(p)).collect();
+                format!("Profile '{}' not found. Available: {}", wanted, names.join(", "))
+            });
+    }
+
+    if profiles.len() == 1 {
+        return Ok(profiles[0].clone());
+    }
+
+    println!("\nSelect a profile:");
+    for (i, p) in profiles.iter().enumerate() {
+        println!("  [{}] {}", i + 1, profile_name(p));
+    }
+    print!("Enter choice [1-{}]: ", profiles.len());
+    let _ = io::stdout().flush();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).map_err(|e| format!("Read error: {}", e))?;
+    match input.trim().parse::<usize>() {
+        Ok(index) if index > 0 && index <= profiles.len() => Ok(profiles[index - 1].clone()),
+        _ => Err("Invalid choice. Use --profile <name> to pick a profile non-interactively.".to_string()),
+    }
+}
+
+// --- Readers ---
+
+/// A bookmark as read from the browser, before tags are assigned
+struct RawBookmark {
+    title: String,
+    url: String,
+    folder: Option<String>,
+    modified: Option<u64>,
+    last_used: Option<u64>,
+}
+
+/// Seconds between 1601-01-01 (the Windows/WebKit epoch Chromium uses) and 1970-01-01
+const WEBKIT_EPOCH_OFFSET: u64 = 11_644_473_600;
+
+/// Converts a Chromium timestamp (a string of microseconds since 1601) to Unix
+/// seconds. `"0"` means never.
+fn chromium_time(node: &Value, key: &str) -> Option<u64> {
+    let micros: u64 = node.get(key)?.as_str()?.parse().ok()?;
+    (micros / 1_000_000).checked_sub(WEBKIT_EPOCH_OFFSET).filter(|&t| t > 0)
+}
+
+fn read_chromium_bookmarks(path: &Path) -> Result<Vec<RawBookmark>, String> {
+    let data = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let json: Value = serde_json::from_str(&data)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+    let mut out = vec![];
+    if let Some(roots) = json.get("roots").and_then(Value::as_object) {
+        for root in roots.values() {
+            // Bookmarks directly under "Bookmarks bar"/"Other bookmarks" stay ungrouped
+            if let Some(children) = root.get("children").and_then(Value::as_array) {
+                for child in children {
+                    walk_chromium_node(child, None, &mut out);
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn walk_chromium_node(node: &Value, folder: Option<&str>, out: &mut Vec<RawBookmark>) {
+    let name = node.get("name").and_then(Value::as_str).unwrap_or_default();
+    match node.get("type").and_then(Value::as_str) {
+        Some("url") => {
+            if let Some(url) = node.get("url").and_then(Value::as_str) {
+                if is_web_url(url) {
+                    out.push(RawBookmark {
+                        title: name.to_string(),
+                        url: url.to_string(),
+                        folder: folder.map(str::to_string),
+                        modified: chromium_time(node, "date_modified").or_else(|| chromium_time(node, "date_added")),
+                        last_used: chromium_time(node, "date_last_used"),
+                    });
+                }
+            }
+        }
+        Some("folder") => {
+            if let Some(children) = node.get("children").and_then(Value::as_array) {
+                for child in children {
+                    walk_chromium_node(child, Some(name), out);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(feature = "rusqlite")]
+fn read_firefox_bookmarks(path: &Path) -> Result<Vec<RawBookmark>, String> {
+    use rusqlite::{Connection, OpenFlags};
+
+    // Firefox keeps places.sqlite locked while running, so read from a copy.
+    let copy = std::env::temp_dir().join(format!("quick_tabs_places_{}.sqlite", std::process::id()));
+    fs::copy(path, &copy).map_err(|e| format!("Failed to copy {}: {}", path.display(), e))?;
+
+    let result = (|| {
+        let conn = Connection::open_with_flags(&copy, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let mut stmt = conn.prepare(
+            "SELECT b.title, p.url, parent.title, b.lastModified, p.last_visit_date
+             FROM moz_bookmarks b
+             JOIN moz_places p ON b.fk = p.id
+             LEFT JOIN moz_bookmarks parent ON b.parent = parent.id
+             WHERE b.type = 1",
+        )?;
+        // Firefox stores PRTime: microseconds since the Unix epoch
+        let seconds = |micros: Option<i64>| micros.filter(|&t| t > 0).map(|t| t as u64 / 1_000_000);
+        let rows = stmt.query_map([], |row| {
+            Ok(RawBookmark {
+                title: row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+                url: row.get(1)?,
+                folder: row.get(2)?,
+                modified: seconds(row.get(3)?),
+                last_used: seconds(row.get(4)?),
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()
+    })();
+    let _ = fs::remove_file(&copy);
+
+    let rows = result.map_err(|e| format!("Failed to query {}: {}", path.display(), e))?;
+    Ok(rows.into_iter()
+        .filter(|b| is_web_url(&b.url))
+        // Firefox's built-in roots ("toolbar", "menu", "unfiled") are not real folders
+        .map(|b| RawBookmark {
+            folder: b.folder.filter(|f| !matches!(f.as_str(), "" | "toolbar" | "menu" | "unfiled" | "mobile")),
+            ..b
+        })
+        .collect())
+}
+
+#[cfg(not(feature = "rusqlite"))]
+fn read_firefox_bookmarks(_path: &Path) -> Result<Vec<RawBookmark>, String> {
+    Err("Firefox import reads places.sqlite and requires building with `--features rusqlite`.".to_string())
+}
+
+// --- Utility Functions ---
+
+fn is_web_url(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+/// Turns bookmark titles into unique, shell-friendly tags.
+fn assign_tags(raw: Vec<RawBookmark>) -> Vec<ImportedBookmark> {
+    let mut used = std::collections::HashSet::new();
+    raw.into_iter()
+        .map(|RawBookmark { title, url, folder, modified, last_used }| {
+            let base = match slugify(&title) {
+                s if s.is_empty() => "bookmark".to_string(),
+                s => s,
+            };
+            let mut tag = base.clone();
+            let mut n = 2;
+            while !used.insert(tag.clone()) {
+                tag = format!("{}-{}", base, n);
+                n += 1;
+            }
+            let folder = folder.map(|f| slugify(&f)).filter(|f| !f.is_empty());
+            ImportedBookmark { tag, url, folder, modified, last_used }
+        })
+        .collect()
+}
+
+fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.ends_with('-') && !slug.is_empty() {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
@@ -0,0 +1,196 @@
+// src/commands/bundle.rs
+use std::fs;
+use std::io;
+use std::path::Path;
+use indexmap::IndexMap;
+use serde::{Serialize, Deserialize};
+use crate::commands::aliases::AliasConfig;
+use crate::commands::detect::Browser;
+use crate::commands::error::QuickTabsError;
+use crate::commands::links::{Change, ChangeSet, Link, LinkConfig};
+use crate::commands::merge::{merge_link, MergeStrategy, Merged};
+
+/// Current `version` written by `export`.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Everything `export` writes to move a setup to another machine: links, aliases
+/// and the preferred browser.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Bundle {
+    #[serde(default = "bundle_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub links: Vec<Link>,
+    #[serde(default)]
+    pub aliases: IndexMap<String, String>,
+    /// The saved default browser; its path may not exist on the importing machine
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub browser: Option<Browser>,
+}
+
+fn bundle_version() -> u32 {
+    BUNDLE_VERSION
+}
+
+impl Bundle {
+    pub fn new(links: &LinkConfig, aliases: &AliasConfig, browser: Option<Browser>) -> Self {
+        Bundle {
+            version: BUNDLE_VERSION,
+            links: links.links.clone(),
+            aliases: aliases.aliases.clone(),
+            browser,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, QuickTabsError> {
+        let data = fs::read_to_string(path)?;
+        let bundle: Bundle = serde_json::from_str(&data)
+            .map_err(|e| QuickTabsError::Import(format!("{} is not a Quick Tabs export: {}", path.display(), e)))?;
+        if bundle.version > BUNDLE_VERSION {
+            return Err(QuickTabsError::Import(format!(
+                "{} was exported by a newer version (format {}, this build reads up to {})",
+                path.display(), bundle.version, BUNDLE_VERSION
+            )));
+        }
+        Ok(bundle)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)
+    }
+
+    /// Replaces `links` and `aliases` with the bundle's, keeping its order and
+    /// timestamps exactly. Returns the differences, for `--dry-run` and the summary.
+    pub fn replace_into(&self, links: &mut LinkConfig, aliases: &mut AliasConfig) -> ChangeSet {
+        let mut changes = ChangeSet::default();
+        for old in &links.links {
+            if !self.links.iter().any(|l| l.tag == old.tag) {
+                changes.extend([Change::Removed { kind: "link", tag: old.tag.clone(), value: old.urls_text() }]);
+            }
+        }
+        for new in &self.links {
+            match links.links.iter().find(|l| l.tag == new.tag) {
+                Some(old) if old.urls != new.urls => changes.extend([
+                    Change::Updated { kind: "link", tag: new.tag.clone(), old: old.urls_text(), new: new.urls_text() },
+                ]),
+                Some(_) => {}
+                None => changes.extend([Change::Added { kind: "link", tag: new.tag.clone(), value: new.urls_text() }]),
+            }
+        }
+        for (tag, old) in &aliases.aliases {
+            if !self.aliases.contains_key(tag) {
+                changes.extend([Change::Removed { kind: "alias", tag: tag.clone(), value: old.clone() }]);
+            }
+        }
+        for (tag, new) in &self.aliases {
+            match aliases.aliases.get(tag) {
+                Some(old) if old != new => changes.extend([
+                    Change::Updated { kind: "alias", tag: tag.clone(), old: old.clone(), new: new.clone() },
+                ]),
+                Some(_) => {}
+                None => changes.extend([Change::Added { kind: "alias", tag: tag.clone(), value: new.clone() }]),
+            }
+        }
+
+        links.links = self.links.clone();
+        aliases.aliases = self.aliases.clone();
+        changes
+    }
+
+    /// Adds the bundle's links and aliases whose tags are not saved yet. Saved tags
+    /// are kept; those whose imported value differs are returned as collisions
+    /// (`(kind, tag)`) so they can be reported.
+    pub fn merge_into(&self, links: &mut LinkConfig, aliases: &mut AliasConfig) -> (ChangeSet, Vec<(&'static str, String)>) {
+        let mut changes = ChangeSet::default();
+        let mut collisions = vec![];
+        for link in &self.links {
+            let differs = links.get_urls(&link.tag).is_some_and(|urls| urls != link.urls);
+            let (merged, link_changes) = merge_link(links, link.clone(), MergeStrategy::Skip);
+            changes.extend(link_changes);
+            if merged == Merged::KeptLocal && differs {
+                collisions.push(("link", link.tag.clone()));
+            }
+        }
+        for (tag, target) in &self.aliases {
+            match aliases.aliases.get(tag) {
+                Some(saved) if saved != target => collisions.push(("alias", tag.clone())),
+                Some(_) => {}
+                None => changes.extend(aliases.add_alias(tag.clone(), target.clone())),
+            }
+        }
+        (changes, collisions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (LinkConfig, AliasConfig) {
+        let links = LinkConfig {
+            links: vec![
+                Link { modified: Some(100), last_used: Some(200), ..Link::new("ci", "https://ci.example") },
+                Link::new("docs", "https://docs.rs"),
+            ],
+        };
+        let aliases = AliasConfig {
+            aliases: IndexMap::from([
+                ("d".to_string(), "docs".to_string()),
+                ("gh".to_string(), "https://github.com".to_string()),
+            ]),
+        };
+        (links, aliases)
+    }
+
+    #[test]
+    fn export_then_import_into_empty_config_round_trips() {
+        let (links, aliases) = setup();
+        let browser = Browser { name: "Firefox".to_string(), path: "/usr/bin/firefox".into(), version: Some("128.0".to_string()), ..Default::default() };
+        let path = std::env::temp_dir().join(format!("quick_tabs_bundle_{}.json", std::process::id()));
+        Bundle::new(&links, &aliases, Some(browser)).save(&path).unwrap();
+
+        let bundle = Bundle::load(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        let (mut new_links, mut new_aliases) = (LinkConfig { links: vec![] }, AliasConfig { aliases: IndexMap::new() });
+        let changes = bundle.replace_into(&mut new_links, &mut new_aliases);
+
+        assert_eq!(changes.summary(), "4 added");
+        assert_eq!(serde_json::to_string(&new_links).unwrap(), serde_json::to_string(&links).unwrap());
+        assert_eq!(serde_json::to_string(&new_aliases).unwrap(), serde_json::to_string(&aliases).unwrap());
+        assert_eq!(bundle.browser.unwrap().version.as_deref(), Some("128.0"));
+    }
+
+    #[test]
+    fn merge_keeps_saved_tags_and_reports_collisions() {
+        let (links, aliases) = setup();
+        let mut bundle = Bundle::new(&links, &aliases, None);
+        bundle.links[1].urls = vec!["https://docs.example".to_string()];
+        bundle.links.push(Link::new("new", "https://new.example"));
+        bundle.aliases.insert("gh".to_string(), "https://gitlab.com".to_string());
+        bundle.aliases.insert("n".to_string(), "new".to_string());
+
+        let (mut links, mut aliases) = setup();
+        let (changes, collisions) = bundle.merge_into(&mut links, &mut aliases);
+
+        assert_eq!(changes.summary(), "2 added");
+        assert_eq!(collisions, [("link", "docs".to_string()), ("alias", "gh".to_string())]);
+        assert_eq!(links.get_urls("docs"), Some(vec!["https://docs.rs".to_string()]));
+        assert_eq!(aliases.resolve("gh").as_deref(), Some("https://github.com"));
+        assert_eq!(aliases.resolve("n").as_deref(), Some("new"));
+    }
+
+    #[test]
+    fn replace_drops_tags_missing_from_the_bundle() {
+        let (links, aliases) = setup();
+        let mut bundle = Bundle::new(&links, &aliases, None);
+        bundle.links.remove(0);
+        bundle.aliases.shift_remove("gh");
+
+        let (mut links, mut aliases) = setup();
+        let changes = bundle.replace_into(&mut links, &mut aliases);
+        assert_eq!(changes.summary(), "2 removed");
+        assert_eq!(links.links.len(), 1);
+        assert_eq!(aliases.aliases.len(), 1);
+    }
+}
@@ -0,0 +1,56 @@
+// src/commands/cache.rs
+use std::io;
+use crate::commands::detect;
+use crate::commands::error::QuickTabsError;
+use crate::say;
+
+/// A derived cache that can be thrown away and regenerated from its source.
+/// Both functions return a size in bytes for reporting.
+pub struct CacheEntry {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub clear: fn() -> io::Result<u64>,
+    pub rebuild: fn() -> io::Result<u64>,
+}
+
+/// Every cache `reindex` knows about. New caches register themselves here.
+pub const CACHES: &[CacheEntry] = &[
+    CacheEntry {
+        name: "detection",
+        description: "the detection cache of installed browsers",
+        clear: detect::clear_cache,
+        rebuild: detect::rebuild_cache,
+    },
+];
+
+/// Clears and rebuilds the cache called `what`, or every cache when `None`.
+pub fn reindex(what: Option<&str>) -> Result<(), QuickTabsError> {
+    let selected: Vec<&CacheEntry> = match what {
+        None => CACHES.iter().collect(),
+        Some(name) => {
+            let entry = CACHES.iter().find(|c| c.name.eq_ignore_ascii_case(name)).ok_or_else(|| {
+                let known: Vec<&str> = CACHES.iter().map(|c| c.name).collect();
+                QuickTabsError::NotFound(format!("Cache '{}' (known: {})", name, known.join(", ")))
+            })?;
+            vec![entry]
+        }
+    };
+
+    for cache in selected {
+        say!("🔄 Reindexing {}: {}", cache.name, cache.description);
+        let cleared = (cache.clear)()?;
+        say!("  🧹 Cleared {}", format_size(cleared));
+        let rebuilt = (cache.rebuild)()?;
+        say!("  ✅ Rebuilt {}", format_size(rebuilt));
+    }
+    Ok(())
+}
+
+/// Formats a byte count as B, KB or MB.
+pub fn format_size(bytes: u64) -> String {
+    match bytes {
+        b if b < 1024 => format!("{} B", b),
+        b if b < 1024 * 1024 => format!("{:.1} KB", b as f64 / 1024.0),
+        b => format!("{:.1} MB", b as f64 / (1024.0 * 1024.0)),
+    }
+}
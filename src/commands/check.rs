@@ -0,0 +1,157 @@
+// src/commands/check.rs
+use std::fmt;
+use indexmap::IndexMap;
+use crate::commands::aliases::AliasConfig;
+use crate::commands::error::QuickTabsError;
+use crate::commands::links::LinkConfig;
+use crate::commands::url::{comparable_url, normalize_url, url_host};
+use crate::say;
+
+/// Something `check` found wrong with the saved URLs.
+#[derive(Debug, PartialEq)]
+pub enum Problem {
+    /// Several tags save the same URL (compared with `comparable_url`)
+    Duplicate { url: String, tags: Vec<String> },
+    /// The URL does not parse
+    Malformed { tag: String, url: String, reason: String },
+    /// The server answered with an error status or could not be reached (`--online`)
+    Dead { tag: String, url: String, reason: String },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Duplicate { url, tags } => write!(f, "🔁 {} is saved under {}", url, tags.join(", ")),
+            Problem::Malformed { tag, url, reason } => write!(f, "❌ [{}] {}: {}", tag, url, reason),
+            Problem::Dead { tag, url, reason } => write!(f, "💀 [{}] {}: {}", tag, url, reason),
+        }
+    }
+}
+
+/// Every saved `(tag, url)` pair: each URL of each link, then the aliases whose target
+/// is a literal URL rather than another tag.
+fn saved_urls(links: &LinkConfig, aliases: &AliasConfig) -> Vec<(String, String)> {
+    let link_urls = links.links.iter()
+        .flat_map(|link| link.urls.iter().map(|url| (link.tag.clone(), url.clone())));
+    let alias_urls = aliases.aliases.iter()
+        .filter(|(_, target)| !aliases.aliases.contains_key(*target) && !links.contains(target))
+        .map(|(tag, target)| (tag.clone(), target.clone()));
+    link_urls.chain(alias_urls).collect()
+}
+
+/// Finds duplicate and malformed URLs among the saved links and aliases. Nothing is
+/// fetched; see `probe` for `--online`.
+pub fn find_problems(links: &LinkConfig, aliases: &AliasConfig) -> Vec<Problem> {
+    let saved = saved_urls(links, aliases);
+
+    // Keyed like `dedupe_urls` compares; reported under the earliest saved spelling
+    let mut by_url: IndexMap<String, (&str, Vec<String>)> = IndexMap::new();
+    for (tag, url) in &saved {
+        let (_, tags) = by_url.entry(comparable_url(url)).or_insert_with(|| (url.as_str(), vec![]));
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+    let duplicates = by_url.into_values()
+        .filter(|(_, tags)| tags.len() > 1)
+        .map(|(url, tags)| Problem::Duplicate { url: url.to_string(), tags });
+
+    let malformed = saved.iter().filter_map(|(tag, url)| {
+        parse_error(url).map(|reason| Problem::Malformed { tag: tag.clone(), url: url.clone(), reason })
+    });
+
+    duplicates.chain(malformed).collect()
+}
+
+/// Why `url` would not open as saved, or `None` when it parses. Values starting with a
+/// `$VAR` or `{token}` placeholder are only known at launch time and always pass.
+fn parse_error(url: &str) -> Option<String> {
+    if url.starts_with('$') || url.starts_with('{') {
+        return None;
+    }
+    if url != url.trim() || url.chars().any(char::is_whitespace) {
+        return Some("contains whitespace".to_string());
+    }
+    if let Err(e) = normalize_url(url) {
+        return Some(e);
+    }
+    let is_web = url.starts_with("http://") || url.starts_with("https://");
+    (is_web && url_host(url).is_empty()).then(|| "has no host".to_string())
+}
+
+/// Sends a HEAD request to every saved http(s) URL and reports those answering with a
+/// 4xx/5xx status or not at all. URLs with placeholders are skipped.
+#[cfg(feature = "http")]
+pub fn probe(links: &LinkConfig, aliases: &AliasConfig) -> Result<Vec<Problem>, String> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(std::time::Duration::from_secs(10))
+        .build();
+    Ok(saved_urls(links, aliases).into_iter()
+        .filter(|(_, url)| url.starts_with("http://") || url.starts_with("https://"))
+        .filter(|(_, url)| !url.contains('{') && !url.contains('$'))
+        .filter_map(|(tag, url)| {
+            let reason = match agent.head(&url).call() {
+                Ok(_) => return None,
+                Err(ureq::Error::Status(code, response)) => format!("HTTP {} {}", code, response.status_text()),
+                Err(e) => format!("unreachable: {}", e),
+            };
+            Some(Problem::Dead { tag, url, reason })
+        })
+        .collect())
+}
+
+#[cfg(not(feature = "http"))]
+pub fn probe(_links: &LinkConfig, _aliases: &AliasConfig) -> Result<Vec<Problem>, String> {
+    Err("`check --online` sends HTTP requests and requires building with `--features http`.".to_string())
+}
+
+/// Prints every problem with the saved URLs and fails when there is at least one, so
+/// `quick_tabs check` can gate CI. Read-only: nothing is rewritten.
+pub fn run(links: &LinkConfig, aliases: &AliasConfig, online: bool) -> Result<(), QuickTabsError> {
+    let mut problems = find_problems(links, aliases);
+    if online {
+        problems.extend(probe(links, aliases).map_err(QuickTabsError::Other)?);
+    }
+
+    for problem in &problems {
+        say!("{}", problem);
+    }
+    match problems.len() {
+        0 => {
+            say!("✅ No problems found in {} saved URL(s).", saved_urls(links, aliases).len());
+            Ok(())
+        }
+        n => Err(QuickTabsError::Other(format!("check found {} problem(s)", n))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::links::Link;
+
+    #[test]
+    fn duplicates_and_malformed_urls_are_reported() {
+        let links = LinkConfig {
+            links: vec![
+                Link::new("gh", "https://github.com"),
+                Link::new("hub", "https://GitHub.com/"),
+                Link::new("bad", "https://"),
+                Link::new("home", "$HOME/index.html"),
+            ],
+        };
+        let mut aliases = AliasConfig { aliases: IndexMap::new() };
+        aliases.aliases.insert("code".to_string(), "gh".to_string());
+        aliases.aliases.insert("git".to_string(), "https://github.com".to_string());
+        aliases.aliases.insert("typo".to_string(), "not a url".to_string());
+
+        assert_eq!(find_problems(&links, &aliases), vec![
+            Problem::Duplicate {
+                url: "https://github.com".to_string(),
+                tags: vec!["gh".to_string(), "hub".to_string(), "git".to_string()],
+            },
+            Problem::Malformed { tag: "bad".to_string(), url: "https://".to_string(), reason: "has no host".to_string() },
+            Problem::Malformed { tag: "typo".to_string(), url: "not a url".to_string(), reason: "contains whitespace".to_string() },
+        ]);
+    }
+}
@@ -0,0 +1,16 @@
+// src/commands/clipboard.rs
+use arboard::Clipboard;
+use crate::commands::error::QuickTabsError;
+
+/// Puts `text` on the system clipboard. Fails on headless systems (no X11/Wayland
+/// display) and wherever else the clipboard cannot be opened.
+///
+/// On Linux the clipboard belongs to the process that set it, so the text stays
+/// available after exit only when a clipboard manager takes it over.
+pub fn copy_to_clipboard(text: &str) -> Result<(), QuickTabsError> {
+    let unavailable = |e: arboard::Error| QuickTabsError::Other(format!("Could not access the clipboard: {}", e));
+    Clipboard::new()
+        .map_err(unavailable)?
+        .set_text(text)
+        .map_err(unavailable)
+}
@@ -0,0 +1,648 @@
+// src/commands/config.rs
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::env;
+use std::sync::OnceLock;
+use indexmap::IndexMap;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use crate::commands::error::QuickTabsError;
+use crate::commands::detect::{set_browser, DetectOptions};
+use crate::commands::links::{unix_now, Change, ChangeSet, LaunchMode, Link, LinkConfig};
+use crate::commands::merge::{merge_link, MergeStrategy};
+use crate::commands::url::url_host;
+use crate::commands::output::is_plain;
+use crate::{say, esay};
+
+/// Colors accepted for group metadata. These mirror Chrome's tab-group palette so the
+/// values can be handed to the browser unchanged once tab groups are supported.
+pub const GROUP_COLORS: &[&str] = &[
+    "grey", "blue", "red", "yellow", "green", "pink", "purple", "cyan", "orange",
+];
+
+// --- Data Structures ---
+
+/// Display metadata attached to a link group.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GroupMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+}
+
+/// Application-wide settings, stored next to the browser config.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AppConfig {
+    #[serde(default)]
+    pub group_meta: HashMap<String, GroupMeta>,
+    /// Drop links/aliases with an already-saved URL whenever a mutation is saved
+    #[serde(default)]
+    pub dedupe_on_save: bool,
+    /// Shell command run after each successful launch (see `hooks::run_post_launch_hook`)
+    #[serde(default)]
+    pub post_launch_hook: Option<String>,
+    /// Browser names in order of preference; decides auto-selection and picker order
+    #[serde(default)]
+    pub browser_preference: Vec<String>,
+    /// Always check URLs against the blocklist, as if `--safe-launch` were passed
+    #[serde(default)]
+    pub safe_launch: bool,
+    /// Upper bound on threads used while probing for browsers
+    #[serde(default = "default_detect_threads")]
+    pub detect_threads: usize,
+    /// Browser profile each group opens in (`--profile-directory` for Chromium)
+    #[serde(default)]
+    pub group_profiles: HashMap<String, String>,
+    /// Launch mode per browser name (`"normal"` or `"private"`), used when neither
+    /// `--incognito` nor `--normal` is given
+    #[serde(default)]
+    pub browser_default_mode: HashMap<String, LaunchMode>,
+    /// Cap on links opened per minute across all invocations; unlimited when unset
+    #[serde(default)]
+    pub max_opens_per_minute: Option<u32>,
+    /// Seconds a detection run is reused before browsers are scanned again (0 disables)
+    #[serde(default = "default_detect_cache_ttl_secs")]
+    pub detect_cache_ttl_secs: u64,
+    /// Ask before `open-all-links`/`open-all-aliases` opens more than this many URLs (0 never asks)
+    #[serde(default = "default_confirm_open_above")]
+    pub confirm_open_above: usize,
+    /// Search tag -> URL with a `{}` for the query, launched as `launch "!tag words"`
+    #[serde(default)]
+    pub searches: IndexMap<String, String>,
+}
+
+fn default_detect_threads() -> usize {
+    4
+}
+
+fn default_detect_cache_ttl_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_confirm_open_above() -> usize {
+    10
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            group_meta: HashMap::new(),
+            dedupe_on_save: false,
+            post_launch_hook: None,
+            browser_preference: vec![],
+            safe_launch: false,
+            detect_threads: default_detect_threads(),
+            group_profiles: HashMap::new(),
+            browser_default_mode: HashMap::new(),
+            max_opens_per_minute: None,
+            detect_cache_ttl_secs: default_detect_cache_ttl_secs(),
+            confirm_open_above: default_confirm_open_above(),
+            searches: IndexMap::new(),
+        }
+    }
+}
+
+// --- AppConfig Implementation ---
+
+impl AppConfig {
+    pub fn load(path: &Path) -> Self {
+        if path.exists() {
+            match fs::read_to_string(path) {
+                Ok(data) => serde_json::from_str(&data).unwrap_or_else(|e| {
+                    esay!("⚠️ Failed to parse settings {}: {}", path.display(), e);
+                    AppConfig::default()
+                }),
+                Err(e) => {
+                    esay!("⚠️ Failed to read settings {}: {}", path.display(), e);
+                    AppConfig::default()
+                }
+            }
+        } else {
+            AppConfig::default()
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(&self)?;
+        write_atomic(path, json)
+    }
+
+    /// Loads the settings file, or defaults when no config directory is available.
+    pub fn load_or_default() -> Self {
+        settings_path().map(|p| AppConfig::load(&p)).unwrap_or_default()
+    }
+
+    /// Returns a single setting as JSON.
+    pub fn get_value(&self, key: &str) -> Option<Value> {
+        serde_json::to_value(self).ok()?.get(key).cloned()
+    }
+
+    /// Sets a single setting from its command-line form. `raw` is parsed as JSON
+    /// (`true`, `5`, `["a","b"]`) and otherwise taken as a plain string.
+    pub fn set_value(&mut self, key: &str, raw: &str) -> Result<ChangeSet, String> {
+        let mut json = serde_json::to_value(&*self).map_err(|e| e.to_string())?;
+        let fields = json.as_object_mut().ok_or("settings are not an object")?;
+        if !fields.contains_key(key) {
+            let mut known: Vec<&String> = fields.keys().collect();
+            known.sort();
+            let known: Vec<&str> = known.into_iter().map(|k| k.as_str()).collect();
+            return Err(format!("unknown setting '{}' (known: {})", key, known.join(", ")));
+        }
+
+        let value: Value = serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()));
+        let new = value.to_string();
+        let old = fields.insert(key.to_string(), value).map(|v| v.to_string()).unwrap_or_default();
+        *self = serde_json::from_value(json).map_err(|e| format!("invalid value for '{}': {}", key, e))?;
+        Ok(Change::Updated { kind: "setting", tag: key.to_string(), old, new }.into())
+    }
+}
+
+/// Gets the settings path (~/.config/quick_tabs/settings.json)
+pub fn settings_path() -> Result<PathBuf, QuickTabsError> {
+    Ok(app_config_dir()?.join("settings.json"))
+}
+
+// --- State File Locations ---
+
+/// Where links, aliases and groups are saved. Build it with `config_paths()` so
+/// every command agrees on the locations.
+#[derive(Debug, Clone)]
+pub struct ConfigPaths {
+    /// ~/.config/quick_tabs/links.json
+    pub links: PathBuf,
+    /// ~/.config/quick_tabs/aliases.json
+    pub aliases: PathBuf,
+    /// ~/.config/quick_tabs/groups.json
+    pub groups: PathBuf,
+}
+
+/// Directory given with `--config-dir`; holds all state for the rest of the run.
+static CONFIG_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Keeps all state (links, aliases, groups, the browser config, settings and caches)
+/// under `dir` for the rest of the run (`--config-dir`). Only the first call counts.
+pub fn set_config_dir(dir: PathBuf) {
+    let _ = CONFIG_DIR_OVERRIDE.set(dir);
+}
+
+/// Resolves the state file locations in `app_config_dir()`, next to the browser
+/// config and settings, so all state lives in one directory.
+///
+/// Older builds kept dotfiles in the home directory (`~/.quick_tabs_links.json`).
+/// Such files are moved into place the first time they are found. With
+/// `--config-dir`, only dotfiles in that directory are adopted. Files left in other
+/// directories are only imported on request, by `migrate_dir`.
+pub fn config_paths() -> Result<ConfigPaths, QuickTabsError> {
+    let paths = state_paths_in(&app_config_dir()?);
+    let legacy_dir = match CONFIG_DIR_OVERRIDE.get() {
+        Some(dir) => dir.clone(),
+        None => dirs::home_dir().ok_or(QuickTabsError::NoConfigDir)?,
+    };
+    adopt_legacy_files(&legacy_dir, &paths);
+    Ok(paths)
+}
+
+/// Imports what older versions left in `dir` (`migrate-launcher`): the old
+/// interactive launcher's `links.txt` and `quick_tabs_config.json`, and the state
+/// dotfiles builds run without `$HOME` wrote to their working directory. Returns
+/// whether anything was found.
+pub fn migrate_dir(dir: &Path, paths: &ConfigPaths) -> bool {
+    let moved = adopt_legacy_files(dir, paths);
+    let links = adopt_launcher_links(dir, &paths.links);
+    let browser = adopt_launcher_browser(dir);
+    moved || links || browser
+}
+
+fn state_paths_in(dir: &Path) -> ConfigPaths {
+    ConfigPaths {
+        links: dir.join("links.json"),
+        aliases: dir.join("aliases.json"),
+        groups: dir.join("groups.json"),
+    }
+}
+
+/// The dotfiles older builds kept in the home directory, paired with where they live now.
+fn legacy_state_files<'a>(dir: &Path, paths: &'a ConfigPaths) -> [(PathBuf, &'a Path); 3] {
+    [
+        (dir.join(".quick_tabs_links.json"), &paths.links),
+        (dir.join(".quick_tabs_aliases.json"), &paths.aliases),
+        (dir.join(".quick_tabs_groups.json"), &paths.groups),
+    ]
+}
+
+/// Gets the browser config path (~/.config/quick_tabs/browser_config.json)
+pub fn browser_config_path() -> Result<PathBuf, QuickTabsError> {
+    Ok(app_config_dir()?.join("browser_config.json"))
+}
+
+/// Moves the state dotfiles older builds left in `dir` (and their backups) to their
+/// place in the config directory, unless a file is there already. Prints a notice per
+/// file moved, so it shows once. Returns whether any file was moved.
+fn adopt_legacy_files(dir: &Path, paths: &ConfigPaths) -> bool {
+    let mut moved = false;
+    for (legacy, path) in legacy_state_files(dir, paths) {
+        if path.exists() || !legacy.is_file() || fs::canonicalize(&legacy).ok() == fs::canonicalize(path).ok() {
+            continue;
+        }
+        moved = true;
+        match move_file(&legacy, path) {
+            Ok(()) => say!("📦 Moved {} to {} (Quick Tabs now keeps all its files in one directory)",
+                legacy.display(), path.display()),
+            Err(e) => {
+                esay!("⚠️ Could not move {} to {}: {}", legacy.display(), path.display(), e);
+                continue;
+            }
+        }
+        for slot in 1..=BACKUP_SLOTS {
+            let backup = backup_path(&legacy, slot);
+            if backup.is_file() {
+                let _ = move_file(&backup, &backup_path(path, slot));
+            }
+        }
+    }
+    moved
+}
+
+/// Renames `from` to `to`; rename fails across filesystems, so falls back to copy + remove.
+fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    fs::rename(from, to).or_else(|_| fs::copy(from, to).and_then(|_| fs::remove_file(from)))
+}
+
+/// URLs saved one per line by the old interactive launcher, in the directory it ran in.
+const LAUNCHER_LINKS_FILE: &str = "links.txt";
+/// The old interactive launcher's settings: `{"preferred": "<browser path>"}`.
+const LAUNCHER_CONFIG_FILE: &str = "quick_tabs_config.json";
+
+#[derive(Deserialize)]
+struct LauncherConfig {
+    preferred: Option<String>,
+}
+
+/// Imports the URLs the old interactive launcher kept in `dir/links.txt` as links
+/// tagged by host (`github.com`, `github.com-2`, ...), then renames the file to
+/// `links.txt.migrated`. URLs already saved are skipped. A file holding anything but
+/// URLs was not written by the launcher and is left alone. Returns whether a launcher
+/// file was found.
+fn adopt_launcher_links(dir: &Path, links_path: &Path) -> bool {
+    let legacy = dir.join(LAUNCHER_LINKS_FILE);
+    let Ok(text) = fs::read_to_string(&legacy) else {
+        return false;
+    };
+    let urls: Vec<&str> = text.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+    if urls.is_empty() || !urls.iter().all(|url| url.contains("://")) {
+        return false;
+    }
+
+    let mut links = match LinkConfig::load(links_path) {
+        Ok(links) => links,
+        Err(e) => {
+            esay!("⚠️ Could not import {}: {}", legacy.display(), e);
+            return true;
+        }
+    };
+    let mut imported = 0;
+    for url in urls {
+        if links.links.iter().any(|l| l.urls.iter().any(|u| u == url)) {
+            continue;
+        }
+        let host = url_host(url);
+        let tag = match host.strip_prefix("www.").unwrap_or(host) {
+            "" => "link",
+            tag => tag,
+        };
+        let link = Link { modified: Some(unix_now()), ..Link::new(tag, url) };
+        merge_link(&mut links, link, MergeStrategy::Rename);
+        imported += 1;
+    }
+
+    let migrated = legacy.with_extension("txt.migrated");
+    match links.save(links_path).and_then(|_| fs::rename(&legacy, &migrated)) {
+        Ok(()) => say!("📦 Imported {} link(s) from the old launcher's {}", imported, legacy.display()),
+        Err(e) => esay!("⚠️ Could not import {}: {}", legacy.display(), e),
+    }
+    true
+}
+
+/// Saves the old interactive launcher's preferred browser from
+/// `dir/quick_tabs_config.json` as the default browser, unless one is saved already,
+/// then renames the file to `quick_tabs_config.json.migrated`. Returns whether a
+/// launcher config was found.
+fn adopt_launcher_browser(dir: &Path) -> bool {
+    let legacy = dir.join(LAUNCHER_CONFIG_FILE);
+    let Some(config) = fs::read_to_string(&legacy).ok()
+        .and_then(|text| serde_json::from_str::<LauncherConfig>(&text).ok())
+    else {
+        return false;
+    };
+
+    let saved = browser_config_path().is_ok_and(|path| path.exists());
+    if let Some(preferred) = config.preferred.filter(|_| !saved) {
+        match set_browser(&preferred, &DetectOptions::from_config(&AppConfig::load_or_default())) {
+            Ok(browser) => say!("📦 Saved the old launcher's browser as the default: {}", browser.path.display()),
+            Err(e) => esay!("⚠️ Could not adopt the old launcher's browser {}: {}", preferred, e),
+        }
+    }
+    if let Err(e) = fs::rename(&legacy, legacy.with_extension("json.migrated")) {
+        esay!("⚠️ Could not rename {}: {}", legacy.display(), e);
+    }
+    true
+}
+
+/// Replaces `path` with `contents` all at once: writes a temporary file next to it
+/// and renames it over the target. A crash or a concurrent run can then never leave
+/// a half-written file behind; readers see either the old contents or the new.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let name = path.file_name().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a file path"))?;
+    // Unique per process, so two runs saving at once never share a temp file
+    let tmp = path.with_file_name(format!(".{}.{}.tmp", name.to_string_lossy(), std::process::id()));
+    let written = fs::File::create(&tmp).and_then(|mut file| {
+        io::Write::write_all(&mut file, contents.as_ref())?;
+        file.sync_all()
+    });
+    match written.and_then(|_| fs::rename(&tmp, path)) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            let _ = fs::remove_file(&tmp);
+            Err(e)
+        }
+    }
+}
+
+/// How many earlier versions of `links.json` and `aliases.json` are kept.
+pub const BACKUP_SLOTS: usize = 3;
+
+/// Where backup `slot` (1 is the most recent) of `path` is kept, e.g. `links.json.bak.1`.
+pub fn backup_path(path: &Path, slot: usize) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".bak.{}", slot));
+    path.with_file_name(name)
+}
+
+/// Copies the current `path` to backup slot 1 before it is overwritten, shifting older
+/// backups up a slot and dropping the one past `BACKUP_SLOTS`. Nothing happens when
+/// `path` does not exist yet.
+pub fn rotate_backups(path: &Path) -> io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    for slot in (1..BACKUP_SLOTS).rev() {
+        let from = backup_path(path, slot);
+        if from.exists() {
+            fs::rename(&from, backup_path(path, slot + 1))?;
+        }
+    }
+    fs::copy(path, backup_path(path, 1))?;
+    Ok(())
+}
+
+/// Moves a config file that does not parse out of the way, to `<file>.corrupt` (or
+/// `<file>.corrupt.2`, ... when that is taken), so the next save cannot overwrite
+/// it. Returns where it went.
+pub fn set_aside_corrupt(path: &Path) -> io::Result<PathBuf> {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".corrupt");
+    let first = path.with_file_name(&name);
+    let target = std::iter::once(first.clone())
+        .chain((2..).map(|n| {
+            let mut numbered = name.clone();
+            numbered.push(format!(".{}", n));
+            path.with_file_name(numbered)
+        }))
+        .find(|candidate| !candidate.exists())
+        .unwrap_or(first);
+    fs::rename(path, &target)?;
+    Ok(target)
+}
+
+/// Warns that the `kind` config at `path` does not parse and moves it aside with
+/// `set_aside_corrupt`; the caller carries on with an empty config.
+pub fn report_corrupt(kind: &str, path: &Path, error: &serde_json::Error) {
+    match set_aside_corrupt(path) {
+        Ok(moved) => esay!(
+            "⚠️ Failed to parse {} config {}: {}. Moved it to {} so it is not overwritten; fix it and move it back.",
+            kind, path.display(), error, moved.display()
+        ),
+        Err(e) => esay!("⚠️ Failed to parse {} config {}: {} (could not move it aside: {})", kind, path.display(), error, e),
+    }
+}
+
+/// Puts backup `slot` of `path` back in place. The file being replaced is backed up
+/// first, so a restore can itself be undone with `restore`. `validate` rejects a
+/// backup that does not parse before anything is touched.
+pub fn restore_backup(path: &Path, slot: usize, validate: impl Fn(&str) -> Result<(), String>) -> Result<PathBuf, QuickTabsError> {
+    if !(1..=BACKUP_SLOTS).contains(&slot) {
+        return Err(QuickTabsError::Other(format!("Backup slot must be between 1 and {}", BACKUP_SLOTS)));
+    }
+    let backup = backup_path(path, slot);
+    let contents = match fs::read_to_string(&backup) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Err(QuickTabsError::NotFound(format!("Backup {}", backup.display())));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    validate(&contents).map_err(|e| QuickTabsError::Other(format!("Backup {} does not parse: {}", backup.display(), e)))?;
+    rotate_backups(path)?;
+    write_atomic(path, contents)?;
+    Ok(backup)
+}
+
+// --- Config Directory Resolution ---
+
+/// Gets (and creates) the application config directory, e.g. ~/.config/quick_tabs.
+///
+/// Tries, in order: `--config-dir`, the `QUICK_TABS_CONFIG_DIR` override,
+/// `dirs::config_dir()`, `$XDG_CONFIG_HOME` and `$HOME/.config`. Fails rather than
+/// falling back to the current directory, so state never ends up scattered wherever
+/// the tool was run.
+pub fn app_config_dir() -> Result<PathBuf, QuickTabsError> {
+    let dir = resolve_config_dir(
+        CONFIG_DIR_OVERRIDE.get().cloned().or_else(|| env::var_os("QUICK_TABS_CONFIG_DIR").map(PathBuf::from)),
+        dirs::config_dir(),
+        env::var_os("XDG_CONFIG_HOME").map(PathBuf::from),
+        dirs::home_dir(),
+    )?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn resolve_config_dir(
+    override_dir: Option<PathBuf>,
+    config_dir: Option<PathBuf>,
+    xdg_config_home: Option<PathBuf>,
+    home: Option<PathBuf>,
+) -> Result<PathBuf, QuickTabsError> {
+    if let Some(dir) = override_dir.filter(|d| !d.as_os_str().is_empty()) {
+        return Ok(dir);
+    }
+
+    config_dir
+        .or_else(|| xdg_config_home.filter(|d| d.is_absolute()))
+        .or_else(|| home.map(|h| h.join(".config")))
+        .map(|dir| dir.join("quick_tabs"))
+        .ok_or(QuickTabsError::NoConfigDir)
+}
+
+// --- Colors ---
+
+/// Clap value parser: accepts only colors from `GROUP_COLORS` (case-insensitive).
+pub fn parse_group_color(value: &str) -> Result<String, String> {
+    let color = value.to_lowercase();
+    if GROUP_COLORS.contains(&color.as_str()) {
+        Ok(color)
+    } else {
+        Err(format!("unknown color '{}' (expected one of: {})", value, GROUP_COLORS.join(", ")))
+    }
+}
+
+/// Wraps `text` in the ANSI escape sequence for a group color.
+pub fn paint(text: &str, color: &str) -> String {
+    if is_plain() {
+        return text.to_string();
+    }
+    let code = match color {
+        "grey" => 245,
+        "blue" => 33,
+        "red" => 196,
+        "yellow" => 220,
+        "green" => 40,
+        "pink" => 213,
+        "purple" => 135,
+        "cyan" => 51,
+        "orange" => 208,
+        _ => return text.to_string(),
+    };
+    format!("\x1b[38;5;{}m{}\x1b[0m", code, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saves_keep_the_last_three_versions() {
+        let dir = env::temp_dir().join(format!("quick_tabs_backups_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("links.json");
+
+        rotate_backups(&path).unwrap();
+        assert!(!backup_path(&path, 1).exists());
+
+        for version in 1..=5 {
+            rotate_backups(&path).unwrap();
+            fs::write(&path, version.to_string()).unwrap();
+        }
+        let read = |p: PathBuf| fs::read_to_string(p).unwrap();
+        assert_eq!(read(backup_path(&path, 1)), "4");
+        assert_eq!(read(backup_path(&path, 3)), "2");
+        assert!(!backup_path(&path, 4).exists());
+
+        restore_backup(&path, 3, |_| Ok(())).unwrap();
+        assert_eq!(read(path.clone()), "2");
+        assert_eq!(read(backup_path(&path, 1)), "5");
+        assert!(restore_backup(&path, 1, |_| Err("bad".to_string())).is_err());
+        assert_eq!(read(path.clone()), "2");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn config_dir_fails_when_nothing_is_available() {
+        let result = resolve_config_dir(None, None, None, None);
+        assert!(matches!(result, Err(QuickTabsError::NoConfigDir)));
+    }
+
+    #[test]
+    fn config_dir_override_wins() {
+        let dir = resolve_config_dir(Some(PathBuf::from("/tmp/qt")), Some(PathBuf::from("/cfg")), None, None);
+        assert_eq!(dir.unwrap(), PathBuf::from("/tmp/qt"));
+    }
+
+    #[test]
+    fn config_dir_falls_back_to_xdg_then_home() {
+        let xdg = resolve_config_dir(None, None, Some(PathBuf::from("/xdg")), Some(PathBuf::from("/home/u")));
+        assert_eq!(xdg.unwrap(), PathBuf::from("/xdg/quick_tabs"));
+
+        let home = resolve_config_dir(None, None, None, Some(PathBuf::from("/home/u")));
+        assert_eq!(home.unwrap(), PathBuf::from("/home/u/.config/quick_tabs"));
+    }
+
+    #[test]
+    fn home_dotfiles_move_to_the_config_dir() {
+        let home = env::temp_dir().join(format!("quick_tabs_legacy_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&home);
+        let config = home.join(".config").join("quick_tabs");
+        fs::create_dir_all(&config).unwrap();
+        fs::write(home.join(".quick_tabs_links.json"), r#"{"links":[]}"#).unwrap();
+        fs::write(backup_path(&home.join(".quick_tabs_links.json"), 1), "old").unwrap();
+        fs::write(home.join(".quick_tabs_aliases.json"), "{}").unwrap();
+        fs::write(config.join("aliases.json"), r#"{"a":"b"}"#).unwrap();
+
+        let paths = state_paths_in(&config);
+        adopt_legacy_files(&home, &paths);
+        assert_eq!(fs::read_to_string(&paths.links).unwrap(), r#"{"links":[]}"#);
+        assert_eq!(fs::read_to_string(backup_path(&paths.links, 1)).unwrap(), "old");
+        assert!(!home.join(".quick_tabs_links.json").exists());
+
+        // Already migrated: the newer file wins and the dotfile is left alone
+        assert_eq!(fs::read_to_string(&paths.aliases).unwrap(), r#"{"a":"b"}"#);
+        assert!(home.join(".quick_tabs_aliases.json").exists());
+        let _ = fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn launcher_links_are_imported_once() {
+        let dir = env::temp_dir().join(format!("quick_tabs_launcher_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let links_path = dir.join("links.json");
+        let mut links = LinkConfig { links: vec![Link::new("docs", "https://docs.rs")] };
+        links.save(&links_path).unwrap();
+        fs::write(dir.join("links.txt"), "https://docs.rs\nhttps://www.github.com\n\nhttps://github.com/rust-lang\n").unwrap();
+
+        assert!(adopt_launcher_links(&dir, &links_path));
+        links = LinkConfig::load(&links_path).unwrap();
+        let tags: Vec<&str> = links.links.iter().map(|l| l.tag.as_str()).collect();
+        assert_eq!(tags, ["docs", "github.com", "github.com-2"]);
+        assert!(!dir.join("links.txt").exists());
+        assert!(dir.join("links.txt.migrated").exists());
+
+        // Not a launcher file: left where it is
+        fs::write(dir.join("links.txt"), "todo: read the docs\n").unwrap();
+        assert!(!adopt_launcher_links(&dir, &links_path));
+        assert!(dir.join("links.txt").exists());
+        assert_eq!(LinkConfig::load(&links_path).unwrap().links.len(), 3);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn interrupted_saves_keep_the_old_config() {
+        let dir = env::temp_dir().join(format!("quick_tabs_atomic_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("links.json");
+        let mut links = LinkConfig { links: vec![] };
+        links.add_link(Link::new("a", "https://a.example"), false).unwrap();
+        links.save(&path).unwrap();
+
+        // A crash mid-write leaves only the temp file truncated
+        let tmp = dir.join(format!(".links.json.{}.tmp", std::process::id()));
+        fs::write(&tmp, r#"{"links":[{"tag":"b","#).unwrap();
+        assert_eq!(LinkConfig::load(&path).unwrap().get_urls("a"), Some(vec!["https://a.example".to_string()]));
+
+        // A write that fails outright leaves the target untouched
+        fs::remove_file(&tmp).unwrap();
+        fs::create_dir(&tmp).unwrap();
+        links.add_link(Link::new("b", "https://b.example"), false).unwrap();
+        assert!(links.save(&path).is_err());
+        let kept = LinkConfig::load(&path).unwrap();
+        assert_eq!(kept.links.len(), 1);
+        assert_eq!(kept.get_urls("a"), Some(vec!["https://a.example".to_string()]));
+
+        fs::remove_dir(&tmp).unwrap();
+        links.save(&path).unwrap();
+        assert_eq!(LinkConfig::load(&path).unwrap().links.len(), 2);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,17 @@
+// src/commands/csv.rs
+use std::io::{self, Write};
+
+/// Writes one CSV record (RFC 4180). Fields containing commas, quotes or line breaks
+/// are quoted, with embedded quotes doubled.
+pub fn write_row<W: Write>(out: &mut W, fields: &[&str]) -> io::Result<()> {
+    let row: Vec<String> = fields.iter().map(|f| escape(f)).collect();
+    writeln!(out, "{}", row.join(","))
+}
+
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,2053 @@
+// src/commands/detect.rs
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::fs;
+use std::env;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use clap::ValueEnum;
+use rayon::prelude::*;
+use serde::{Serialize, Deserialize};
+use which::which;
+use crate::commands::config::{app_config_dir, browser_config_path, write_atomic, AppConfig};
+use crate::commands::error::QuickTabsError;
+use crate::commands::links::unix_now;
+use crate::commands::version::Version;
+use crate::{esay, say, verbose};
+
+#[cfg(target_os = "windows")]
+use winreg::enums::*;
+#[cfg(target_os = "windows")]
+use winreg::RegKey;
+
+// --- Data Structures ---
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Browser {
+    pub name: String,
+    pub path: PathBuf,
+    pub version: Option<String>,
+    /// Arguments added to every launch, before the URLs (`set-browser-flags`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_args: Vec<String>,
+    /// Used instead of the built-in private-mode flags when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_args: Option<Vec<String>>,
+    /// How the browser was installed, which decides how it is started
+    #[serde(default, skip_serializing_if = "InstallKind::is_native")]
+    pub kind: InstallKind,
+}
+
+/// How a browser is packaged. Snap and Flatpak browsers are started through their
+/// package manager (`snap run <name>`, `flatpak run <app-id>`) rather than by path.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallKind {
+    #[default]
+    Native,
+    /// Under `/snap/bin`
+    Snap,
+    /// An `exports/bin/<app-id>` wrapper of a system or user Flatpak installation
+    Flatpak,
+}
+
+impl InstallKind {
+    /// Recognizes Snap and Flatpak installs by where their launcher lives.
+    pub fn of(path: &Path) -> Self {
+        if path.starts_with("/snap/bin") {
+            InstallKind::Snap
+        } else if path.parent().is_some_and(|dir| dir.ends_with("flatpak/exports/bin")) {
+            InstallKind::Flatpak
+        } else {
+            InstallKind::Native
+        }
+    }
+
+    fn is_native(&self) -> bool {
+        *self == InstallKind::Native
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Config {
+    browser: Browser,
+}
+
+/// Settings that shape detection, taken from `AppConfig` and command-line overrides.
+#[derive(Debug, Clone)]
+pub struct DetectOptions {
+    /// Browser names in order of preference (`AppConfig.browser_preference`)
+    pub preference: Vec<String>,
+    /// Maximum number of probing threads; at least one is always used
+    pub threads: usize,
+    /// Keep only the newest install of each browser (`detect --latest-only`)
+    pub latest_only: bool,
+    /// Select afresh and save under this name instead of as the default (`detect --save-as`)
+    pub save_as: Option<String>,
+    /// How long a detection run is reused (`AppConfig.detect_cache_ttl_secs`); zero disables the cache
+    pub cache_ttl: Duration,
+    /// Ignore the saved browser and the detection cache and rescan (`detect --refresh`)
+    pub refresh: bool,
+    /// Also search the install roots recursively for browsers in unusual locations (`detect --deep`)
+    pub deep: bool,
+    /// Extra directories to search for portable or unpacked browsers (`detect --search-path`)
+    pub search_paths: Vec<PathBuf>,
+}
+
+impl DetectOptions {
+    pub fn from_config(cfg: &AppConfig) -> Self {
+        DetectOptions {
+            preference: cfg.browser_preference.clone(),
+            threads: cfg.detect_threads,
+            latest_only: false,
+            save_as: None,
+            cache_ttl: Duration::from_secs(cfg.detect_cache_ttl_secs),
+            refresh: false,
+            deep: false,
+            search_paths: vec![],
+        }
+    }
+}
+
+/// One location checked while looking for a browser.
+#[derive(Debug, Serialize)]
+pub struct CandidatePath {
+    pub path: PathBuf,
+    pub exists: bool,
+}
+
+/// Every location checked for one known browser, for `detect --debug-paths`.
+#[derive(Debug, Serialize)]
+pub struct ProbeReport {
+    pub name: String,
+    pub executable: String,
+    /// Result of the PATH lookup
+    pub on_path: Option<PathBuf>,
+    pub candidates: Vec<CandidatePath>,
+    /// The path detection would use for this browser (PATH first, then candidates)
+    pub chosen: Option<PathBuf>,
+}
+
+/// How long a `--version` probe may run before it is killed.
+const VERSION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Browsers probed by name: (display name, executable base name)
+const KNOWN_BROWSERS: &[(&str, &str)] = &[
+    ("Google Chrome", "chrome"),
+    ("Mozilla Firefox", "firefox"),
+    ("Brave", "brave"),
+    ("Microsoft Edge", "msedge"),
+    ("Opera", "opera"),
+    ("Chromium", "chromium"),
+    ("Vivaldi", "vivaldi"),
+    ("LibreWolf", "librewolf"),
+    ("Waterfox", "waterfox"),
+    ("Zen Browser", "zen"),
+    ("Tor Browser", "tor-browser"),
+    // Linux ships release channels as separate executables; on Windows and macOS the
+    // channel is only visible in the install path (see `common_paths`, `channel_of`)
+    ("Google Chrome Beta", "google-chrome-beta"),
+    ("Google Chrome Dev", "google-chrome-unstable"),
+    ("Firefox Nightly", "firefox-nightly"),
+    ("Firefox Developer Edition", "firefox-developer-edition"),
+    ("Microsoft Edge Beta", "microsoft-edge-beta"),
+    ("Microsoft Edge Dev", "microsoft-edge-dev"),
+];
+
+// --- Public Entry Point ---
+
+/// Loads the saved browser, or detects (`detect_all`) and selects one
+/// (`interactive_select`). With `save_as` set, always selects and saves the choice as
+/// that named config, leaving the default alone.
+pub fn run(options: &DetectOptions) -> Option<Browser> {
+    let config_path = match &options.save_as {
+        Some(name) => named_config_path(name),
+        None => get_app_config_path(),
+    };
+    let config_path = match config_path {
+        Ok(path) => Some(path),
+        Err(e) => {
+            esay!("⚠️ {} The selected browser will not be saved.", e);
+            None
+        }
+    };
+
+    if options.save_as.is_none() && !options.refresh {
+        if let Some(browser) = config_path.as_deref().and_then(load_saved_browser) {
+            say!("⚡ Using saved browser: {}", browser.path.display());
+            return Some(browser);
+        }
+    }
+
+    let detected = detect_all(options);
+    write_outputs(&detected).ok(); // Write full list to CWD
+
+    let selected = interactive_select(detected, &options.preference);
+    match (selected, &config_path) {
+        (Some(b), Some(path)) => {
+            let b = with_saved_flags(path, b);
+            save_browser(path, &b);
+            Some(b)
+        }
+        (selected, _) => selected,
+    }
+}
+
+/// Picks the browser to use from `detected` (in preference order): the only one, or
+/// the first when it is a preferred browser; otherwise the user is asked, with manual
+/// entry when nothing was detected. `None` when no browser was chosen.
+pub fn interactive_select(mut detected: Vec<Browser>, preference: &[String]) -> Option<Browser> {
+    match detected.len() {
+        0 => {
+            say!("⚠️ No browsers detected. Please enter manually.");
+            manual_select()
+        }
+        1 => {
+            let b = detected.remove(0);
+            say!("✅ Auto-selected: {}", b.name);
+            Some(b)
+        }
+        // The list is sorted by preference, so a listed browser is always first
+        _ if preference_rank(&detected[0], preference).is_some() => {
+            let b = detected.remove(0);
+            say!("✅ Auto-selected preferred browser: {}", b.name);
+            Some(b)
+        }
+        _ => choose_browser_interactively(&mut detected),
+    }
+}
+
+// --- Detection Logic ---
+
+/// Detected browsers, from the cache when it is fresh, in preference order. Like
+/// `detect_browsers`, but reports progress, refreshes the cache and prints the list.
+pub fn detect_all(options: &DetectOptions) -> Vec<Browser> {
+    let scanned = match load_detection_cache(options) {
+        Some((cached, age)) => {
+            say!("⚡ Using browsers detected {} ago (`detect --refresh` to rescan)", format_age(age));
+            cached
+        }
+        None => {
+            say!("🔍 Searching for installed browsers...");
+            if options.deep {
+                say!("🔍 Searching install directories (up to {} levels deep)...", DEEP_MAX_DEPTH);
+            }
+            let found = scan_browsers(options);
+            if let Err(e) = save_detection_cache(&found) {
+                esay!("⚠️ Could not cache detected browsers: {}", e);
+            }
+            found
+        }
+    };
+    let unique_found = arrange(scanned.clone(), options);
+    if options.latest_only {
+        report_skipped(&scanned, &unique_found);
+    }
+
+    if !unique_found.is_empty() {
+        say!("✨ Found {} unique browsers:", unique_found.len());
+        for (i, b) in unique_found.iter().enumerate() {
+            let ver = b.version.clone().unwrap_or_else(|| "unknown".to_string());
+            say!("  [{}] {} (version: {}, path: {})", i + 1, b.name, ver, b.path.display());
+        }
+    } else {
+        say!("⚠️ Did not find any known browsers.");
+    }
+
+    unique_found
+}
+
+/// The installed browsers, from the cache when it is fresh, otherwise a scan, in
+/// preference order. Prints nothing to stdout, never prompts, and writes no file (not
+/// even the cache), so library users and reports such as `doctor` can call it freely.
+pub fn detect_browsers(options: &DetectOptions) -> Vec<Browser> {
+    let found = load_detection_cache(options)
+        .map(|(cached, _)| cached)
+        .unwrap_or_else(|| scan_browsers(options));
+    arrange(found, options)
+}
+
+/// Applies `--latest-only` and the preference order to a detection run.
+fn arrange(mut found: Vec<Browser>, options: &DetectOptions) -> Vec<Browser> {
+    if options.latest_only {
+        found = keep_latest(found);
+    }
+    sort_by_preference(&mut found, &options.preference);
+    found
+}
+
+/// Prints which install `--latest-only` kept for each browser found more than once.
+fn report_skipped(found: &[Browser], kept: &[Browser]) {
+    for b in kept {
+        let skipped = found.iter().filter(|f| f.name == b.name).count() - 1;
+        if skipped > 0 {
+            say!("🏷️ Kept latest {} ({}), skipped {} older install(s)",
+                b.name, b.version.as_deref().unwrap_or("unknown version"), skipped);
+        }
+    }
+}
+
+/// How `detect --list-only` prints the browsers it finds.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ListFormat {
+    /// One `name (path, version: ...)` line per browser
+    Text,
+    /// The browsers as a JSON array
+    Json,
+}
+
+/// Prints the browsers detection would offer without prompting or saving anything,
+/// for integrators that do their own selection.
+pub fn list_browsers(options: &DetectOptions, format: ListFormat) -> Result<(), QuickTabsError> {
+    let browsers = detect_browsers(options);
+    match format {
+        ListFormat::Json => {
+            let out = serde_json::to_string_pretty(&browsers).map_err(|e| QuickTabsError::Other(e.to_string()))?;
+            println!("{}", out);
+        }
+        ListFormat::Text => {
+            for b in &browsers {
+                println!("{} ({}, version: {})", b.name, b.path.display(), b.version.as_deref().unwrap_or("unknown"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Probes the filesystem (and registry) for browsers, deduplicated by path.
+///
+/// Paths are found first and versions probed afterwards, once per unique path.
+/// Both steps run on a pool of `options.threads` threads; results keep the probe
+/// order, so the list is the same from run to run.
+fn scan_browsers(options: &DetectOptions) -> Vec<Browser> {
+    // Probing is IO-bound, so a small dedicated pool beats rayon's one-thread-per-core
+    // global pool.
+    let pool = match rayon::ThreadPoolBuilder::new().num_threads(options.threads.max(1)).build() {
+        Ok(pool) => Some(pool),
+        Err(e) => {
+            esay!("⚠️ Could not start detection threads ({}); using the shared pool.", e);
+            None
+        }
+    };
+
+    // 1. Check PATH and common installation directories
+    let mut found: Vec<Browser> = in_pool(pool.as_ref(), || {
+        KNOWN_BROWSERS.par_iter()
+            .flat_map_iter(|(name, exec)| detect_browser(name, exec))
+            .collect() // keeps KNOWN_BROWSERS order
+    });
+    
+    // 2. Check Windows Registry (most reliable method on Windows)
+    #[cfg(target_os = "windows")]
+    {
+        found.extend(probe_registry());
+    }
+
+    // 3. Linux .desktop launchers (Flatpak, Snap and other non-standard installs)
+    #[cfg(target_os = "linux")]
+    {
+        for browser in probe_desktop_entries() {
+            if !found.iter().any(|b| same_file(&b.path, &browser.path)) {
+                found.push(browser);
+            }
+        }
+    }
+
+    // 4. The --search-path roots and, with --deep, a recursive search of the install
+    // roots, for browsers nothing above knows about
+    let mut extra = search_path_browsers(options);
+    if options.deep {
+        extra.extend(deep_search(&deep_search_roots(), DEEP_MAX_DEPTH));
+    }
+    for browser in extra {
+        if !found.iter().any(|b| same_file(&b.path, &browser.path)) {
+            found.push(browser);
+        }
+    }
+
+    // 5. The system default browser goes first, so it wins the dedup below
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(default) = detect_default_macos() {
+            found.insert(0, default);
+        }
+    }
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(default) = detect_default_linux() {
+            found.retain(|b| !same_file(&b.path, &default.path));
+            found.insert(0, default);
+        }
+    }
+
+    // 6. Install kinds, then versions last: each probe starts the browser and may
+    // wait out VERSION_TIMEOUT
+    let mut found = dedup_by_path(found);
+    for browser in &mut found {
+        browser.kind = InstallKind::of(&browser.path);
+    }
+    probe_versions(&mut found, pool.as_ref());
+    found
+}
+
+/// Fills in every browser's version, probing side by side; the order is unchanged.
+fn probe_versions(browsers: &mut [Browser], pool: Option<&rayon::ThreadPool>) {
+    in_pool(pool, || {
+        browsers.par_iter_mut().for_each(|b| b.version = get_version(&b.path));
+    });
+}
+
+/// Runs `probe` on `pool`, or on rayon's shared pool when there is none.
+fn in_pool<R: Send>(pool: Option<&rayon::ThreadPool>, probe: impl FnOnce() -> R + Send) -> R {
+    match pool {
+        Some(pool) => pool.install(probe),
+        None => probe(),
+    }
+}
+
+// --- Detection Cache ---
+
+/// A detection run kept between invocations (`detection_cache.json` in the config dir).
+#[derive(Debug, Serialize, Deserialize)]
+struct DetectionCache {
+    /// Seconds since the Unix epoch
+    detected_at: u64,
+    browsers: Vec<Browser>,
+}
+
+fn detection_cache_path() -> Result<PathBuf, QuickTabsError> {
+    Ok(app_config_dir()?.join("detection_cache.json"))
+}
+
+/// The cached browser list and its age in seconds, unless `--refresh` was given or the
+/// cache is stale.
+fn load_detection_cache(options: &DetectOptions) -> Option<(Vec<Browser>, u64)> {
+    if options.refresh || options.cache_ttl.is_zero() {
+        return None;
+    }
+    let data = fs::read_to_string(detection_cache_path().ok()?).ok()?;
+    let cache: DetectionCache = serde_json::from_str(&data).ok()?;
+    let now = unix_now();
+    let age = now.saturating_sub(cache.detected_at);
+    let browsers = fresh_browsers(cache, now, options.cache_ttl)?;
+    Some((browsers, age))
+}
+
+/// The cached browsers if the cache is younger than `ttl` and every path still
+/// exists; an uninstalled browser means the whole list is rescanned.
+fn fresh_browsers(cache: DetectionCache, now: u64, ttl: Duration) -> Option<Vec<Browser>> {
+    let age = now.checked_sub(cache.detected_at)?;
+    if age >= ttl.as_secs() || !cache.browsers.iter().all(|b| b.path.exists()) {
+        return None;
+    }
+    Some(cache.browsers)
+}
+
+fn save_detection_cache(browsers: &[Browser]) -> Result<(), QuickTabsError> {
+    let cache = DetectionCache { detected_at: unix_now(), browsers: browsers.to_vec() };
+    let json = serde_json::to_string_pretty(&cache).map_err(|e| e.to_string())?;
+    write_atomic(&detection_cache_path()?, json)?;
+    Ok(())
+}
+
+fn format_age(secs: u64) -> String {
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 60 * 60 => format!("{}m", s / 60),
+        s => format!("{}h", s / (60 * 60)),
+    }
+}
+
+/// Keeps the first browser seen for each executable. Paths are compared after
+/// resolving symlinks, so `/usr/bin/firefox` and the `/usr/lib/firefox/firefox` it
+/// points at count once; a path that cannot be resolved is compared as-is. Snap
+/// launchers all link to `/usr/bin/snap` and are compared as-is too. Channels install
+/// to different paths, so e.g. Chrome and Chrome Canary both survive.
+///
+/// The registry and `.desktop` probes name a browser after its file (`brave`), so the
+/// same executable can turn up under two names. The entry keeps its place but takes
+/// the display name from `KNOWN_BROWSERS` (`Brave`) when the duplicate has it.
+fn dedup_by_path(found: Vec<Browser>) -> Vec<Browser> {
+    let mut kept: Vec<(PathBuf, Browser)> = vec![];
+    for b in found {
+        let key = match InstallKind::of(&b.path) {
+            InstallKind::Snap => b.path.clone(),
+            _ => fs::canonicalize(&b.path).unwrap_or_else(|_| b.path.clone()),
+        };
+        match kept.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) if existing.name != b.name => {
+                verbose!("Skipping '{}' at {}: the same executable as '{}'", b.name, b.path.display(), existing.name);
+                if !is_display_name(&existing.name) && is_display_name(&b.name) {
+                    existing.name = b.name;
+                }
+            }
+            Some(_) => {}
+            None => kept.push((key, b)),
+        }
+    }
+    kept.into_iter().map(|(_, b)| b).collect()
+}
+
+/// Whether `name` is a `KNOWN_BROWSERS` display name, possibly with a channel
+/// (`Google Chrome Canary`), rather than a file stem.
+fn is_display_name(name: &str) -> bool {
+    KNOWN_BROWSERS.iter().any(|(display, _)| name.starts_with(display))
+}
+
+/// Keeps only the newest install of each browser (entries sharing a name); see
+/// `report_skipped` for the message. Unparseable versions count as oldest; on a tie the entry that
+/// came first (the one dedup would keep) wins.
+fn keep_latest(found: Vec<Browser>) -> Vec<Browser> {
+    let version = |b: &Browser| b.version.as_deref().and_then(Version::parse);
+
+    let mut kept: Vec<Browser> = vec![];
+    for b in found {
+        match kept.iter_mut().find(|k| k.name == b.name) {
+            Some(existing) => {
+                if version(&b) > version(existing) {
+                    *existing = b;
+                }
+            }
+            None => kept.push(b),
+        }
+    }
+
+    kept
+}
+
+/// Position of `browser` in the preference list (see `browser_matches`).
+fn preference_rank(browser: &Browser, preference: &[String]) -> Option<usize> {
+    preference.iter().position(|p| browser_matches(browser, p))
+}
+
+/// Whether a user-supplied browser name refers to `browser`: compared case-insensitively
+/// with the display name and the executable's file stem.
+pub fn browser_matches(browser: &Browser, name: &str) -> bool {
+    let stem = browser.path.file_stem().unwrap_or_default().to_string_lossy();
+    name.eq_ignore_ascii_case(&browser.name) || name.eq_ignore_ascii_case(&stem)
+}
+
+/// Orders browsers by `preference`. Unlisted browsers keep their relative order after
+/// the listed ones.
+pub fn sort_by_preference(browsers: &mut [Browser], preference: &[String]) {
+    browsers.sort_by_key(|b| preference_rank(b, preference).unwrap_or(usize::MAX));
+}
+
+fn detect_browser(name: &str, base_exec: &str) -> Vec<Browser> {
+    let mut found = vec![];
+    let exec_name = get_executable_name(base_exec);
+
+    // Check PATH
+    verbose!("Looking up {} on PATH", exec_name);
+    if let Ok(path) = which(&exec_name) {
+        found.push(Browser {
+            name: with_channel(name, &path),
+            path: path.clone(),
+            ..Default::default()
+        });
+    }
+
+    // Check common platform-specific paths
+    for candidate in common_paths(&exec_name) {
+        verbose!("Probing {}", candidate.display());
+        if candidate.exists() && !found.iter().any(|b| b.path == candidate) {
+            found.push(Browser {
+                name: with_channel(name, &candidate),
+                path: candidate.clone(),
+                ..Default::default()
+            });
+        }
+    }
+
+    found
+}
+
+/// How many directory levels below each root `--deep` descends.
+const DEEP_MAX_DEPTH: usize = 5;
+
+/// Directories `--deep` never enters (compared case-insensitively): system and data
+/// trees that are slow to walk and never hold a browser install.
+const DEEP_SKIP_DIRS: &[&str] = &[
+    "windows", "winsxs", "$recycle.bin", "system volume information", "windowsapps",
+    "installer", "microsoft.net", "common files", "windows defender", "windows nt",
+    "node_modules", ".git", "cache", "caches", "temp", "tmp", "locales", "extensions",
+];
+
+/// How many directory levels below each `--search-path` root are searched without
+/// `--deep`: enough for an unpacked archive such as `chrome-linux64/chrome`.
+const SEARCH_PATH_DEPTH: usize = 2;
+
+/// Browsers under the `--search-path` roots, `SEARCH_PATH_DEPTH` levels deep or, with
+/// `--deep`, `DEEP_MAX_DEPTH`. Roots are canonicalized, so the same directory given
+/// twice is searched once and the paths found are absolute; missing ones are skipped
+/// with a warning.
+fn search_path_browsers(options: &DetectOptions) -> Vec<Browser> {
+    let mut roots: Vec<PathBuf> = vec![];
+    for dir in &options.search_paths {
+        match fs::canonicalize(dir) {
+            Ok(root) if root.is_dir() => {
+                if !roots.contains(&root) {
+                    roots.push(root);
+                }
+            }
+            _ => esay!("⚠️ Search path {} is not a directory; skipping it.", dir.display()),
+        }
+    }
+    let depth = if options.deep { DEEP_MAX_DEPTH } else { SEARCH_PATH_DEPTH };
+    deep_search(&roots, depth)
+}
+
+/// Where `--deep` looks: the platform's install roots, including per-user ones.
+fn deep_search_roots() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = vec![];
+    if cfg!(target_os = "windows") {
+        for var in ["ProgramFiles", "ProgramFiles(x86)", "LOCALAPPDATA"] {
+            if let Some(dir) = env::var_os(var) {
+                roots.push(PathBuf::from(dir));
+            }
+        }
+    } else if cfg!(target_os = "macos") {
+        roots.push(PathBuf::from("/Applications"));
+        roots.extend(dirs::home_dir().map(|h| h.join("Applications")));
+    } else {
+        roots.extend(["/opt", "/usr/lib", "/usr/local", "/snap"].map(PathBuf::from));
+        if let Some(home) = dirs::home_dir() {
+            roots.push(home.join(".local"));
+            roots.push(home.join("Applications"));
+        }
+    }
+    roots.dedup();
+    roots
+}
+
+/// Walks each root up to `max_depth` levels, skipping `DEEP_SKIP_DIRS` and
+/// symlinked directories, and returns every executable named like a known browser.
+fn deep_search(roots: &[PathBuf], max_depth: usize) -> Vec<Browser> {
+    let mut found = vec![];
+    for root in roots {
+        search_dir(root, 0, max_depth, &mut found);
+    }
+    found
+}
+
+fn search_dir(dir: &Path, depth: usize, max_depth: usize, found: &mut Vec<Browser>) {
+    verbose!("Searching {}", dir.display());
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        // `DirEntry::file_type` does not follow symlinks, so links cannot loop the walk
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let file_name = entry.file_name().to_string_lossy().to_lowercase();
+        let path = entry.path();
+        if file_type.is_dir() {
+            if depth < max_depth && !DEEP_SKIP_DIRS.contains(&file_name.as_str()) {
+                search_dir(&path, depth + 1, max_depth, found);
+            }
+        } else if let Some(name) = known_browser_named(&file_name).filter(|_| is_executable(&path)) {
+            found.push(Browser {
+                name: with_channel(name, &path),
+                path,
+                ..Default::default()
+            });
+        }
+    }
+}
+
+/// The known browser whose executable is `file_name` (lowercase): its base name
+/// (`chrome`, `msedge.exe`) or, as in macOS bundles, its display name (`google chrome`).
+fn known_browser_named(file_name: &str) -> Option<&'static str> {
+    let stem = file_name.strip_suffix(".exe").unwrap_or(file_name);
+    KNOWN_BROWSERS.iter()
+        .find(|(name, exec)| stem == *exec || stem.eq_ignore_ascii_case(name))
+        .map(|(name, _)| *name)
+}
+
+fn is_executable(path: &Path) -> bool {
+    check_executable(path).is_ok()
+}
+
+/// Why `path` cannot be run as a browser, or `Ok` when it can: it must be a file and,
+/// on Unix, have an execute bit set; on Windows it must be an `.exe`, `.com` or `.bat`.
+pub fn check_executable(path: &Path) -> Result<(), String> {
+    let metadata = fs::metadata(path).map_err(|_| "does not exist".to_string())?;
+    if metadata.is_dir() {
+        return Err("is a directory, not an executable".to_string());
+    }
+    if !metadata.is_file() {
+        return Err("is not a regular file".to_string());
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if metadata.permissions().mode() & 0o111 == 0 {
+            return Err("is not executable (no execute permission; `chmod +x` it if it is a program)".to_string());
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let runnable = path.extension()
+            .is_some_and(|ext| ["exe", "com", "bat"].iter().any(|e| ext.eq_ignore_ascii_case(e)));
+        if !runnable {
+            return Err("is not a program (expected an .exe, .com or .bat file)".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Reports every candidate path checked for each known browser, without launching
+/// anything or probing versions.
+pub fn probe_report() -> Vec<ProbeReport> {
+    KNOWN_BROWSERS.iter()
+        .map(|(name, base_exec)| {
+            let exec_name = get_executable_name(base_exec);
+            let on_path = which(&exec_name).ok();
+            let candidates: Vec<CandidatePath> = common_paths(&exec_name)
+                .into_iter()
+                .map(|path| CandidatePath { exists: path.exists(), path })
+                .collect();
+            let chosen = on_path.clone()
+                .or_else(|| candidates.iter().find(|c| c.exists).map(|c| c.path.clone()));
+
+            ProbeReport {
+                name: name.to_string(),
+                executable: exec_name,
+                on_path,
+                candidates,
+                chosen,
+            }
+        })
+        .collect()
+}
+
+/// Prints a probe report as plain text.
+pub fn print_probe_report(reports: &[ProbeReport]) {
+    for r in reports {
+        println!("\n{} ({})", r.name, r.executable);
+        match &r.on_path {
+            Some(p) => println!("  [found]   PATH -> {}", p.display()),
+            None => println!("  [missing] PATH"),
+        }
+        for c in &r.candidates {
+            let status = if c.exists { "[found]  " } else { "[missing]" };
+            println!("  {} {}", status, c.path.display());
+        }
+        match &r.chosen {
+            Some(p) => println!("  => chosen: {}", p.display()),
+            None => println!("  => not found"),
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn probe_registry() -> Vec<Browser> {
+    let mut result = Vec::new();
+
+    // Paths where default browser commands are stored
+    let hives = [(HKEY_LOCAL_MACHINE, "HKEY_LOCAL_MACHINE"), (HKEY_CURRENT_USER, "HKEY_CURRENT_USER")];
+    const SUBKEY: &str = "SOFTWARE\\Clients\\StartMenuInternet";
+
+    for &(hive, hive_name) in &hives {
+        verbose!("Reading registry {}\\{}", hive_name, SUBKEY);
+        if let Ok(key) = RegKey::predef(hive).open_subkey(SUBKEY) {
+            for browser_name in key.enum_keys().flatten() {
+                let subpath = format!("{SUBKEY}\\{browser_name}");
+                if let Ok(sub) = RegKey::predef(hive).open_subkey(&subpath) {
+                    if let Ok(cmd) = sub.open_subkey("shell\\open\\command") {
+                        if let Ok(val) = cmd.get_value::<String, _>("") {
+                            let path = PathBuf::from(command_executable(&val));
+
+                            if path.exists() {
+                                let exe_name = path.file_stem()
+                                    .map(|n| n.to_string_lossy().to_string())
+                                    .unwrap_or(browser_name.clone());
+
+                                result.push(Browser {
+                                    name: with_channel(&exe_name, &path),
+                                    path,
+                                    ..Default::default()
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Directories holding `.desktop` launchers, including Flatpak and Snap exports.
+#[cfg(target_os = "linux")]
+fn desktop_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![
+        PathBuf::from("/usr/share/applications"),
+        PathBuf::from("/usr/local/share/applications"),
+        PathBuf::from("/var/lib/flatpak/exports/share/applications"),
+        PathBuf::from("/var/lib/snapd/desktop/applications"),
+    ];
+    if let Some(data) = dirs::data_dir() {
+        dirs.push(data.join("applications"));
+        dirs.push(data.join("flatpak/exports/share/applications"));
+    }
+    dirs
+}
+
+/// On Linux: browsers declared by `.desktop` files with `WebBrowser` in `Categories`.
+/// Flatpak launchers use their `exports/bin` wrapper; others use the first `Exec=`
+/// word, looked up on PATH when it is not absolute.
+#[cfg(target_os = "linux")]
+fn probe_desktop_entries() -> Vec<Browser> {
+    let mut found: Vec<Browser> = vec![];
+    for dir in desktop_dirs() {
+        verbose!("Scanning {} for .desktop launchers", dir.display());
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        for file in entries.flatten().map(|e| e.path()).filter(|p| p.extension().is_some_and(|e| e == "desktop")) {
+            let Some((name, path)) = desktop_entry_browser(&dir, &file) else {
+                continue;
+            };
+            if !found.iter().any(|b| same_file(&b.path, &path)) {
+                found.push(Browser { name, path, ..Default::default() });
+            }
+        }
+    }
+    found
+}
+
+/// Name and executable of the browser a `.desktop` file in `dir` launches.
+#[cfg(target_os = "linux")]
+fn desktop_entry_browser(dir: &Path, file: &Path) -> Option<(String, PathBuf)> {
+    let entry = parse_desktop_entry(&fs::read_to_string(file).ok()?)?;
+    // <exports>/share/applications/<id>.desktop -> <exports>/bin/<id>
+    let flatpak_bin = file.file_stem()
+        .and_then(|id| Some(dir.parent()?.parent()?.join("bin").join(id)))
+        .filter(|bin| dir.ends_with("flatpak/exports/share/applications") && bin.exists());
+    let path = flatpak_bin.or_else(|| {
+        let exe = PathBuf::from(entry.exec.first()?);
+        if exe.is_absolute() { exe.exists().then_some(exe) } else { which(&exe).ok() }
+    })?;
+    Some((entry.name, path))
+}
+
+/// On Linux: the default browser according to `xdg-settings`, resolved through its
+/// `.desktop` file.
+#[cfg(target_os = "linux")]
+fn detect_default_linux() -> Option<Browser> {
+    let stdout = output_with_timeout(Command::new("xdg-settings").args(["get", "default-web-browser"]), VERSION_TIMEOUT)?;
+    let id = String::from_utf8_lossy(&stdout).trim().to_string();
+    if id.is_empty() {
+        return None;
+    }
+    let (name, path) = desktop_dirs().iter()
+        .find_map(|dir| desktop_entry_browser(dir, &dir.join(&id)))?;
+    Some(Browser { name, path, ..Default::default() })
+}
+
+/// The parts of a `.desktop` file detection needs.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, PartialEq)]
+struct DesktopEntry {
+    name: String,
+    /// `Exec=` split into words, field codes removed
+    exec: Vec<String>,
+}
+
+/// Reads the `[Desktop Entry]` group, returning `None` unless it is a visible
+/// application in the `WebBrowser` category.
+#[cfg(any(target_os = "linux", test))]
+fn parse_desktop_entry(contents: &str) -> Option<DesktopEntry> {
+    let mut in_entry = false;
+    let (mut name, mut exec, mut browser, mut hidden) = (None, None, false, false);
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=').filter(|_| in_entry) else {
+            continue;
+        };
+        match key.trim() {
+            "Name" => name = Some(value.trim().to_string()),
+            "Exec" => exec = Some(exec_words(value)),
+            "Categories" => browser = value.split(';').any(|c| c.trim() == "WebBrowser"),
+            "Hidden" => hidden = value.trim() == "true",
+            _ => {}
+        }
+    }
+    let exec = exec.filter(|words| !words.is_empty())?;
+    (browser && !hidden).then_some(DesktopEntry { name: name?, exec })
+}
+
+/// Splits an `Exec=` value into words, honouring double quotes and dropping field
+/// codes such as `%U` (`%%` is a literal `%`).
+#[cfg(any(target_os = "linux", test))]
+fn exec_words(exec: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let (mut quoted, mut in_word) = (false, false);
+    let mut chars = exec.trim().chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            '\\' if quoted => word.extend(chars.next()),
+            c if c.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                }
+                in_word = false;
+            }
+            '%' => {
+                // Anything but `%%` is a field code, expanded by launchers only
+                if chars.next() == Some('%') {
+                    word.push('%');
+                }
+                in_word = true;
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    // A word that was only a field code leaves nothing behind
+    words.retain(|w| !w.is_empty());
+    words
+}
+
+/// The user's URL handlers, relative to the home directory (a binary plist).
+#[cfg(target_os = "macos")]
+const LAUNCH_SERVICES_PLIST: &str = "Library/Preferences/com.apple.LaunchServices/com.apple.launchservices.secure.plist";
+
+/// On macOS: the default browser, i.e. the LaunchServices handler for `http`.
+/// Returns `None` when the preferences are unreadable or the app is not found.
+#[cfg(target_os = "macos")]
+fn detect_default_macos() -> Option<Browser> {
+    let home = dirs::home_dir()?;
+    // plutil turns the binary plist into JSON we can parse
+    let output = Command::new("plutil")
+        .args(["-convert", "json", "-o", "-"])
+        .arg(home.join(LAUNCH_SERVICES_PLIST))
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let bundle_id = http_handler_bundle_id(&String::from_utf8_lossy(&output.stdout))?;
+
+    let (name, path) = [PathBuf::from("/Applications"), home.join("Applications")]
+        .iter()
+        .find_map(|dir| app_for_bundle_id(dir, &bundle_id))?;
+    Some(Browser { name, path, ..Default::default() })
+}
+
+/// Bundle id handling the `http` scheme in the LaunchServices preferences (as JSON).
+#[cfg(any(target_os = "macos", test))]
+fn http_handler_bundle_id(json: &str) -> Option<String> {
+    let prefs: serde_json::Value = serde_json::from_str(json).ok()?;
+    prefs.get("LSHandlers")?
+        .as_array()?
+        .iter()
+        .find(|h| h.get("LSHandlerURLScheme").and_then(|s| s.as_str()) == Some("http"))?
+        .get("LSHandlerRoleAll")?
+        .as_str()
+        .map(|id| id.to_string())
+}
+
+/// Finds the `.app` in `apps_dir` with `bundle_id` and returns its name and
+/// executable. LaunchServices stores ids lowercased, so they match case-insensitively.
+#[cfg(any(target_os = "macos", test))]
+fn app_for_bundle_id(apps_dir: &Path, bundle_id: &str) -> Option<(String, PathBuf)> {
+    fs::read_dir(apps_dir).ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|app| app.extension().is_some_and(|ext| ext == "app"))
+        .find_map(|app| {
+            let info = fs::read_to_string(app.join("Contents/Info.plist")).ok()?;
+            if !plist_string(&info, "CFBundleIdentifier")?.eq_ignore_ascii_case(bundle_id) {
+                return None;
+            }
+            let exe = app.join("Contents/MacOS").join(plist_string(&info, "CFBundleExecutable")?);
+            let name = app.file_stem()?.to_string_lossy().to_string();
+            exe.is_file().then_some((name, exe))
+        })
+}
+
+/// The `<string>` value following `<key>key</key>` in an XML property list.
+#[cfg(any(target_os = "macos", test))]
+fn plist_string(xml: &str, key: &str) -> Option<String> {
+    let tag = format!("<key>{}</key>", key);
+    let rest = xml[xml.find(&tag)? + tag.len()..].trim_start().strip_prefix("<string>")?;
+    Some(rest[..rest.find("</string>")?].trim().to_string())
+}
+
+// --- Utility Functions ---
+
+/// Extracts the executable from a registry shell command such as
+/// `"C:\Program Files\Mozilla Firefox\firefox.exe" -osint -url "%1"`.
+/// A leading quoted token is taken up to its closing quote (spaces included);
+/// otherwise the executable ends at the first whitespace.
+#[cfg(any(target_os = "windows", test))]
+fn command_executable(command: &str) -> &str {
+    let command = command.trim_start();
+    match command.strip_prefix('"') {
+        Some(rest) => rest.split('"').next().unwrap_or(rest),
+        None => command.split_whitespace().next().unwrap_or(command),
+    }
+}
+
+fn get_executable_name(base: &str) -> String {
+    if cfg!(target_os = "windows") {
+        format!("{base}.exe")
+    } else {
+        base.to_string()
+    }
+}
+
+/// Release channel encoded in an install path, e.g. `Chrome SxS` (Canary on Windows),
+/// `Google Chrome Beta.app` or `google-chrome-unstable`.
+fn channel_of(path: &Path) -> Option<&'static str> {
+    path.components().rev().find_map(|c| {
+        let part = c.as_os_str().to_string_lossy().to_lowercase();
+        let part = part.trim_end_matches(".app").trim_end_matches(".exe");
+        let ends = |suffix: &str| part.ends_with(&format!(" {suffix}")) || part.ends_with(&format!("-{suffix}"));
+
+        if ends("canary") || part == "chrome sxs" || part == "edge sxs" {
+            Some("Canary")
+        } else if ends("nightly") {
+            Some("Nightly")
+        } else if part.contains("developer edition") || part.contains("developer-edition") {
+            Some("Developer Edition")
+        } else if ends("beta") {
+            Some("Beta")
+        } else if ends("dev") || ends("unstable") {
+            Some("Dev")
+        } else {
+            None
+        }
+    })
+}
+
+/// Appends the release channel to a display name unless it already mentions it.
+fn with_channel(name: &str, path: &Path) -> String {
+    match channel_of(path) {
+        Some(channel) if !name.to_lowercase().contains(&channel.to_lowercase()) => {
+            format!("{} {}", name, channel)
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// Reads the browser's version. On Windows this comes from the executable's
+/// version resource, which needs no process spawn; `--version` is the fallback.
+///
+/// The registry lists whatever registered itself as a browser, and some of those
+/// programs answer `--version` by opening a window. On Windows the fallback is
+/// therefore only run for executables named like a known browser family.
+fn get_version(path: &PathBuf) -> Option<String> {
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(version) = file_version(path) {
+            return Some(version);
+        }
+        if !is_known_family(path) {
+            verbose!("Not running {} --version: not a known browser", path.display());
+            return None;
+        }
+    }
+    version_from_flag(path)
+}
+
+/// Whether the executable's name belongs to a browser family whose `--version` is
+/// known to print and exit (Chrome, Firefox, Edge, Brave, Opera, Vivaldi, ...).
+#[cfg(any(target_os = "windows", test))]
+fn is_known_family(path: &Path) -> bool {
+    use crate::commands::links::{browser_family, BrowserFamily};
+    browser_family(path) != BrowserFamily::Unknown
+}
+
+/// The file version from the VERSIONINFO resource, as `major.minor.build.revision`.
+#[cfg(target_os = "windows")]
+fn file_version(path: &Path) -> Option<String> {
+    use std::ffi::c_void;
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::{
+        GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW, VS_FIXEDFILEINFO,
+    };
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let root: Vec<u16> = "\\".encode_utf16().chain(Some(0)).collect();
+    // SAFETY: both strings are NUL-terminated, `data` is sized as the API asks, and
+    // the fixed-info pointer is only read after checking it points inside `data`.
+    unsafe {
+        let size = GetFileVersionInfoSizeW(wide.as_ptr(), std::ptr::null_mut());
+        if size == 0 {
+            return None;
+        }
+        let mut data = vec![0u8; size as usize];
+        if GetFileVersionInfoW(wide.as_ptr(), 0, size, data.as_mut_ptr().cast()) == 0 {
+            return None;
+        }
+        let mut info: *mut c_void = std::ptr::null_mut();
+        let mut len = 0u32;
+        if VerQueryValueW(data.as_ptr().cast(), root.as_ptr(), &mut info, &mut len) == 0
+            || info.is_null()
+            || (len as usize) < std::mem::size_of::<VS_FIXEDFILEINFO>()
+        {
+            return None;
+        }
+        let info = &*(info as *const VS_FIXEDFILEINFO);
+        let (ms, ls) = (info.dwFileVersionMS, info.dwFileVersionLS);
+        if ms == 0 && ls == 0 {
+            return None;
+        }
+        Some(format!("{}.{}.{}.{}", ms >> 16, ms & 0xffff, ls >> 16, ls & 0xffff))
+    }
+}
+
+/// Runs the browser with `--version` and keeps the first line of what it prints.
+fn version_from_flag(path: &PathBuf) -> Option<String> {
+    // Note: --version flag is highly common but not universal.
+    let stdout = output_with_timeout(Command::new(path).arg("--version"), VERSION_TIMEOUT)?;
+    let version_str = String::from_utf8_lossy(&stdout);
+    // Typically version is the last word or first line. Clean it up.
+    Some(version_str.lines().next().unwrap_or(&version_str).trim().to_string())
+}
+
+/// Runs `command` and returns its stdout, or `None` if it cannot start or is still
+/// running after `timeout`, in which case it is killed. Executables that ignore
+/// `--version` may open a window and never exit, which would otherwise hang detection.
+fn output_with_timeout(command: &mut Command, timeout: Duration) -> Option<Vec<u8>> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    // Read on a thread: anything the probe left running may hold the pipe open
+    let mut stdout = child.stdout.take()?;
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buf = vec![];
+        let _ = stdout.read_to_end(&mut buf);
+        let _ = tx.send(buf);
+    });
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(20)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    rx.recv_timeout(remaining.max(Duration::from_millis(100))).ok()
+}
+
+fn common_paths(exec: &str) -> Vec<PathBuf> {
+    let mut paths = vec![];
+
+    if cfg!(target_os = "windows") {
+        let pf = env::var("ProgramFiles").unwrap_or_default();
+        let pf_x86 = env::var("ProgramFiles(x86)").unwrap_or_default();
+        let local = env::var("LOCALAPPDATA").unwrap_or_default();
+        
+        let mut candidates = vec![
+            format!("{pf}\\Google\\Chrome\\Application\\{exec}"),
+            format!("{pf_x86}\\Google\\Chrome\\Application\\{exec}"),
+            format!("{pf}\\Mozilla Firefox\\{exec}"),
+            format!("{pf_x86}\\Microsoft\\Edge\\Application\\{exec}"),
+            format!("{pf}\\BraveSoftware\\Brave-Browser\\Application\\{exec}"),
+            format!("{local}\\Programs\\{exec}"),
+            // Release channels
+            format!("{pf}\\Google\\Chrome Beta\\Application\\{exec}"),
+            format!("{pf}\\Google\\Chrome Dev\\Application\\{exec}"),
+            format!("{local}\\Google\\Chrome SxS\\Application\\{exec}"),
+            format!("{pf_x86}\\Microsoft\\Edge Beta\\Application\\{exec}"),
+            format!("{pf_x86}\\Microsoft\\Edge Dev\\Application\\{exec}"),
+            format!("{local}\\Microsoft\\Edge SxS\\Application\\{exec}"),
+            format!("{pf}\\Firefox Nightly\\{exec}"),
+            format!("{pf}\\Firefox Developer Edition\\{exec}"),
+            format!("{local}\\Vivaldi\\Application\\{exec}"),
+            format!("{pf}\\Vivaldi\\Application\\{exec}"),
+            format!("{pf}\\LibreWolf\\{exec}"),
+            format!("{pf}\\Waterfox\\{exec}"),
+            format!("{pf}\\Zen Browser\\{exec}"),
+        ];
+        // Tor Browser installs to the desktop by default and keeps the Firefox executable name
+        if exec == "tor-browser.exe" {
+            let home = env::var("USERPROFILE").unwrap_or_default();
+            candidates.push(format!("{home}\\Desktop\\Tor Browser\\Browser\\firefox.exe"));
+        }
+        paths.extend(candidates.into_iter().map(PathBuf::from));
+    } else if cfg!(target_os = "macos") {
+        // macOS executable paths within .app bundles
+        let base_name = exec.replace(".exe", "");
+        paths.push(PathBuf::from(format!("/Applications/{base_name}.app/Contents/MacOS/{base_name}")));
+        // Handle common variations
+        if base_name == "chrome" {
+             paths.push(PathBuf::from("/Applications/Google Chrome.app/Contents/MacOS/Google Chrome"));
+             for channel in ["Beta", "Dev", "Canary"] {
+                 paths.push(PathBuf::from(format!("/Applications/Google Chrome {channel}.app/Contents/MacOS/Google Chrome {channel}")));
+             }
+        }
+        if base_name == "firefox" {
+             paths.push(PathBuf::from("/Applications/Firefox.app/Contents/MacOS/firefox"));
+             paths.push(PathBuf::from("/Applications/Firefox Nightly.app/Contents/MacOS/firefox"));
+             paths.push(PathBuf::from("/Applications/Firefox Developer Edition.app/Contents/MacOS/firefox"));
+        }
+        if base_name == "msedge" {
+             paths.push(PathBuf::from("/Applications/Microsoft Edge.app/Contents/MacOS/Microsoft Edge"));
+             for channel in ["Beta", "Dev", "Canary"] {
+                 paths.push(PathBuf::from(format!("/Applications/Microsoft Edge {channel}.app/Contents/MacOS/Microsoft Edge {channel}")));
+             }
+        }
+        match base_name.as_str() {
+            "vivaldi" => paths.push(PathBuf::from("/Applications/Vivaldi.app/Contents/MacOS/Vivaldi")),
+            "librewolf" => paths.push(PathBuf::from("/Applications/LibreWolf.app/Contents/MacOS/librewolf")),
+            "waterfox" => paths.push(PathBuf::from("/Applications/Waterfox.app/Contents/MacOS/waterfox")),
+            "zen" => {
+                paths.push(PathBuf::from("/Applications/Zen.app/Contents/MacOS/zen"));
+                paths.push(PathBuf::from("/Applications/Zen Browser.app/Contents/MacOS/zen"));
+            }
+            "tor-browser" => paths.push(PathBuf::from("/Applications/Tor Browser.app/Contents/MacOS/firefox")),
+            _ => {}
+        }
+    } else { // Linux/Unix
+        paths.push(PathBuf::from(format!("/usr/bin/{exec}")));
+        paths.push(PathBuf::from(format!("/usr/local/bin/{exec}")));
+        paths.push(PathBuf::from(format!("/snap/bin/{exec}")));
+        paths.push(PathBuf::from(format!("/opt/{exec}/{exec}")));
+        // Tor Browser is usually unpacked into the home directory, or installed by torbrowser-launcher
+        if exec == "tor-browser" {
+            if let Some(home) = dirs::home_dir() {
+                paths.push(home.join("tor-browser/Browser/start-tor-browser"));
+                paths.push(home.join(".local/share/torbrowser/tbb/x86_64/tor-browser/Browser/start-tor-browser"));
+            }
+        }
+    }
+
+    paths
+}
+
+// --- Interaction and Configuration Saving ---
+
+fn choose_browser_interactively(found: &mut [Browser]) -> Option<Browser> {
+    println!("\nSelect a browser:");
+    println!("  [M] Manual entry");
+
+    print!("Enter choice [1-{} or M]: ", found.len());
+    let _ = io::stdout().flush();
+
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input).is_err() {
+        return None;
+    }
+    let choice = input.trim();
+
+    if choice.eq_ignore_ascii_case("m") {
+        return manual_select();
+    }
+
+    if let Ok(index) = choice.parse::<usize>() {
+        if index > 0 && index <= found.len() {
+            let b = found[index - 1].clone();
+            say!("✅ Selected: {}", b.name);
+            return Some(b);
+        }
+    }
+
+    say!("⚠️ Invalid choice. Retrying manual entry.");
+    manual_select()
+}
+
+pub fn manual_select() -> Option<Browser> {
+    say!("\n🖊️ Enter a browser command (e.g. firefox) or full path to its executable:");
+    print!("Path: ");
+    let _ = io::stdout().flush();
+
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input).is_err() {
+        say!("❌ Read error.");
+        return None;
+    }
+
+    let Some(path) = resolve_browser_path(input.trim()) else {
+        say!("❌ Invalid path: path does not exist.");
+        return None;
+    };
+    match check_executable(&path) {
+        Ok(()) => {
+            say!("✅ Browser added: {}", path.display());
+            Some(custom_browser(path))
+        }
+        Err(reason) => {
+            say!("❌ Invalid path: {} {}.", path.display(), reason);
+            None
+        }
+    }
+}
+
+/// Turns user input into an executable path: a command name found on PATH
+/// (`firefox`) first, then the input as a literal path after expanding `~` and
+/// `$VARS`. `None` if neither exists.
+fn resolve_browser_path(input: &str) -> Option<PathBuf> {
+    if input.is_empty() {
+        return None;
+    }
+    let input: &str = &shellexpand::full(input).ok()?;
+    which(input).ok().or_else(|| {
+        let path = PathBuf::from(input);
+        path.exists().then_some(path)
+    })
+}
+
+fn custom_browser(path: PathBuf) -> Browser {
+    Browser {
+        name: "Custom Browser".to_string(),
+        version: get_version(&path),
+        kind: InstallKind::of(&path),
+        path,
+        ..Default::default()
+    }
+}
+
+/// Saves the browser given as a detected browser's name, a command name or a path
+/// as the preferred browser, without any prompts. Returns the saved entry.
+///
+/// Names are matched like `--browser` does; a name shared by several detected
+/// installs is an error listing their paths. If the path is one detection knows
+/// about, that entry's name and version are kept; only unknown paths become
+/// "Custom Browser" with a fresh `--version` probe.
+pub fn set_browser(input: &str, options: &DetectOptions) -> Result<Browser, QuickTabsError> {
+    let known = detect_all(options);
+    let browser = match unique_match(&known, input)? {
+        Some(browser) => browser.clone(),
+        None => {
+            let path = resolve_browser_path(input)
+                .ok_or_else(|| QuickTabsError::NotFound(format!("Browser name, command or path '{}'", input)))?;
+            executable_or_err(&path)?;
+            match known.into_iter().find(|b| same_file(&b.path, &path)) {
+                Some(known) => Browser { path, ..known },
+                None => custom_browser(path),
+            }
+        }
+    };
+    let config_path = get_app_config_path()?;
+    let browser = with_saved_flags(&config_path, browser);
+    save_browser(&config_path, &browser);
+    Ok(browser)
+}
+
+/// The detected browser called `name`, if exactly one install matches. Installs
+/// that are the same file (symlinks) count once.
+fn unique_match<'a>(known: &'a [Browser], name: &str) -> Result<Option<&'a Browser>, QuickTabsError> {
+    let mut matches: Vec<&Browser> = vec![];
+    for browser in known.iter().filter(|b| browser_matches(b, name)) {
+        if !matches.iter().any(|m| same_file(&m.path, &browser.path)) {
+            matches.push(browser);
+        }
+    }
+    match matches.as_slice() {
+        [] => Ok(None),
+        [browser] => Ok(Some(browser)),
+        _ => {
+            let paths: Vec<String> = matches.iter().map(|b| b.path.display().to_string()).collect();
+            Err(QuickTabsError::Other(format!(
+                "'{}' matches several browsers ({}); pass the path of the one to use",
+                name, paths.join(", ")
+            )))
+        }
+    }
+}
+
+/// Picks a browser for one invocation (`--browser`) without saving it: `input` as a
+/// command on PATH or an executable path, otherwise a detected browser whose name or
+/// executable matches it (case-insensitive). Detection is silent (see
+/// `detect_browsers`); when nothing matches, the detected names are listed on stderr.
+pub fn find_browser(input: &str, options: &DetectOptions) -> Result<Browser, QuickTabsError> {
+    if let Some(path) = resolve_browser_path(input) {
+        executable_or_err(&path)?;
+        // A detected browser at the same path keeps its name and version
+        return Ok(match detect_browsers(options).into_iter().find(|b| same_file(&b.path, &path)) {
+            Some(known) => Browser { path, ..known },
+            None => custom_browser(path),
+        });
+    }
+    let known = detect_browsers(options);
+    if let Some(browser) = known.iter().find(|b| browser_matches(b, input)) {
+        return Ok(browser.clone());
+    }
+
+    if known.is_empty() {
+        esay!("⚠️ No browsers detected.");
+    } else {
+        let names: Vec<&str> = known.iter().map(|b| b.name.as_str()).collect();
+        esay!("ℹ️ Available browsers: {}", names.join(", "));
+    }
+    Err(QuickTabsError::NotFound(format!("Browser '{}'", input)))
+}
+
+/// Browsers to try, in preference order, after `failed` could not be started: the
+/// detected ones (see `detect_browsers`) other than `failed` that are still executable.
+pub fn fallback_browsers(failed: &Browser, options: &DetectOptions) -> Vec<Browser> {
+    fallback_candidates(failed, detect_browsers(options))
+}
+
+fn fallback_candidates(failed: &Browser, detected: Vec<Browser>) -> Vec<Browser> {
+    detected.into_iter()
+        .filter(|b| !same_file(&b.path, &failed.path) && check_executable(&b.path).is_ok())
+        .collect()
+}
+
+/// Saves `replacement` as the default browser when the saved default is `failed` and
+/// its executable is gone, so the next launch does not trip over the same path.
+pub fn replace_missing_default(failed: &Browser, replacement: &Browser) -> Result<(), QuickTabsError> {
+    if failed.path.exists() {
+        return Ok(());
+    }
+    let config_path = get_app_config_path()?;
+    if read_saved_browser(&config_path)?.is_some_and(|saved| saved.path == failed.path) {
+        save_browser(&config_path, replacement);
+    }
+    Ok(())
+}
+
+fn executable_or_err(path: &Path) -> Result<(), QuickTabsError> {
+    check_executable(path).map_err(|reason| QuickTabsError::Other(format!("{} {}", path.display(), reason)))
+}
+
+/// Compares paths after resolving symlinks (e.g. /usr/bin/firefox -> /usr/lib/firefox/firefox).
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+// --- File Storage Handlers ---
+
+/// Gets the application configuration path (~/.config/quick_tabs/browser_config.json)
+fn get_app_config_path() -> Result<PathBuf, QuickTabsError> {
+    browser_config_path()
+}
+
+/// Name that `set-browser-flags` uses for the preferred browser rather than a named config.
+pub const DEFAULT_CONFIG_NAME: &str = "default";
+
+/// Directory holding named browser configs (~/.config/quick_tabs/browsers)
+fn named_configs_dir() -> Result<PathBuf, QuickTabsError> {
+    let dir = app_config_dir()?.join("browsers");
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Path of the named browser config `name`, e.g. browsers/work-chrome.json.
+pub fn named_config_path(name: &str) -> Result<PathBuf, QuickTabsError> {
+    named_config_file(&named_configs_dir()?, name)
+}
+
+fn named_config_file(dir: &Path, name: &str) -> Result<PathBuf, QuickTabsError> {
+    let valid = !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.')) && !name.starts_with('.');
+    if !valid {
+        return Err(QuickTabsError::Other(format!(
+            "Invalid browser config name '{}' (use letters, digits, '-', '_' and '.')",
+            name
+        )));
+    }
+    Ok(dir.join(format!("{}.json", name)))
+}
+
+/// Loads the browser saved by `detect --save-as <name>`; `None` if there is none
+/// or its executable no longer exists.
+pub fn load_named_browser(name: &str) -> Result<Option<Browser>, QuickTabsError> {
+    Ok(load_saved_browser(&named_config_path(name)?))
+}
+
+/// All named browser configs, sorted by name.
+pub fn list_named_browsers() -> Result<Vec<(String, Browser)>, QuickTabsError> {
+    Ok(named_browsers_in(&named_configs_dir()?))
+}
+
+fn named_browsers_in(dir: &Path) -> Vec<(String, Browser)> {
+    let mut configs: Vec<(String, Browser)> = fs::read_dir(dir)
+        .map(|entries| {
+            entries.flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+                .filter_map(|p| {
+                    let data = fs::read_to_string(&p).ok()?;
+                    let cfg: Config = serde_json::from_str(&data).ok()?;
+                    Some((p.file_stem()?.to_string_lossy().to_string(), cfg.browser))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    configs.sort_by(|a, b| a.0.cmp(&b.0));
+    configs
+}
+
+fn load_saved_browser(config_path: &Path) -> Option<Browser> {
+    if config_path.exists() {
+        if let Ok(data) = fs::read_to_string(config_path) {
+            if let Ok(cfg) = serde_json::from_str::<Config>(&data) {
+                match check_executable(&cfg.browser.path) {
+                    Ok(()) => return Some(cfg.browser),
+                    Err(reason) if cfg.browser.path.exists() => {
+                        esay!("⚠️ The saved browser {} {}; ignoring it.", cfg.browser.path.display(), reason);
+                    }
+                    Err(_) => {}
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The browser saved in `config_path`, even when its executable no longer exists;
+/// `None` when nothing is saved. Fails when the file cannot be read or parsed.
+pub fn read_saved_browser(config_path: &Path) -> Result<Option<Browser>, QuickTabsError> {
+    let data = match fs::read_to_string(config_path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let cfg: Config = serde_json::from_str(&data)
+        .map_err(|e| QuickTabsError::Other(format!("Failed to parse browser config {}: {}", config_path.display(), e)))?;
+    Ok(Some(cfg.browser))
+}
+
+/// Path of the browser config `name`: `default` is the preferred browser
+/// (browser_config.json), anything else a name saved with `detect --save-as`.
+pub fn browser_config_file(name: &str) -> Result<PathBuf, QuickTabsError> {
+    if name == DEFAULT_CONFIG_NAME {
+        get_app_config_path()
+    } else {
+        named_config_path(name)
+    }
+}
+
+/// Applies `update` to the browser saved as `name` (see `browser_config_file`) and
+/// saves it. Fails when nothing is saved under that name.
+pub fn update_saved_browser(name: &str, update: impl FnOnce(&mut Browser)) -> Result<Browser, QuickTabsError> {
+    let path = browser_config_file(name)?;
+    let mut browser = read_saved_browser(&path)?
+        .ok_or_else(|| QuickTabsError::NotFound(format!("Browser config '{}'", name)))?;
+    update(&mut browser);
+    save_browser(&path, &browser);
+    Ok(browser)
+}
+
+/// Keeps the launch flags saved in `config_path` when `browser` is the same
+/// executable, so re-detecting does not drop them.
+fn with_saved_flags(config_path: &Path, mut browser: Browser) -> Browser {
+    if let Some(saved) = load_saved_browser(config_path).filter(|s| same_file(&s.path, &browser.path)) {
+        if browser.extra_args.is_empty() {
+            browser.extra_args = saved.extra_args;
+        }
+        if browser.private_args.is_none() {
+            browser.private_args = saved.private_args;
+        }
+    }
+    browser
+}
+
+/// Saves `browser` as the default browser, as `detect` does after a selection.
+pub fn save_default_browser(browser: &Browser) -> Result<(), QuickTabsError> {
+    save_browser(&get_app_config_path()?, browser);
+    Ok(())
+}
+
+fn save_browser(config_path: &Path, browser: &Browser) {
+    let cfg = Config {
+        browser: browser.clone(),
+    };
+    if let Ok(json) = serde_json::to_string_pretty(&cfg) {
+        if write_atomic(config_path, json).is_ok() {
+            say!("💾 Saved preferred browser to config: {}", config_path.display());
+        } else {
+            esay!("⚠️ Could not save browser config to {}", config_path.display());
+        }
+    }
+}
+
+/// Deletes the detection cache in the config directory. The `browsers.json` and
+/// `browsers.txt` reports in the working directory are left alone. Returns the
+/// number of bytes removed.
+pub fn clear_cache() -> io::Result<u64> {
+    let Ok(file) = detection_cache_path() else {
+        return Ok(0);
+    };
+    match fs::metadata(&file) {
+        Ok(meta) => {
+            fs::remove_file(&file)?;
+            Ok(meta.len())
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(0),
+        Err(e) => Err(e),
+    }
+}
+
+/// Re-runs detection, which rewrites the detection cache. The saved browser
+/// selection and the reports in the working directory are left alone. Returns the
+/// size of the new cache.
+pub fn rebuild_cache() -> io::Result<u64> {
+    let options = DetectOptions { refresh: true, ..DetectOptions::from_config(&AppConfig::load_or_default()) };
+    detect_all(&options);
+    match detection_cache_path().map(fs::metadata) {
+        Ok(Ok(meta)) => Ok(meta.len()),
+        _ => Ok(0),
+    }
+}
+
+/// Write JSON and text outputs to the Current Working Directory (CWD)
+pub fn write_outputs(found: &[Browser]) -> std::io::Result<()> {
+    // 1. JSON output (browsers.json)
+    let json_path = PathBuf::from("browsers.json");
+    fs::write(&json_path, browsers_json(found))?;
+    say!("📄 Saved full browser list to {}", json_path.display());
+
+
+    // 2. Text output (browsers.txt)
+    let text_path = PathBuf::from("browsers.txt");
+    fs::write(&text_path, browsers_text(found))?;
+    say!("📄 Saved full browser list to {}", text_path.display());
+
+    Ok(())
+}
+
+/// The browser list as written to `browsers.json`: the full `Browser` records.
+pub fn browsers_json(found: &[Browser]) -> String {
+    serde_json::to_string_pretty(found).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// The browser list as written to `browsers.txt`: one `name = path` line each.
+pub fn browsers_text(found: &[Browser]) -> String {
+    found.iter()
+        .map(|b| format!("{} = {}\n", b.name, b.path.display()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn install_kind_follows_the_launcher_location() {
+        assert_eq!(InstallKind::of(Path::new("/snap/bin/firefox")), InstallKind::Snap);
+        assert_eq!(InstallKind::of(Path::new("/var/lib/flatpak/exports/bin/org.mozilla.firefox")), InstallKind::Flatpak);
+        assert_eq!(InstallKind::of(Path::new("/home/u/.local/share/flatpak/exports/bin/com.brave.Browser")), InstallKind::Flatpak);
+        assert_eq!(InstallKind::of(Path::new("/usr/bin/firefox")), InstallKind::Native);
+    }
+
+    #[test]
+    fn named_configs_round_trip() {
+        let dir = env::temp_dir().join(format!("quick_tabs_named_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        // The executable must exist for a saved config to load
+        let browser = Browser { name: "Work Chrome".to_string(), path: env::current_exe().unwrap(), version: None, ..Default::default() };
+        let path = named_config_file(&dir, "work-chrome").unwrap();
+        assert_eq!(path, dir.join("work-chrome.json"));
+        save_browser(&path, &browser);
+
+        let loaded = load_saved_browser(&path).unwrap();
+        assert_eq!(loaded.name, "Work Chrome");
+        assert_eq!(loaded.path, browser.path);
+        assert!(load_saved_browser(&dir.join("personal-firefox.json")).is_none());
+
+        let listed = named_browsers_in(&dir);
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].0, "work-chrome");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn named_config_names_cannot_escape_the_directory() {
+        let dir = Path::new("/cfg/browsers");
+        assert!(named_config_file(dir, "../browser_config").is_err());
+        assert!(named_config_file(dir, "a/b").is_err());
+        assert!(named_config_file(dir, "").is_err());
+        assert!(named_config_file(dir, "personal_firefox.2").is_ok());
+    }
+
+    /// An executable shell script in a fresh temp dir.
+    #[cfg(unix)]
+    fn script(name: &str, body: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = env::temp_dir().join(format!("quick_tabs_version_{}_{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn only_executable_files_pass_as_browsers() {
+        let browser = script("browser", "exit 0");
+        let dir = browser.parent().unwrap();
+        let data = dir.join("notes.txt");
+        fs::write(&data, "not a program").unwrap();
+
+        assert!(check_executable(&browser).is_ok());
+        assert!(check_executable(dir).unwrap_err().contains("directory"));
+        assert!(check_executable(&data).unwrap_err().contains("not executable"));
+        assert!(check_executable(&dir.join("missing")).is_err());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn fallbacks_skip_the_failed_browser_and_missing_executables() {
+        let firefox = script("firefox", "exit 0");
+        let dir = firefox.parent().unwrap();
+        let browser = |name: &str, path: PathBuf| Browser { name: name.to_string(), path, ..Default::default() };
+        let failed = browser("Google Chrome", dir.join("chrome"));
+        let detected = vec![
+            browser("Google Chrome", failed.path.clone()),
+            browser("Chromium", dir.join("chromium")),
+            browser("Firefox", firefox.clone()),
+        ];
+
+        let names: Vec<String> = fallback_candidates(&failed, detected).into_iter().map(|b| b.name).collect();
+        assert_eq!(names, ["Firefox"]);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn deep_search_finds_nested_executables_within_depth() {
+        let chrome = script("chrome", "echo 'Vivaldi 6.5'");
+        let root = chrome.parent().unwrap().to_path_buf();
+        let nested = root.join("a/b/Vivaldi Portable");
+        fs::create_dir_all(&nested).unwrap();
+        fs::rename(&chrome, nested.join("vivaldi")).unwrap();
+        // Not executable, inside a skipped directory, or too deep: all ignored
+        fs::write(root.join("a/firefox"), "").unwrap();
+        fs::create_dir_all(root.join("node_modules")).unwrap();
+        fs::copy(nested.join("vivaldi"), root.join("node_modules/brave")).unwrap();
+        let deep = (0..=DEEP_MAX_DEPTH).fold(root.clone(), |dir, i| dir.join(i.to_string()));
+        fs::create_dir_all(&deep).unwrap();
+        fs::copy(nested.join("vivaldi"), deep.join("opera")).unwrap();
+
+        let found = deep_search(std::slice::from_ref(&root), DEEP_MAX_DEPTH);
+        assert_eq!(found.len(), 1, "{:?}", found);
+        assert_eq!(found[0].name, "Vivaldi");
+        assert_eq!(found[0].path, nested.join("vivaldi"));
+        assert_eq!(known_browser_named("google chrome"), Some("Google Chrome"));
+        assert_eq!(known_browser_named("msedge.exe"), Some("Microsoft Edge"));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn search_paths_find_portable_browsers_once() {
+        let chrome = script("portable", "exit 0");
+        let root = chrome.parent().unwrap().to_path_buf();
+        let unpacked = root.join("chrome-linux64");
+        fs::create_dir_all(root.join("usb/FirefoxPortable/App")).unwrap();
+        fs::create_dir_all(&unpacked).unwrap();
+        fs::rename(&chrome, unpacked.join("chrome")).unwrap();
+        fs::copy(unpacked.join("chrome"), root.join("usb/FirefoxPortable/App/firefox")).unwrap();
+
+        let options = DetectOptions {
+            search_paths: vec![root.clone(), root.join("usb/.."), root.join("missing")],
+            ..DetectOptions::from_config(&AppConfig::default())
+        };
+        let found = search_path_browsers(&options);
+        let canonical = fs::canonicalize(unpacked.join("chrome")).unwrap();
+        assert_eq!(found.iter().map(|b| &b.path).collect::<Vec<_>>(), [&canonical]);
+        assert_eq!(found[0].name, "Google Chrome");
+
+        // Three levels down is only reached by --deep
+        let found = search_path_browsers(&DetectOptions { deep: true, ..options });
+        assert_eq!(found.len(), 2, "{:?}", found);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn versions_are_probed_in_parallel_and_keep_their_order() {
+        let mut browsers: Vec<Browser> = ["slow_a", "slow_b", "slow_c"].iter()
+            .map(|name| Browser {
+                name: name.to_string(),
+                path: script(name, &format!("sleep 0.5; echo '{} 1.0'", name)),
+                ..Default::default()
+            })
+            .collect();
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(3).build().unwrap();
+
+        let started = Instant::now();
+        probe_versions(&mut browsers, Some(&pool));
+        assert!(started.elapsed() < Duration::from_millis(1200), "probes took {:?}", started.elapsed());
+        let versions: Vec<_> = browsers.iter().map(|b| b.version.as_deref().unwrap()).collect();
+        assert_eq!(versions, ["slow_a 1.0", "slow_b 1.0", "slow_c 1.0"]);
+        for b in &browsers {
+            let _ = fs::remove_dir_all(b.path.parent().unwrap());
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn version_probe_is_killed_after_timeout() {
+        let path = script("hang", "sleep 10");
+        let started = Instant::now();
+        assert_eq!(output_with_timeout(Command::new(&path).arg("--version"), Duration::from_millis(300)), None);
+        assert!(started.elapsed() < Duration::from_secs(5), "probe waited {:?}", started.elapsed());
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        let path = script("quick", "echo 'Fake Browser 1.2.3'");
+        assert_eq!(get_version(&path).as_deref(), Some("Fake Browser 1.2.3"));
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn only_known_families_get_a_version_probe() {
+        assert!(is_known_family(Path::new("C:\\Program Files\\Google\\Chrome\\Application\\chrome.exe")));
+        assert!(is_known_family(Path::new("/var/lib/flatpak/exports/bin/org.mozilla.firefox")));
+        assert!(is_known_family(Path::new("/usr/bin/vivaldi-stable")));
+        assert!(!is_known_family(Path::new("C:\\Program Files\\Vendor\\launcher.exe")));
+    }
+
+    #[test]
+    fn detection_cache_expires_and_checks_paths() {
+        let exe = env::current_exe().unwrap();
+        let cache = |detected_at, path: &Path| DetectionCache {
+            detected_at,
+            browsers: vec![Browser { name: "Chrome".to_string(), path: path.to_path_buf(), version: None, ..Default::default() }],
+        };
+        let day = Duration::from_secs(24 * 60 * 60);
+
+        assert_eq!(fresh_browsers(cache(1_000, &exe), 1_000 + 60, day).map(|b| b.len()), Some(1));
+        assert!(fresh_browsers(cache(1_000, &exe), 1_000 + day.as_secs(), day).is_none());
+        // A cache from the future (clock changed) is not trusted
+        assert!(fresh_browsers(cache(5_000, &exe), 1_000, day).is_none());
+        assert!(fresh_browsers(cache(1_000, Path::new("/nonexistent/chrome")), 1_060, day).is_none());
+    }
+
+    #[test]
+    fn desktop_entries_for_browsers_are_parsed() {
+        let firefox = "[Desktop Entry]\nName=Firefox\nName[de]=Firefox-Webbrowser\nExec=/usr/lib/firefox/firefox %u\n\
+                       Categories=GNOME;GTK;Network;WebBrowser;\n\n[Desktop Action new-window]\nName=New Window\nExec=/usr/lib/firefox/firefox --new-window %u\n";
+        assert_eq!(
+            parse_desktop_entry(firefox),
+            Some(DesktopEntry { name: "Firefox".to_string(), exec: vec!["/usr/lib/firefox/firefox".to_string()] })
+        );
+
+        let editor = "[Desktop Entry]\nName=Text Editor\nExec=gedit %U\nCategories=GNOME;Utility;TextEditor;\n";
+        assert_eq!(parse_desktop_entry(editor), None);
+        let hidden = "[Desktop Entry]\nName=Old Chrome\nExec=chrome\nCategories=WebBrowser;\nHidden=true\n";
+        assert_eq!(parse_desktop_entry(hidden), None);
+    }
+
+    #[test]
+    fn exec_field_codes_and_quotes_are_handled() {
+        assert_eq!(exec_words("/usr/bin/google-chrome-stable %U"), vec!["/usr/bin/google-chrome-stable"]);
+        assert_eq!(
+            exec_words("/usr/bin/flatpak run --branch=stable org.mozilla.firefox @@u %u @@"),
+            vec!["/usr/bin/flatpak", "run", "--branch=stable", "org.mozilla.firefox", "@@u", "@@"]
+        );
+        assert_eq!(exec_words(r#""/opt/My Browser/browser" --name=100%% %F"#), vec!["/opt/My Browser/browser", "--name=100%"]);
+    }
+
+    #[test]
+    fn macos_http_handler_maps_to_app_executable() {
+        let prefs = r#"{"LSHandlers": [
+            {"LSHandlerContentType": "public.html", "LSHandlerRoleAll": "com.apple.safari"},
+            {"LSHandlerURLScheme": "mailto", "LSHandlerRoleAll": "com.apple.mail"},
+            {"LSHandlerURLScheme": "http", "LSHandlerRoleAll": "com.google.chrome"}
+        ]}"#;
+        assert_eq!(http_handler_bundle_id(prefs).as_deref(), Some("com.google.chrome"));
+        assert_eq!(http_handler_bundle_id("{}"), None);
+
+        let apps = env::temp_dir().join(format!("quick_tabs_apps_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&apps);
+        for (app, id, exe) in [("Safari", "com.apple.Safari", "Safari"), ("Google Chrome", "com.google.Chrome", "Google Chrome")] {
+            let contents = apps.join(format!("{}.app/Contents", app));
+            fs::create_dir_all(contents.join("MacOS")).unwrap();
+            fs::write(contents.join("MacOS").join(exe), "").unwrap();
+            let info = format!(
+                "<plist><dict>\n  <key>CFBundleExecutable</key>\n  <string>{}</string>\n  <key>CFBundleIdentifier</key>\n  <string>{}</string>\n</dict></plist>",
+                exe, id
+            );
+            fs::write(contents.join("Info.plist"), info).unwrap();
+        }
+
+        let (name, path) = app_for_bundle_id(&apps, "com.google.chrome").unwrap();
+        assert_eq!(name, "Google Chrome");
+        assert_eq!(path, apps.join("Google Chrome.app/Contents/MacOS/Google Chrome"));
+        assert!(app_for_bundle_id(&apps, "org.mozilla.firefox").is_none());
+        assert!(app_for_bundle_id(&apps.join("missing"), "com.apple.safari").is_none());
+        let _ = fs::remove_dir_all(&apps);
+    }
+
+    #[test]
+    fn registry_command_keeps_quoted_path_with_spaces() {
+        assert_eq!(
+            command_executable(r#""C:\Program Files\Mozilla Firefox\firefox.exe""#),
+            r"C:\Program Files\Mozilla Firefox\firefox.exe"
+        );
+        assert_eq!(
+            command_executable(r#""C:\Program Files\Google\Chrome\Application\chrome.exe" -- "%1""#),
+            r"C:\Program Files\Google\Chrome\Application\chrome.exe"
+        );
+    }
+
+    #[test]
+    fn registry_command_unquoted_path_ends_at_whitespace() {
+        assert_eq!(command_executable(r"C:\Browsers\brave.exe"), r"C:\Browsers\brave.exe");
+        assert_eq!(command_executable(r"C:\Browsers\brave.exe %1"), r"C:\Browsers\brave.exe");
+        assert_eq!(command_executable(r#"  C:\Opera\launcher.exe --single-argument "%1""#), r"C:\Opera\launcher.exe");
+    }
+
+    #[test]
+    fn registry_command_unterminated_quote_takes_rest() {
+        assert_eq!(command_executable(r#""C:\Program Files\x.exe"#), r"C:\Program Files\x.exe");
+    }
+
+    fn browser(name: &str, path: &str) -> Browser {
+        Browser { name: name.to_string(), path: PathBuf::from(path), version: None, ..Default::default() }
+    }
+
+    #[test]
+    fn preferred_browser_sorts_first() {
+        let mut found = vec![
+            browser("Google Chrome", "/usr/bin/chrome"),
+            browser("Mozilla Firefox", "/usr/bin/firefox"),
+            browser("Chromium", "/usr/bin/chromium"),
+        ];
+        sort_by_preference(&mut found, &["chromium".to_string(), "Google Chrome".to_string()]);
+
+        let names: Vec<&str> = found.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, ["Chromium", "Google Chrome", "Mozilla Firefox"]);
+        assert_eq!(preference_rank(&found[0], &["chromium".to_string()]), Some(0));
+    }
+
+    #[test]
+    fn channels_are_named_and_kept_separate() {
+        let stable = "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome";
+        let canary = "/Applications/Google Chrome Canary.app/Contents/MacOS/Google Chrome Canary";
+        let found = dedup_by_path(vec![
+            browser(&with_channel("Google Chrome", Path::new(stable)), stable),
+            browser(&with_channel("Google Chrome", Path::new(canary)), canary),
+            browser(&with_channel("Google Chrome", Path::new(canary)), canary),
+        ]);
+
+        let names: Vec<&str> = found.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, ["Google Chrome", "Google Chrome Canary"]);
+        assert_eq!(with_channel("Google Chrome Dev", Path::new("/usr/bin/google-chrome-unstable")), "Google Chrome Dev");
+        assert_eq!(channel_of(Path::new("/opt/Firefox Nightly/firefox")), Some("Nightly"));
+        assert_eq!(channel_of(Path::new("/home/dev/bin/firefox")), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_installs_are_found_once() {
+        let dir = env::temp_dir().join(format!("quick_tabs_dedup_{}", std::process::id()));
+        fs::create_dir_all(dir.join("lib")).unwrap();
+        let target = dir.join("lib/firefox");
+        let link = dir.join("firefox");
+        fs::write(&target, "").unwrap();
+        let _ = fs::remove_file(&link);
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let found = dedup_by_path(vec![
+            browser("Mozilla Firefox", link.to_str().unwrap()),
+            browser("Mozilla Firefox", target.to_str().unwrap()),
+            browser("Mozilla Firefox", "/snap/bin/firefox"),
+            browser("Chromium", "/snap/bin/chromium"),
+        ]);
+        let paths: Vec<&Path> = found.iter().map(|b| b.path.as_path()).collect();
+        assert_eq!(paths, [link.as_path(), Path::new("/snap/bin/firefox"), Path::new("/snap/bin/chromium")]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn one_executable_under_two_names_keeps_the_display_name() {
+        let found = dedup_by_path(vec![
+            browser("brave", "/opt/brave.com/brave/brave"),
+            browser("Mozilla Firefox", "/usr/bin/firefox"),
+            browser("Brave", "/opt/brave.com/brave/brave"),
+            browser("firefox", "/usr/bin/firefox"),
+        ]);
+
+        let names: Vec<&str> = found.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, ["Brave", "Mozilla Firefox"]);
+        assert_eq!(found[0].path, Path::new("/opt/brave.com/brave/brave"));
+    }
+
+    #[test]
+    fn selection_without_a_prompt() {
+        let chrome = browser("Google Chrome", "/usr/bin/google-chrome");
+        let firefox = browser("Mozilla Firefox", "/usr/bin/firefox");
+
+        let only = interactive_select(vec![firefox.clone()], &[]);
+        assert_eq!(only.map(|b| b.path), Some(firefox.path.clone()));
+
+        let preferred = interactive_select(vec![firefox.clone(), chrome], &["firefox".to_string()]);
+        assert_eq!(preferred.map(|b| b.path), Some(firefox.path));
+    }
+
+    #[test]
+    fn latest_only_keeps_newest_chrome() {
+        let mut old = browser("Google Chrome", "/opt/chrome-old/chrome");
+        old.version = Some("Google Chrome 118.0.5993.70".to_string());
+        let mut new = browser("Google Chrome", "/opt/google/chrome/chrome");
+        new.version = Some("Google Chrome 120.0.6099.109".to_string());
+        let firefox = browser("Mozilla Firefox", "/usr/bin/firefox");
+
+        let kept = keep_latest(vec![old.clone(), firefox, new]);
+        let paths: Vec<&str> = kept.iter().map(|b| b.path.to_str().unwrap()).collect();
+        assert_eq!(paths, ["/opt/google/chrome/chrome", "/usr/bin/firefox"]);
+
+        // Equal versions: the first entry stays
+        let twin = Browser { path: PathBuf::from("/usr/bin/chrome"), ..old.clone() };
+        let kept = keep_latest(vec![old, twin]);
+        assert_eq!(kept[0].path, PathBuf::from("/opt/chrome-old/chrome"));
+    }
+
+    #[test]
+    fn unlisted_browsers_keep_their_order() {
+        let mut found = vec![
+            browser("Brave", "/usr/bin/brave"),
+            browser("Opera", "/usr/bin/opera"),
+            browser("Mozilla Firefox", "/usr/bin/firefox"),
+        ];
+        sort_by_preference(&mut found, &["firefox".to_string(), "safari".to_string()]);
+
+        let names: Vec<&str> = found.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, ["Mozilla Firefox", "Brave", "Opera"]);
+        assert_eq!(preference_rank(&found[1], &["firefox".to_string()]), None);
+    }
+
+    #[test]
+    fn names_must_match_one_install() {
+        let found = vec![
+            browser("Mozilla Firefox", "/usr/bin/firefox"),
+            browser("Mozilla Firefox", "/snap/bin/firefox"),
+            browser("Brave", "/usr/bin/brave"),
+        ];
+        assert_eq!(unique_match(&found, "brave").unwrap().map(|b| b.path.as_path()), Some(Path::new("/usr/bin/brave")));
+        assert!(unique_match(&found, "opera").unwrap().is_none());
+        let err = unique_match(&found, "firefox").unwrap_err();
+        assert!(err.to_string().contains("/snap/bin/firefox"), "{}", err);
+    }
+}
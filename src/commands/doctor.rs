@@ -0,0 +1,140 @@
+// src/commands/doctor.rs
+use std::fs;
+use std::io;
+use std::path::Path;
+use serde::de::DeserializeOwned;
+use crate::commands::aliases::AliasConfig;
+use crate::commands::blocklist::{blocklist_path, Blocklist};
+use crate::commands::config::{app_config_dir, browser_config_path, settings_path, AppConfig, ConfigPaths};
+use crate::commands::detect::{detect_browsers, read_saved_browser, DetectOptions};
+use crate::commands::error::QuickTabsError;
+use crate::commands::groups::GroupConfig;
+use crate::commands::history::{self, history_path};
+use crate::commands::links::LinkConfig;
+use crate::say;
+
+/// Prints a read-only health report: the config directory, whether each state file
+/// exists and parses, the saved browser and the browsers detection would find.
+///
+/// Fails when a file does not parse or the saved browser's executable is gone, so
+/// `quick_tabs doctor` can be used as a health check. Nothing is written: the
+/// detection cache is reused when fresh but never refreshed, and `browsers.json`
+/// and `browsers.txt` are left alone.
+pub fn run(paths: &ConfigPaths, options: &DetectOptions) -> Result<(), QuickTabsError> {
+    let mut problems = 0;
+    say!("🩺 Quick Tabs doctor");
+    say!("📁 Config directory: {}", app_config_dir()?.display());
+
+    say!("\n📄 Files:");
+    problems += check_json(&paths.links, "Links", |c: &LinkConfig| format!("{} link(s)", c.links.len()));
+    problems += check_json(&paths.aliases, "Aliases", |c: &AliasConfig| format!("{} alias(es)", c.aliases.len()));
+    problems += check_json(&paths.groups, "Groups", |c: &GroupConfig| format!("{} group(s)", c.groups.len()));
+    problems += check_json(&settings_path()?, "Settings", |_: &AppConfig| "parsed".to_string());
+
+    let blocklist = blocklist_path()?;
+    if blocklist.exists() {
+        report(true, "Blocklist", &blocklist, &format!("{} pattern(s)", Blocklist::load(&blocklist).patterns.len()));
+    } else {
+        report_missing("Blocklist", &blocklist);
+    }
+    let history = history_path()?;
+    match history::load(&history) {
+        Ok(_) if !history.exists() => report_missing("History", &history),
+        Ok(entries) => report(true, "History", &history, &format!("{} launch(es)", entries.len())),
+        Err(e) => {
+            report(false, "History", &history, &e.to_string());
+            problems += 1;
+        }
+    }
+
+    say!("\n🌐 Saved browser:");
+    let browser_config = browser_config_path()?;
+    match read_saved_browser(&browser_config) {
+        Ok(Some(browser)) if browser.path.exists() => {
+            report(true, &browser.name, &browser.path, browser.version.as_deref().unwrap_or("version unknown"));
+        }
+        Ok(Some(browser)) => {
+            report(false, &browser.name, &browser.path, "executable no longer exists; run `quick_tabs detect --refresh`");
+            problems += 1;
+        }
+        Ok(None) => report_missing("Browser config", &browser_config),
+        Err(e) => {
+            report(false, "Browser config", &browser_config, &e.to_string());
+            problems += 1;
+        }
+    }
+
+    say!("\n🔍 Detectable browsers:");
+    let detected = detect_browsers(options);
+    if detected.is_empty() {
+        say!("  ⚠️ None found");
+    }
+    for b in &detected {
+        say!("  - {} ({}, version: {})", b.name, b.path.display(), b.version.as_deref().unwrap_or("unknown"));
+    }
+
+    match problems {
+        0 => {
+            say!("\n✅ No problems found.");
+            Ok(())
+        }
+        n => Err(QuickTabsError::Other(format!("doctor found {} problem(s)", n))),
+    }
+}
+
+/// Reports whether the JSON file at `path` parses as `T`. A missing file is fine:
+/// every command falls back to an empty config. Returns the number of problems (0 or 1).
+fn check_json<T: DeserializeOwned>(path: &Path, label: &str, describe: impl Fn(&T) -> String) -> usize {
+    let data = match fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            report_missing(label, path);
+            return 0;
+        }
+        Err(e) => {
+            report(false, label, path, &e.to_string());
+            return 1;
+        }
+    };
+    match serde_json::from_str::<T>(&data) {
+        Ok(config) => {
+            report(true, label, path, &describe(&config));
+            0
+        }
+        Err(e) => {
+            report(false, label, path, &format!("does not parse: {}", e));
+            1
+        }
+    }
+}
+
+fn report(ok: bool, label: &str, path: &Path, detail: &str) {
+    say!("  {} {}: {} ({})", if ok { "✅" } else { "❌" }, label, path.display(), detail);
+}
+
+fn report_missing(label: &str, path: &Path) {
+    say!("  ➖ {}: {} (not created yet)", label, path.display());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_unparseable_files_are_problems() {
+        let dir = std::env::temp_dir().join(format!("quick_tabs_doctor_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let count = |c: &LinkConfig| c.links.len().to_string();
+
+        let good = dir.join("links.json");
+        fs::write(&good, r#"{"links":[{"tag":"a","url":"https://a.com"}]}"#).unwrap();
+        assert_eq!(check_json(&good, "Links", count), 0);
+
+        let bad = dir.join("broken.json");
+        fs::write(&bad, "{not json").unwrap();
+        assert_eq!(check_json(&bad, "Links", count), 1);
+
+        assert_eq!(check_json(&dir.join("missing.json"), "Links", count), 0);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
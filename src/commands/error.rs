@@ -0,0 +1,99 @@
+// src/commands/error.rs
+use std::fmt;
+use std::io;
+use clap::ValueEnum;
+use crate::commands::output::decorate;
+
+/// How failures are reported on stderr.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ErrorFormat {
+    /// Human-readable message
+    Text,
+    /// A single `{ "error": "...", "code": N }` object
+    Json,
+}
+
+/// Errors surfaced to the user. Each variant has a stable exit code so scripts can
+/// tell failures apart without parsing the message.
+#[derive(Debug)]
+pub enum QuickTabsError {
+    /// Anything without a more specific variant
+    Other(String),
+    /// Reading or writing a file failed
+    Io(io::Error),
+    /// No browser is configured or detected
+    NoBrowser,
+    /// A tag, group or other named entry does not exist
+    NotFound(String),
+    /// Importing from a browser or file failed
+    Import(String),
+    /// No config directory could be determined (no config dir, XDG_CONFIG_HOME or HOME)
+    NoConfigDir,
+    /// The browser could not be started
+    Launch(String),
+    /// The browser waited on with `--wait` exited with this non-zero code
+    BrowserExited(i32),
+}
+
+impl QuickTabsError {
+    /// Process exit code for this error. Code 2 is left to clap for usage errors.
+    pub fn code(&self) -> i32 {
+        match self {
+            QuickTabsError::Other(_) => 1,
+            QuickTabsError::Io(_) => 3,
+            QuickTabsError::NoBrowser => 4,
+            QuickTabsError::NotFound(_) => 5,
+            QuickTabsError::Import(_) => 6,
+            QuickTabsError::NoConfigDir => 7,
+            QuickTabsError::Launch(_) => 8,
+            // Passed through, so scripts see the browser's own status
+            QuickTabsError::BrowserExited(code) => *code,
+        }
+    }
+
+    /// Prints the error to stderr in the requested format.
+    pub fn report(&self, format: ErrorFormat) {
+        match format {
+            // Not `esay!`: errors are printed even with --quiet
+            ErrorFormat::Text => eprintln!("{}", decorate(&format!("❌ Error: {}", self))),
+            ErrorFormat::Json => eprintln!(
+                "{}",
+                serde_json::json!({ "error": self.to_string(), "code": self.code() })
+            ),
+        }
+    }
+}
+
+impl fmt::Display for QuickTabsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuickTabsError::Other(msg) => write!(f, "{}", msg),
+            QuickTabsError::Io(e) => write!(f, "I/O error: {}", e),
+            QuickTabsError::NoBrowser => {
+                write!(f, "No browser configured. Run 'quick_tabs detect' or set manually.")
+            }
+            QuickTabsError::NotFound(what) => write!(f, "{} not found", what),
+            QuickTabsError::Import(msg) => write!(f, "Import failed: {}", msg),
+            QuickTabsError::NoConfigDir => write!(
+                f,
+                "Could not determine a config directory. Set HOME, XDG_CONFIG_HOME or QUICK_TABS_CONFIG_DIR."
+            ),
+            QuickTabsError::Launch(msg) => write!(f, "Launch failed: {}", msg),
+            QuickTabsError::BrowserExited(code) => write!(f, "The browser exited with status {}", code),
+        }
+    }
+}
+
+impl std::error::Error for QuickTabsError {}
+
+impl From<io::Error> for QuickTabsError {
+    fn from(e: io::Error) -> Self {
+        QuickTabsError::Io(e)
+    }
+}
+
+impl From<String> for QuickTabsError {
+    fn from(msg: String) -> Self {
+        QuickTabsError::Other(msg)
+    }
+}
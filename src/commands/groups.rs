@@ -0,0 +1,183 @@
+// src/commands/groups.rs
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use crate::commands::config::{paint, write_atomic, GroupMeta};
+use crate::commands::error::QuickTabsError;
+use crate::commands::links::{Change, ChangeSet, LinkConfig};
+use crate::commands::output::is_plain;
+use crate::commands::schema::{self, load_config};
+use crate::{esay, say};
+
+/// Named sets of link tags that are meant to be opened together.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GroupConfig {
+    pub groups: BTreeMap<String, Vec<String>>,
+}
+
+impl GroupConfig {
+    /// Reads the groups from `path`; see `schema::load_config` for missing, damaged and
+    /// newer files.
+    pub fn load(path: &Path) -> Result<Self, QuickTabsError> {
+        load_config(path, "groups", "group", GroupConfig::default)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = schema::to_json(&self)?;
+        write_atomic(path, json)
+    }
+
+    /// Creates the group `name`, replacing its members if it already exists.
+    /// Members must be link tags: a member naming a group is rejected, since nested
+    /// groups could reference each other in a loop.
+    pub fn add_group(&mut self, name: String, tags: Vec<String>, links: &LinkConfig) -> Result<ChangeSet, QuickTabsError> {
+        if let Some(nested) = tags.iter().find(|t| self.is_group_reference(&name, t, links)) {
+            return Err(QuickTabsError::Other(format!(
+                "Group '{}' cannot contain '{}': it refers to a group, and nested groups are not supported",
+                name, nested
+            )));
+        }
+
+        let new = tags.join(", ");
+        Ok(match self.groups.insert(name.clone(), tags) {
+            Some(old) => Change::Updated { kind: "group", tag: name, old: old.join(", "), new },
+            None => Change::Added { kind: "group", tag: name, value: new },
+        }
+        .into())
+    }
+
+    /// Whether `tag`, as a member of group `name`, refers to a group rather than a
+    /// link: `group:<x>`, the group itself, or another group's name with no link of
+    /// that tag.
+    fn is_group_reference(&self, name: &str, tag: &str, links: &LinkConfig) -> bool {
+        tag.starts_with("group:")
+            || (!links.contains(tag) && (tag == name || self.groups.contains_key(tag)))
+    }
+
+    /// Appends `tag` to `name`, creating the group if needed. Duplicates are ignored.
+    pub fn add_to_group(&mut self, name: &str, tag: &str) -> ChangeSet {
+        let tags = self.groups.entry(name.to_string()).or_default();
+        if tags.iter().any(|t| t == tag) {
+            return ChangeSet::default();
+        }
+        let old = tags.join(", ");
+        tags.push(tag.to_string());
+        let new = tags.join(", ");
+        if old.is_empty() {
+            Change::Added { kind: "group", tag: name.to_string(), value: new }.into()
+        } else {
+            Change::Updated { kind: "group", tag: name.to_string(), old, new }.into()
+        }
+    }
+
+    /// Removes the group `name`; the change set is empty when there is none.
+    pub fn remove_group(&mut self, name: &str) -> ChangeSet {
+        match self.groups.remove(name) {
+            Some(tags) => Change::Removed { kind: "group", tag: name.to_string(), value: tags.join(", ") }.into(),
+            None => ChangeSet::default(),
+        }
+    }
+
+    /// Expands a group into the URLs of its member links, in group order.
+    /// Members without a saved link are skipped with a warning.
+    pub fn resolve(&self, name: &str, links: &LinkConfig) -> Option<Vec<String>> {
+        let tags = self.groups.get(name)?;
+        let mut urls = vec![];
+        for tag in tags {
+            match links.get_urls(tag) {
+                Some(link_urls) => urls.extend(link_urls),
+                // On stderr, so `resolve-all --json` output stays parseable
+                None if self.is_group_reference(name, tag, links) => {
+                    esay!("⚠️ '{}' in group '{}' is a nested group reference, skipping.", tag, name)
+                }
+                None => esay!("⚠️ Link tag '{}' in group '{}' not found, skipping.", tag, name),
+            }
+        }
+        Some(urls)
+    }
+
+    /// Prints every group with its member tags.
+    pub fn list(&self, meta: &HashMap<String, GroupMeta>) {
+        if self.groups.is_empty() {
+            say!("⚠️ No groups saved.");
+            return;
+        }
+        for (name, tags) in &self.groups {
+            println!("{} {}", group_header(name, meta.get(name)), tags.join(", "));
+        }
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.groups.contains_key(name)
+    }
+
+    /// Lists links under a header per group, colored and prefixed with the group's icon
+    /// when metadata is set. Links that belong to no group are listed last.
+    pub fn list_by_group(&self, links: &LinkConfig, meta: &HashMap<String, GroupMeta>) {
+        if links.links.is_empty() {
+            say!("⚠️ No links saved.");
+            return;
+        }
+
+        for (name, tags) in &self.groups {
+            println!("\n{}", group_header(name, meta.get(name)));
+            for tag in tags {
+                match links.get_urls(tag) {
+                    Some(urls) => println!("  [{}] {}", tag, urls.join(" ")),
+                    None => say!("  [{}] ⚠️ missing link", tag),
+                }
+            }
+        }
+
+        let ungrouped: Vec<_> = links.links.iter()
+            .filter(|l| !self.groups.values().any(|tags| tags.contains(&l.tag)))
+            .collect();
+        if !ungrouped.is_empty() {
+            say!("\n📄 Ungrouped:");
+            for l in ungrouped {
+                println!("  [{}] {}", l.tag, l.urls_text());
+            }
+        }
+    }
+}
+
+fn group_header(name: &str, meta: Option<&GroupMeta>) -> String {
+    let icon = meta.and_then(|m| m.icon.as_deref()).unwrap_or(if is_plain() { "[group]" } else { "📁" });
+    let header = format!("{} {}:", icon, name);
+    match meta.and_then(|m| m.color.as_deref()) {
+        Some(color) => paint(&header, color),
+        None => header,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::links::Link;
+
+    fn links() -> LinkConfig {
+        LinkConfig { links: vec![Link::new("news", "https://news.example")] }
+    }
+
+    #[test]
+    fn nested_group_references_are_rejected() {
+        let links = links();
+        let mut groups = GroupConfig::default();
+        groups.add_group("morning".to_string(), vec!["news".to_string()], &links).unwrap();
+
+        assert!(groups.add_group("day".to_string(), vec!["morning".to_string()], &links).is_err());
+        assert!(groups.add_group("day".to_string(), vec!["group:morning".to_string()], &links).is_err());
+        assert!(groups.add_group("loop".to_string(), vec!["loop".to_string()], &links).is_err());
+        assert!(!groups.contains("day"));
+    }
+
+    #[test]
+    fn resolve_expands_member_urls() {
+        let links = links();
+        let mut groups = GroupConfig::default();
+        groups.add_group("morning".to_string(), vec!["news".to_string(), "missing".to_string()], &links).unwrap();
+        assert_eq!(groups.resolve("morning", &links), Some(vec!["https://news.example".to_string()]));
+        assert_eq!(groups.resolve("evening", &links), None);
+    }
+}
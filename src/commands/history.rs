@@ -0,0 +1,137 @@
+// src/commands/history.rs
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use clap::ValueEnum;
+use serde::{Serialize, Deserialize};
+use crate::commands::config::{app_config_dir, write_atomic};
+use crate::commands::csv;
+use crate::commands::error::QuickTabsError;
+use crate::esay;
+
+// --- Data Structures ---
+
+/// One line of `history.jsonl`: a successful launch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    /// Tag the URL was launched by; `None` for raw URLs
+    #[serde(default)]
+    pub tag: Option<String>,
+    pub url: String,
+}
+
+impl HistoryEntry {
+    /// A launch happening now.
+    pub fn now(tag: Option<String>, url: String) -> Self {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        HistoryEntry { timestamp, tag, url }
+    }
+}
+
+/// Launches kept in the history; older ones are dropped as new ones are recorded.
+pub const MAX_ENTRIES: usize = 50;
+
+/// File formats accepted by `history export`.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+// --- Loading ---
+
+/// Gets the history path (~/.config/quick_tabs/history.jsonl)
+pub fn history_path() -> Result<PathBuf, QuickTabsError> {
+    Ok(app_config_dir()?.join("history.jsonl"))
+}
+
+/// Reads every entry from a JSONL history file. A missing file is an empty history;
+/// unparseable lines are skipped with a warning.
+pub fn load(path: &Path) -> io::Result<Vec<HistoryEntry>> {
+    let data = match fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    let mut entries = vec![];
+    for (i, line) in data.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        match serde_json::from_str(line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => esay!("⚠️ Skipping history line {}: {}", i + 1, e),
+        }
+    }
+    Ok(entries)
+}
+
+/// The entries newest first, numbered from 1 as `history` lists them and `replay`
+/// takes them.
+pub fn recent(entries: &[HistoryEntry]) -> impl Iterator<Item = (usize, &HistoryEntry)> {
+    entries.iter().rev().enumerate().map(|(i, e)| (i + 1, e))
+}
+
+// --- Recording ---
+
+/// Appends launches to the history file, keeping only the newest `MAX_ENTRIES`.
+pub fn record(path: &Path, launched: impl IntoIterator<Item = HistoryEntry>) -> io::Result<()> {
+    let mut entries = load(path)?;
+    entries.extend(launched);
+    let mut data = String::new();
+    for entry in &entries[entries.len().saturating_sub(MAX_ENTRIES)..] {
+        data.push_str(&serde_json::to_string(entry)?);
+        data.push('\n');
+    }
+    write_atomic(path, data)
+}
+
+/// Keeps only entries launched within `window` of now.
+pub fn filter_since(entries: Vec<HistoryEntry>, window: Duration) -> Vec<HistoryEntry> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let cutoff = now.saturating_sub(window.as_secs());
+    entries.into_iter().filter(|e| e.timestamp >= cutoff).collect()
+}
+
+// --- Export ---
+
+/// Serializes entries as a CSV table (with header) or a JSON array. An empty history
+/// still produces a header row or `[]`.
+pub fn export<W: Write>(entries: &[HistoryEntry], format: ExportFormat, out: &mut W) -> io::Result<()> {
+    match format {
+        ExportFormat::Csv => {
+            csv::write_row(out, &["timestamp", "tag", "url"])?;
+            for e in entries {
+                let timestamp = e.timestamp.to_string();
+                csv::write_row(out, &[&timestamp, e.tag.as_deref().unwrap_or(""), &e.url])?;
+            }
+        }
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, entries)?;
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn history_keeps_the_newest_entries() {
+        let path = env::temp_dir().join(format!("quick_tabs_history_{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        record(&path, (0..MAX_ENTRIES).map(|i| HistoryEntry::now(None, format!("https://{}.example", i)))).unwrap();
+        record(&path, [HistoryEntry::now(Some("docs".to_string()), "https://docs.rs".to_string())]).unwrap();
+
+        let entries = load(&path).unwrap();
+        assert_eq!(entries.len(), MAX_ENTRIES);
+        assert_eq!(entries[0].url, "https://1.example");
+        let (index, newest) = recent(&entries).next().unwrap();
+        assert_eq!((index, newest.tag.as_deref(), newest.url.as_str()), (1, Some("docs"), "https://docs.rs"));
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,42 @@
+// src/commands/hooks.rs
+use std::process::{Command, Stdio};
+use crate::commands::detect::Browser;
+use crate::commands::links::LaunchMode;
+use crate::esay;
+
+/// Runs the user's post-launch hook through the shell without waiting for it.
+///
+/// The hook receives the launch details as environment variables:
+/// - `QT_URLS`: the launched URLs, one per line
+/// - `QT_BROWSER`: full path of the browser executable
+/// - `QT_MODE`: `normal`, `private` or `container`
+///
+/// A failing hook never fails the launch; it only prints a warning.
+pub fn run_post_launch_hook(hook: &str, browser: &Browser, urls: &[&str], mode: LaunchMode) {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut c = Command::new("cmd");
+        c.args(["/C", hook]);
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.args(["-c", hook]);
+        c
+    };
+
+    let mode = match mode {
+        LaunchMode::Normal => "normal",
+        LaunchMode::Private => "private",
+        LaunchMode::Container => "container",
+    };
+
+    let result = command
+        .env("QT_URLS", urls.join("\n"))
+        .env("QT_BROWSER", &browser.path)
+        .env("QT_MODE", mode)
+        .stdin(Stdio::null())
+        .spawn();
+
+    if let Err(e) = result {
+        esay!("⚠️ Post-launch hook failed to start: {}", e);
+    }
+}
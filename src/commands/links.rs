@@ -0,0 +1,1647 @@
+// src/commands/links.rs
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use std::process::{Child, Command};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::commands::aliases::AliasConfig;
+use crate::commands::config::{rotate_backups, write_atomic};
+use crate::commands::blocklist::Blocklist;
+use crate::commands::detect::{browser_matches, fallback_browsers, replace_missing_default, Browser, DetectOptions, InstallKind};
+use crate::commands::error::QuickTabsError;
+use crate::commands::glob::glob_match;
+use crate::commands::schema::{self, load_config};
+use crate::commands::hooks::run_post_launch_hook;
+use crate::commands::profile::TempProfile;
+use crate::commands::quote::command_line;
+use crate::commands::random::shuffle;
+use crate::commands::url::{comparable_url, expand_env, render_template};
+use crate::{esay, say, verbose};
+use chrono::Local;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use std::io;
+
+// --- Data Structures ---
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Link {
+    pub tag: String,
+    /// Opened together when the tag is launched. Saved as `url`: a string when there
+    /// is one, as in files written before links could hold several, else an array.
+    #[serde(rename = "url", alias = "urls", with = "one_or_many")]
+    pub urls: Vec<String>,
+    /// What the link is for, shown under it in `list-links`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Browser this link always opens in (a detected browser's name, a command or a
+    /// path), instead of the preferred one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub browser: Option<String>,
+    /// Category the link is listed under, e.g. `work` (`list-links` groups by it)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// When the link was saved or last changed (seconds since the Unix epoch)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<u64>,
+    /// When the link was last opened, if known (seconds since the Unix epoch)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<u64>,
+}
+
+/// Serde helpers for `Link::urls`: one URL is written as a plain string, and
+/// either form is read back.
+mod one_or_many {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    pub fn serialize<S: Serializer>(urls: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+        match urls {
+            [url] => url.serialize(serializer),
+            _ => urls.serialize(serializer),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+        Ok(match OneOrMany::deserialize(deserializer)? {
+            OneOrMany::One(url) => vec![url],
+            OneOrMany::Many(urls) => urls,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LinkConfig {
+    pub links: Vec<Link>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchMode {
+    #[default]
+    Normal,
+    Private,
+    /// Private mode inside a fresh, throwaway profile (`--incognito-container`)
+    Container,
+}
+
+/// Picks the launch mode. Precedence, highest first:
+/// 1. an explicit `--incognito`/`--normal` flag
+/// 2. the browser's entry in `AppConfig.browser_default_mode`
+/// 3. normal mode
+pub fn effective_mode(explicit: Option<LaunchMode>, browser: &Browser, defaults: &HashMap<String, LaunchMode>) -> LaunchMode {
+    explicit
+        .or_else(|| {
+            defaults.iter()
+                .find(|(name, _)| browser_matches(browser, name))
+                .map(|(_, mode)| *mode)
+        })
+        .unwrap_or_default()
+}
+
+/// Everything besides the URLs that affects how the browser command is built.
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOptions {
+    pub mode: LaunchMode,
+    /// Suppress first-run and default-browser prompts (`--clean-launch`)
+    pub clean: bool,
+    /// Shell command run after every successful launch (`AppConfig.post_launch_hook`)
+    pub post_launch_hook: Option<String>,
+    /// Refuse URLs matching this blocklist (`--safe-launch` or `AppConfig.safe_launch`)
+    pub blocklist: Option<Blocklist>,
+    /// Browser profile to open in; `None` uses the browser's default profile
+    pub profile: Option<String>,
+    /// Force a new window instead of adding tabs to an existing one
+    pub new_window: bool,
+    /// Firefox Multi-Account Container to open the URLs in (`--container`)
+    pub firefox_container: Option<String>,
+    /// Print the browser command instead of running it (`--dry-run`)
+    pub dry_run: bool,
+    /// Block until the browser process exits and fail with its exit code (`--wait`)
+    pub wait: bool,
+    /// Refuse private launches in browsers with no known private mode (`--strict-private`)
+    pub strict_private: bool,
+    /// When the browser fails to start, detect others with these options and use the
+    /// first that starts; `None` fails straight away
+    pub fallback: Option<DetectOptions>,
+}
+
+/// How a batch of URLs is ordered and split into windows by the open-all/open-group commands.
+#[derive(Debug, Clone, Default)]
+pub struct BatchOptions {
+    /// Open every URL in its own new window (`--window-each`)
+    pub window_each: bool,
+    /// Shuffle the URLs with this seed (`--shuffle`, `--seed`)
+    pub shuffle_seed: Option<u64>,
+    /// Open in reverse saved order, newest first (`--reverse`)
+    pub reverse: bool,
+    /// Launch the URLs one at a time with this pause in between (`--delay`)
+    pub delay: Option<Duration>,
+    /// Only open the first this many entries, after `reverse` or `shuffle_seed` reorder them (`--count`)
+    pub count: Option<usize>,
+}
+
+impl BatchOptions {
+    /// How many of `len` saved entries are opened.
+    pub fn limit(&self, len: usize) -> usize {
+        self.count.map_or(len, |count| count.min(len))
+    }
+
+    /// Reorders entries or resolved URLs before they are launched.
+    pub fn arrange<T>(&self, items: &mut [T]) {
+        if let Some(seed) = self.shuffle_seed {
+            shuffle(items, seed);
+        } else if self.reverse {
+            items.reverse();
+        }
+    }
+
+    /// The saved entries to open: reordered first and then cut to `--count`, so
+    /// `--shuffle --count 3` is three random entries and `--reverse --count 3` the
+    /// three newest.
+    pub fn pick<'a, T>(&self, entries: &'a [T]) -> Vec<&'a T> {
+        let mut picked: Vec<&T> = entries.iter().collect();
+        self.arrange(&mut picked);
+        picked.truncate(self.limit(entries.len()));
+        picked
+    }
+}
+
+/// A single edit made by a mutating command. `kind` names the store it touches
+/// (`link`, `alias`, `group`, `block`, `setting`, ...).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    Added { kind: &'static str, tag: String, value: String },
+    Updated { kind: &'static str, tag: String, old: String, new: String },
+    Removed { kind: &'static str, tag: String, value: String },
+    Renamed { kind: &'static str, old: String, new: String },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added { kind, tag, value } if value.is_empty() => write!(f, "+ {} [{}]", kind, tag),
+            Change::Added { kind, tag, value } => write!(f, "+ {} [{}] {}", kind, tag, value),
+            Change::Updated { kind, tag, old, new } => write!(f, "~ {} [{}] {} -> {}", kind, tag, old, new),
+            Change::Removed { kind, tag, value } if value.is_empty() => write!(f, "- {} [{}]", kind, tag),
+            Change::Removed { kind, tag, value } => write!(f, "- {} [{}] {}", kind, tag, value),
+            Change::Renamed { kind, old, new } => write!(f, "> {} [{}] -> [{}]", kind, old, new),
+        }
+    }
+}
+
+/// The edits a command made to its in-memory config. Mutation methods return one,
+/// and the command either saves the result or, with `--dry-run`, only prints it.
+#[derive(Debug, Default, PartialEq)]
+pub struct ChangeSet {
+    pub changes: Vec<Change>,
+}
+
+impl ChangeSet {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Whether any change replaced an existing entry.
+    pub fn has_updates(&self) -> bool {
+        self.changes.iter().any(|c| matches!(c, Change::Updated { .. }))
+    }
+
+    /// Counts per change type, e.g. `2 added, 1 removed`.
+    pub fn summary(&self) -> String {
+        let count = |f: fn(&Change) -> bool| self.changes.iter().filter(|c| f(c)).count();
+        let parts: Vec<String> = [
+            (count(|c| matches!(c, Change::Added { .. })), "added"),
+            (count(|c| matches!(c, Change::Updated { .. })), "updated"),
+            (count(|c| matches!(c, Change::Removed { .. })), "removed"),
+            (count(|c| matches!(c, Change::Renamed { .. })), "renamed"),
+        ]
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, label)| format!("{} {}", n, label))
+        .collect();
+
+        if parts.is_empty() {
+            "no changes".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    /// Saves through `save` unless this is a dry run, in which case the changes are
+    /// printed and nothing is written. Returns whether anything was saved.
+    pub fn commit<E>(&self, dry_run: bool, save: impl FnOnce() -> Result<(), E>) -> Result<bool, E> {
+        if dry_run {
+            for change in &self.changes {
+                println!("  {}", change);
+            }
+            say!("🔍 Dry run: {}. Nothing was saved.", self.summary());
+            return Ok(false);
+        }
+        if self.is_empty() {
+            return Ok(false);
+        }
+        save()?;
+        Ok(true)
+    }
+}
+
+impl From<Change> for ChangeSet {
+    fn from(change: Change) -> Self {
+        ChangeSet { changes: vec![change] }
+    }
+}
+
+impl Extend<Change> for ChangeSet {
+    fn extend<I: IntoIterator<Item = Change>>(&mut self, iter: I) {
+        self.changes.extend(iter);
+    }
+}
+
+impl IntoIterator for ChangeSet {
+    type Item = Change;
+    type IntoIter = std::vec::IntoIter<Change>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.changes.into_iter()
+    }
+}
+
+/// Whether a tag or its URL contains `needle`, ignoring case (`list-links <filter>`).
+pub fn matches_filter(tag: &str, url: &str, needle: &str) -> bool {
+    let needle = needle.to_lowercase();
+    tag.to_lowercase().contains(&needle) || url.to_lowercase().contains(&needle)
+}
+
+// --- Link Implementation ---
+
+impl Link {
+    /// A single-URL link without timestamps.
+    pub fn new(tag: impl Into<String>, url: impl Into<String>) -> Self {
+        Link {
+            tag: tag.into(),
+            urls: vec![url.into()],
+            description: None,
+            browser: None,
+            category: None,
+            modified: None,
+            last_used: None,
+        }
+    }
+
+    /// The URLs separated by spaces, for listings and change reports.
+    pub fn urls_text(&self) -> String {
+        self.urls.join(" ")
+    }
+
+    /// The later of `modified` and `last_used`, if either is known.
+    pub fn last_touched(&self) -> Option<u64> {
+        self.modified.max(self.last_used)
+    }
+
+    /// Parses one `tag url [url...]` line of `import-links` input, fields separated by
+    /// whitespace. `None` for blank lines and `#` comments; an error for a tag without
+    /// a URL. The URLs are returned as written.
+    pub fn parse_line(line: &str) -> Option<Result<Link, String>> {
+        let mut fields = line.split_whitespace();
+        let tag = fields.next().filter(|tag| !tag.starts_with('#'))?;
+        let urls: Vec<String> = fields.map(str::to_string).collect();
+        if urls.is_empty() {
+            return Some(Err(format!("'{}' has no URL (expected `tag url`)", tag)));
+        }
+        Some(Ok(Link { urls, ..Link::new(tag, "") }))
+    }
+}
+
+/// `links` split by category, categories sorted by name and uncategorized links
+/// last; each keeps its saved order.
+pub fn by_category(links: Vec<&Link>) -> Vec<(Option<&str>, Vec<&Link>)> {
+    let mut categories: BTreeMap<Option<&str>, Vec<&Link>> = BTreeMap::new();
+    for link in links {
+        categories.entry(link.category.as_deref()).or_default().push(link);
+    }
+    // `None` sorts first in a BTreeMap; move it to the end
+    let uncategorized = categories.remove(&None);
+    categories.into_iter().chain(uncategorized.map(|links| (None, links))).collect()
+}
+
+/// Seconds since the Unix epoch, as stored in link timestamps.
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+// --- LinkConfig Implementation ---
+
+impl LinkConfig {
+    /// Reads the links from `path`; see `schema::load_config` for missing, damaged and
+    /// newer files.
+    pub fn load(path: &Path) -> Result<Self, QuickTabsError> {
+        load_config(path, "links", "link", || LinkConfig { links: vec![] })
+    }
+
+    /// Writes the config, keeping the previous file as a backup (see `rotate_backups`).
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = schema::to_json(&self)?;
+        rotate_backups(path)?;
+        write_atomic(path, json)
+    }
+
+    /// Adds a link, stamped as modified now. An existing link with the same tag is an
+    /// error unless `force` is set, in which case it is replaced (and moved to the end).
+    pub fn add_link(&mut self, link: Link, force: bool) -> Result<ChangeSet, QuickTabsError> {
+        let link = Link { modified: Some(unix_now()), last_used: None, ..link };
+        let tag = link.tag.clone();
+        let change = match self.links.iter().position(|l| l.tag == tag) {
+            Some(_) if !force => {
+                return Err(QuickTabsError::Other(format!(
+                    "Link tag '{}' already exists; pass --force to replace it or use `edit-link`", tag
+                )));
+            }
+            Some(pos) => {
+                let old = self.links.remove(pos).urls_text();
+                Change::Updated { kind: "link", tag, old, new: link.urls_text() }
+            }
+            None => Change::Added { kind: "link", tag, value: link.urls_text() },
+        };
+        self.links.push(link);
+        Ok(change.into())
+    }
+
+    /// Changes the URLs of the existing link `tag`, keeping its place in the list.
+    /// Unlike `add_link`, a missing tag is an error.
+    pub fn edit_link(&mut self, tag: &str, urls: Vec<String>) -> Result<ChangeSet, QuickTabsError> {
+        let link = self.links.iter_mut()
+            .find(|l| l.tag == tag)
+            .ok_or_else(|| QuickTabsError::NotFound(format!("Link tag '{}'", tag)))?;
+        if link.urls == urls {
+            return Ok(ChangeSet::default());
+        }
+        let old = link.urls_text();
+        link.urls = urls;
+        link.modified = Some(unix_now());
+        Ok(Change::Updated { kind: "link", tag: tag.to_string(), old, new: link.urls_text() }.into())
+    }
+
+    /// Sets or, with `None`, clears the description of the existing link `tag`.
+    pub fn describe_link(&mut self, tag: &str, description: Option<String>) -> Result<ChangeSet, QuickTabsError> {
+        let link = self.links.iter_mut()
+            .find(|l| l.tag == tag)
+            .ok_or_else(|| QuickTabsError::NotFound(format!("Link tag '{}'", tag)))?;
+        if link.description == description {
+            return Ok(ChangeSet::default());
+        }
+        let old = std::mem::replace(&mut link.description, description.clone());
+        link.modified = Some(unix_now());
+        let tag = tag.to_string();
+        Ok(match (old, description) {
+            (None, Some(value)) => Change::Added { kind: "description", tag, value },
+            (Some(value), None) => Change::Removed { kind: "description", tag, value },
+            (old, new) => Change::Updated { kind: "description", tag, old: old.unwrap_or_default(), new: new.unwrap_or_default() },
+        }
+        .into())
+    }
+
+    /// The URLs saved under `tag`.
+    pub fn get_urls(&self, tag: &str) -> Option<Vec<String>> {
+        self.links.iter().find(|l| l.tag == tag).map(|l| l.urls.clone())
+    }
+
+    pub fn contains(&self, tag: &str) -> bool {
+        self.links.iter().any(|l| l.tag == tag)
+    }
+
+    /// How many URLs opening every link launches.
+    pub fn url_count(&self) -> usize {
+        self.links.iter().map(|l| l.urls.len()).sum()
+    }
+
+    pub fn list(&self) {
+        self.list_filtered(None);
+    }
+
+    /// Like `list`, but only the links whose tag or URL contains `needle` (see `matches_filter`).
+    pub fn list_filtered(&self, needle: Option<&str>) {
+        let shown: Vec<&Link> = self.links.iter()
+            .filter(|l| needle.is_none_or(|n| matches_filter(&l.tag, &l.urls_text(), n)))
+            .collect();
+        match needle {
+            _ if self.links.is_empty() => say!("⚠️ No links saved."),
+            Some(n) if shown.is_empty() => say!("⚠️ No links match '{}'.", n),
+            _ => {
+                say!("\n📄 Saved links:");
+                // Without any categories the list stays flat
+                let categorized = shown.iter().any(|l| l.category.is_some());
+                for (category, links) in by_category(shown) {
+                    if categorized {
+                        say!("\n🏷️ {}:", category.unwrap_or("Uncategorized"));
+                    }
+                    for l in links {
+                        println!("  [{}] {}", l.tag, l.urls_text());
+                        if let Some(description) = &l.description {
+                            println!("      {}", description);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Keeps only the links in `category` (ignoring case); fails when there are none.
+    pub fn keep_category(&mut self, category: &str) -> Result<(), QuickTabsError> {
+        self.links.retain(|l| l.category.as_deref().is_some_and(|c| c.eq_ignore_ascii_case(category)));
+        if self.links.is_empty() {
+            return Err(QuickTabsError::NotFound(format!("Category '{}'", category)));
+        }
+        Ok(())
+    }
+
+    /// Removes the link with `tag`; the change set is empty when there is none.
+    pub fn remove_link(&mut self, tag: &str) -> ChangeSet {
+        match self.links.iter().position(|l| l.tag == tag) {
+            Some(pos) => {
+                let link = self.links.remove(pos);
+                Change::Removed { kind: "link", value: link.urls_text(), tag: link.tag }.into()
+            }
+            None => ChangeSet::default(),
+        }
+    }
+
+    /// Removes every link.
+    pub fn clear(&mut self) -> ChangeSet {
+        let changes = self.links.drain(..)
+            .map(|link| Change::Removed { kind: "link", value: link.urls_text(), tag: link.tag })
+            .collect();
+        ChangeSet { changes }
+    }
+
+    /// Renames the link `old` to `new` and points aliases that target `old` at `new`.
+    /// The alias updates follow the rename in the returned change set.
+    pub fn rename_link(&mut self, old: &str, new: &str, aliases: &mut AliasConfig) -> Result<ChangeSet, QuickTabsError> {
+        if old != new && self.contains(new) {
+            return Err(QuickTabsError::Other(format!("Link tag '{}' already exists", new)));
+        }
+        let link = self.links.iter_mut()
+            .find(|l| l.tag == old)
+            .ok_or_else(|| QuickTabsError::NotFound(format!("Link tag '{}'", old)))?;
+        link.tag = new.to_string();
+        link.modified = Some(unix_now());
+
+        let mut changes = ChangeSet::from(Change::Renamed { kind: "link", old: old.to_string(), new: new.to_string() });
+        changes.extend(aliases.retarget(old, new));
+        Ok(changes)
+    }
+
+    /// Moves the link `tag` to `position` (1-based) in the saved order, which is the
+    /// order `open-all` opens tabs in. Positions past the end move it to the end.
+    pub fn move_link(&mut self, tag: &str, position: usize) -> Result<ChangeSet, QuickTabsError> {
+        let from = self.links.iter()
+            .position(|l| l.tag == tag)
+            .ok_or_else(|| QuickTabsError::NotFound(format!("Link tag '{}'", tag)))?;
+        let to = position.clamp(1, self.links.len()) - 1;
+        if from == to {
+            return Ok(ChangeSet::default());
+        }
+        let link = self.links.remove(from);
+        self.links.insert(to, link);
+        Ok(Change::Updated { kind: "position", tag: tag.to_string(), old: (from + 1).to_string(), new: (to + 1).to_string() }.into())
+    }
+
+    /// Tags matching any of `patterns` (globs such as `old-*`, or plain tags), in saved order.
+    pub fn matching_tags(&self, patterns: &[String]) -> Vec<String> {
+        self.links.iter()
+            .filter(|l| patterns.iter().any(|p| glob_match(p, &l.tag)))
+            .map(|l| l.tag.clone())
+            .collect()
+    }
+
+    /// The first saved link other than `tag` holding one of `urls`, compared with
+    /// `comparable_url`, as `(tag, saved url)`.
+    pub fn find_duplicate(&self, tag: &str, urls: &[String]) -> Option<(&str, &str)> {
+        let wanted: HashSet<String> = urls.iter().map(|url| comparable_url(url)).collect();
+        self.links.iter()
+            .filter(|l| l.tag != tag)
+            .find_map(|l| {
+                l.urls.iter()
+                    .find(|url| wanted.contains(&comparable_url(url)))
+                    .map(|url| (l.tag.as_str(), url.as_str()))
+            })
+    }
+
+    /// Removes links whose URLs are already saved under an earlier tag, compared with
+    /// `comparable_url`. Returns the tags that were dropped.
+    pub fn dedupe_urls(&mut self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let mut removed = vec![];
+        self.links.retain(|l| {
+            if seen.insert(l.urls.iter().map(|url| comparable_url(url)).collect::<Vec<_>>()) {
+                true
+            } else {
+                removed.push(l.tag.clone());
+                false
+            }
+        });
+        removed
+    }
+
+    pub fn open_all(&self, browser: &Browser, options: &LaunchOptions, batch: &BatchOptions) -> Result<(), QuickTabsError> {
+        let links = batch.pick(&self.links);
+        if links.is_empty() {
+            say!("⚠️ No links to open.");
+            return Ok(());
+        }
+        
+        // Collect URLs to launch simultaneously (better UX than sequential spawning);
+        // a link's URLs stay together in the order `pick` chose
+        let urls: Vec<&str> = links.iter().flat_map(|l| &l.urls).map(String::as_str).collect();
+        launch_batch(browser, &urls, options, batch)
+    }
+}
+
+// --- Launch Logic ---
+
+/// `--window-each` warns above this many windows.
+const WINDOW_EACH_WARN_AT: usize = 5;
+
+/// Browser families that share command-line flags.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BrowserFamily {
+    /// Chrome, Chromium, Brave, Vivaldi, Opera
+    Chromium,
+    /// Microsoft Edge: Chromium-based, but with its own private-mode flag
+    Edge,
+    /// Firefox, LibreWolf, Waterfox, Zen
+    Firefox,
+    Safari,
+    /// Tor Browser: Firefox-based, but every window is already private
+    Tor,
+    Unknown,
+}
+
+/// Determines the browser family from the executable name. Tor Browser ships a
+/// `firefox` executable, so it is recognized by its install directory instead.
+pub fn browser_family(browser_path: &Path) -> BrowserFamily {
+    let exe_lower = browser_path.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_lowercase();
+    let stem = exe_lower.trim_end_matches(".exe");
+    let is_tor = browser_path.components().any(|c| {
+        let part = c.as_os_str().to_string_lossy().to_lowercase();
+        part == "tor browser" || part == "tor browser.app" || part.contains("tor-browser") || part.contains("torbrowser")
+    });
+
+    if is_tor {
+        BrowserFamily::Tor
+    } else if exe_lower.contains("firefox") || exe_lower.contains("librewolf") || exe_lower.contains("waterfox")
+        || stem == "zen" || stem.starts_with("zen-browser") {
+        BrowserFamily::Firefox
+    // Flatpak launchers are named by app ID, e.g. com.microsoft.Edge
+    } else if exe_lower.contains("msedge") || exe_lower.contains("microsoft.edge") {
+        BrowserFamily::Edge
+    } else if exe_lower.contains("brave") || exe_lower.contains("chrome") || exe_lower.contains("chromium") || exe_lower.contains("vivaldi") || exe_lower.contains("opera") {
+        BrowserFamily::Chromium
+    } else if exe_lower.contains("safari") {
+        BrowserFamily::Safari
+    } else {
+        BrowserFamily::Unknown
+    }
+}
+
+/// Determines the correct private mode flags for a browser family.
+fn get_private_flags(family: BrowserFamily) -> &'static [&'static str] {
+    match family {
+        BrowserFamily::Firefox => &["-private-window"],
+        BrowserFamily::Edge => &["--inprivate"],
+        BrowserFamily::Chromium => &["--incognito"],
+        // No flag exists; on macOS `safari_private_command` scripts a private window instead
+        BrowserFamily::Safari => &[],
+        // Every Tor Browser window is private; it has no flag for it
+        BrowserFamily::Tor => &[],
+        BrowserFamily::Unknown => &[], // Unknown browser or standard launch
+    }
+}
+
+/// Determines the flag that forces a new window for a browser family.
+fn get_new_window_flags(family: BrowserFamily) -> &'static [&'static str] {
+    match family {
+        BrowserFamily::Firefox => &["-new-window"],
+        BrowserFamily::Chromium | BrowserFamily::Edge => &["--new-window"],
+        BrowserFamily::Safari | BrowserFamily::Tor | BrowserFamily::Unknown => &[],
+    }
+}
+
+/// Flags that suppress first-run and "make default browser" prompts for a browser family.
+fn get_clean_launch_flags(family: BrowserFamily) -> &'static [&'static str] {
+    match family {
+        BrowserFamily::Chromium | BrowserFamily::Edge => &["--no-first-run", "--no-default-browser-check"],
+        // Firefox only exposes these as profile prefs (browser.shell.checkDefaultBrowser),
+        // not as command-line flags.
+        BrowserFamily::Firefox | BrowserFamily::Safari | BrowserFamily::Tor | BrowserFamily::Unknown => &[],
+    }
+}
+
+/// Arguments that run a browser family privately inside the throwaway profile `dir`.
+/// Unlike plain private mode, nothing is shared with other windows, not even the
+/// incognito session that all of a profile's incognito windows normally share.
+fn get_container_args(family: BrowserFamily, dir: &Path) -> Vec<String> {
+    match family {
+        BrowserFamily::Chromium => vec![
+            format!("--user-data-dir={}", dir.display()),
+            "--incognito".to_string(),
+            "--no-first-run".to_string(),
+            "--no-default-browser-check".to_string(),
+        ],
+        BrowserFamily::Edge => vec![
+            format!("--user-data-dir={}", dir.display()),
+            "--inprivate".to_string(),
+            "--no-first-run".to_string(),
+            "--no-default-browser-check".to_string(),
+        ],
+        // -no-remote keeps a running Firefox from taking over the launch
+        BrowserFamily::Firefox => vec![
+            "-profile".to_string(),
+            dir.display().to_string(),
+            "-no-remote".to_string(),
+            "-private".to_string(),
+        ],
+        BrowserFamily::Safari | BrowserFamily::Tor | BrowserFamily::Unknown => vec![],
+    }
+}
+
+/// Arguments that select a named profile for a browser family.
+fn get_profile_args(family: BrowserFamily, profile: &str) -> Vec<String> {
+    match family {
+        BrowserFamily::Chromium | BrowserFamily::Edge => vec![format!("--profile-directory={}", profile)],
+        BrowserFamily::Firefox => vec!["-P".to_string(), profile.to_string()],
+        BrowserFamily::Safari | BrowserFamily::Tor | BrowserFamily::Unknown => vec![],
+    }
+}
+
+/// The command that starts `browser`, before any flags: the executable itself, or
+/// `snap run <name>` / `flatpak run <app-id>` for packaged installs.
+fn browser_program(browser: &Browser) -> Command {
+    let name = browser.path.file_name().unwrap_or_default();
+    let (manager, package) = match browser.kind {
+        InstallKind::Native => return Command::new(&browser.path),
+        InstallKind::Snap => ("snap", name),
+        InstallKind::Flatpak => ("flatpak", name),
+    };
+    let mut command = Command::new(manager);
+    command.arg("run").arg(package);
+    command
+}
+
+/// Builds the browser command for the given options, including any private-mode flags.
+/// `container` is the throwaway profile directory used in `LaunchMode::Container`.
+fn build_command(browser: &Browser, options: &LaunchOptions, container: Option<&Path>) -> Command {
+    let family = browser_family(&browser.path);
+    let mut command = browser_program(browser);
+
+    if options.clean {
+        let flags = get_clean_launch_flags(family);
+        if flags.is_empty() {
+            say!("⚠️ Warning: Clean-launch flags unknown for this browser. Launching as usual.");
+        } else {
+            command.args(flags);
+        }
+    }
+
+    // Firefox's private flags open a window of their own; `-new-window` next to them
+    // would take the URL and open it in a normal window instead
+    let private_window = family == BrowserFamily::Firefox && options.mode != LaunchMode::Normal;
+    if options.new_window && !private_window {
+        let flags = get_new_window_flags(family);
+        if flags.is_empty() {
+            say!("⚠️ Warning: New-window flag unknown for this browser. Opening as usual.");
+        } else {
+            command.args(flags);
+        }
+    }
+
+    // A throwaway profile replaces any named one (Firefox rejects `-P` with `-profile`)
+    if let Some(profile) = options.profile.as_ref().filter(|_| container.is_none()) {
+        let args = get_profile_args(family, profile);
+        if args.is_empty() {
+            say!("⚠️ Warning: Profile selection unknown for this browser. Using its default profile.");
+        } else {
+            command.args(args);
+        }
+    }
+
+    if let LaunchMode::Private = options.mode {
+        match &browser.private_args {
+            Some(flags) => {
+                command.args(flags);
+            }
+            None if family == BrowserFamily::Tor => {
+                say!("ℹ️ Tor Browser windows are always private; no flag needed.");
+            }
+            None => {
+                let flags = get_private_flags(family);
+                if flags.is_empty() {
+                    say!("⚠️ Warning: Private mode flags unknown for this browser. Launching normally.");
+                } else {
+                    command.args(flags);
+                }
+            }
+        }
+    }
+
+    if let Some(container) = container {
+        command.args(get_container_args(family, container));
+    }
+
+    command.args(&browser.extra_args);
+
+    command
+}
+
+/// Whether private mode can be requested from `browser`: through its saved
+/// `private_args`, a known flag, Tor Browser (always private) or, on macOS, Safari's
+/// scripted private window.
+pub fn supports_private_mode(browser: &Browser) -> bool {
+    let family = browser_family(&browser.path);
+    browser.private_args.is_some()
+        || !get_private_flags(family).is_empty()
+        || family == BrowserFamily::Tor
+        || (cfg!(target_os = "macos") && family == BrowserFamily::Safari)
+}
+
+/// How launch messages start: a dry run only says what it would do.
+fn launch_verb(options: &LaunchOptions) -> &'static str {
+    if options.dry_run { "🔍 Would launch" } else { "🚀 Launching" }
+}
+
+fn mode_label(mode: LaunchMode) -> &'static str {
+    match mode {
+        LaunchMode::Normal => "Normal Mode",
+        LaunchMode::Private => "Private Mode",
+        LaunchMode::Container => "Private Container",
+    }
+}
+
+/// The command that opens `urls`: the browser itself, or on macOS an `osascript`
+/// run for a private Safari window.
+fn launch_command(browser: &Browser, urls: &[&str], options: &LaunchOptions, container: Option<&Path>) -> Command {
+    if let Some(command) = safari_private_command(browser, urls, options) {
+        return command;
+    }
+    let mut command = build_command(browser, options, container);
+    match options.firefox_container.as_deref().filter(|_| browser_family(&browser.path) == BrowserFamily::Firefox) {
+        Some(name) => command.args(urls.iter().map(|url| container_url(name, url))),
+        None => command.args(urls),
+    };
+    command
+}
+
+/// Wraps `url` in the `ext+container:` scheme registered by the Multi-Account
+/// Containers extension (and compatible ones), which opens it in the container `name`.
+/// Firefox creates the container if it does not exist yet.
+fn container_url(name: &str, url: &str) -> String {
+    format!(
+        "ext+container:name={}&url={}",
+        utf8_percent_encode(name, NON_ALPHANUMERIC),
+        utf8_percent_encode(url, NON_ALPHANUMERIC)
+    )
+}
+
+/// Safari has no private-window flag, so its private windows are opened by sending
+/// the New Private Window shortcut (⇧⌘N) through System Events and loading the URLs
+/// into that window. Saved `private_args` still take precedence.
+#[cfg(target_os = "macos")]
+fn safari_private_command(browser: &Browser, urls: &[&str], options: &LaunchOptions) -> Option<Command> {
+    let applies = options.mode == LaunchMode::Private
+        && browser_family(&browser.path) == BrowserFamily::Safari
+        && browser.private_args.is_none();
+    if !applies {
+        return None;
+    }
+    let mut command = Command::new("osascript");
+    command.arg("-e").arg(safari_private_script(urls));
+    Some(command)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn safari_private_command(_browser: &Browser, _urls: &[&str], _options: &LaunchOptions) -> Option<Command> {
+    None
+}
+
+/// AppleScript that opens a private Safari window with `urls` as its tabs.
+#[cfg(any(target_os = "macos", test))]
+fn safari_private_script(urls: &[&str]) -> String {
+    let quote = |url: &str| format!("\"{}\"", url.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut script = String::from(concat!(
+        "tell application \"Safari\" to activate\n",
+        "tell application \"System Events\" to keystroke \"n\" using {command down, shift down}\n",
+        "delay 0.5\n",
+        "tell application \"Safari\"\n",
+    ));
+    for (i, url) in urls.iter().enumerate() {
+        if i == 0 {
+            script.push_str(&format!("    set URL of current tab of front window to {}\n", quote(url)));
+        } else {
+            script.push_str(&format!("    tell front window to set current tab to (make new tab with properties {{URL:{}}})\n", quote(url)));
+        }
+    }
+    script.push_str("end tell");
+    script
+}
+
+/// A running browser, plus the throwaway profile it uses in container mode. A dry
+/// run has neither.
+struct Launched {
+    child: Option<Child>,
+    container: Option<TempProfile>,
+    /// Wait for the browser to exit (`--wait`)
+    wait: bool,
+}
+
+impl Launched {
+    /// Returns at once, or waits for the browser to exit: with `--wait`, failing with
+    /// its exit code, and in container mode so its temporary profile can be removed.
+    fn finish(mut self) -> Result<(), QuickTabsError> {
+        let Some(child) = self.child.as_mut() else {
+            return Ok(());
+        };
+        if self.wait {
+            say!("⏳ Waiting for the browser to exit...");
+            let status = child.wait()?;
+            if !status.success() {
+                return Err(QuickTabsError::BrowserExited(status.code().unwrap_or(1)));
+            }
+        } else if let Some(container) = &self.container {
+            say!("🔒 Waiting for the browser to close before removing {}", container.path().display());
+            let _ = child.wait();
+        }
+        Ok(())
+    }
+}
+
+/// Builds and spawns the browser for `urls` and fires the post-launch hook on success.
+/// A dry run prints the command and the URL count instead.
+fn spawn(browser: &Browser, urls: &[&str], options: &LaunchOptions) -> Result<Launched, QuickTabsError> {
+    if options.strict_private && options.mode == LaunchMode::Private && !supports_private_mode(browser) {
+        return Err(QuickTabsError::Launch(format!(
+            "{} has no known private mode; refusing to open a normal window (--strict-private). Save a flag with `set-browser-flags default --private <flag>`",
+            browser.path.display()
+        )));
+    }
+    if options.dry_run {
+        let container = (options.mode == LaunchMode::Container).then(|| Path::new("<temporary profile>"));
+        let command = launch_command(browser, urls, options, container);
+        say!("🔍 Dry run, {} URL(s): {}", urls.len(), command_line(&command));
+        return Ok(Launched { child: None, container: None, wait: false });
+    }
+
+    let container = match options.mode {
+        LaunchMode::Container => {
+            if matches!(browser_family(&browser.path), BrowserFamily::Safari | BrowserFamily::Tor | BrowserFamily::Unknown) {
+                return Err(QuickTabsError::Launch(format!(
+                    "--incognito-container supports Chromium-based browsers and Firefox only; not launching {}",
+                    browser.path.display()
+                )));
+            }
+            let profile = TempProfile::create()
+                .map_err(|e| QuickTabsError::Launch(format!("could not create a temporary profile: {}", e)))?;
+            Some(profile)
+        }
+        LaunchMode::Normal | LaunchMode::Private => None,
+    };
+
+    let mut command = launch_command(browser, urls, options, container.as_ref().map(TempProfile::path));
+    verbose!("Running {}", command_line(&command));
+
+    match command.spawn() {
+        Ok(child) => {
+            if let Some(hook) = &options.post_launch_hook {
+                run_post_launch_hook(hook, browser, urls, options.mode);
+            }
+            Ok(Launched { child: Some(child), container, wait: options.wait })
+        }
+        Err(e) => {
+            let error = QuickTabsError::Launch(format!(
+                "could not start {}: {} (command: {})",
+                browser.path.display(), e, command_line(&command)
+            ));
+            match &options.fallback {
+                Some(detect) => spawn_fallback(browser, urls, options, detect, error),
+                None => Err(error),
+            }
+        }
+    }
+}
+
+/// Retries a launch that `failed` to start with the other detected browsers, in
+/// preference order. The first that starts is used, and saved as the default when the
+/// default was `failed` and its executable is gone. Fails with `error` when none starts.
+fn spawn_fallback(failed: &Browser, urls: &[&str], options: &LaunchOptions, detect: &DetectOptions, error: QuickTabsError) -> Result<Launched, QuickTabsError> {
+    esay!("⚠️ {}", error);
+    let options = LaunchOptions { fallback: None, ..options.clone() };
+    for browser in fallback_browsers(failed, detect) {
+        say!("🔁 Falling back to {} ({})", browser.name, browser.path.display());
+        match spawn(&browser, urls, &options) {
+            Ok(launched) => {
+                if let Err(e) = replace_missing_default(failed, &browser) {
+                    esay!("⚠️ Could not update the saved browser: {}", e);
+                }
+                return Ok(launched);
+            }
+            Err(e) => esay!("⚠️ {}", e),
+        }
+    }
+    Err(error)
+}
+
+/// Expands `~`, `$VARS` and `{date}` tokens (see `url::expand_env` and
+/// `url::render_template`) and drops URLs refused by the blocklist, printing each
+/// problem. Callers pass the resolved URLs (after alias expansion), never the raw
+/// tags, so the blocklist sees what would really open.
+fn prepare_urls(urls: &[&str], options: &LaunchOptions) -> Vec<String> {
+    let now = Local::now();
+    urls.iter()
+        .filter_map(|url| match expand_env(url).and_then(|url| render_template(&url, &now)) {
+            Ok(rendered) => Some(rendered),
+            Err(e) => {
+                esay!("❌ Skipping {}: {}", url, e);
+                None
+            }
+        })
+        .filter(|url| match options.blocklist.as_ref().and_then(|b| b.find_match(url)) {
+            Some(_) => {
+                say!("⛔ Blocked by policy: {}", url);
+                false
+            }
+            None => true,
+        })
+        .collect()
+}
+
+/// Whether `prepare_urls` keeps `url`: it expands and renders, and the blocklist lets
+/// it through. Silent, so a finished launch can tell which URLs it really opened.
+pub fn is_launchable(url: &str, options: &LaunchOptions) -> bool {
+    expand_env(url)
+        .and_then(|url| render_template(&url, &Local::now()))
+        .is_ok_and(|url| options.blocklist.as_ref().and_then(|b| b.find_match(&url)).is_none())
+}
+
+/// Launch a single URL in the selected browser. Fails when the browser could not be
+/// started; a URL skipped by `prepare_urls` is not an error.
+pub fn launch_link(browser: &Browser, url: &str, options: &LaunchOptions) -> Result<(), QuickTabsError> {
+    let Some(url) = prepare_urls(&[url], options).pop() else {
+        return Ok(());
+    };
+    let url = url.as_str();
+    say!("{} {} in {} ({})", launch_verb(options), url, browser.path.display(), mode_label(options.mode));
+
+    spawn(browser, &[url], options)?.finish()
+}
+
+/// Launch a single URL, keep it open for `duration`, then kill the spawned browser process.
+///
+/// This is best-effort: single-instance browsers (Chrome, Firefox, Edge, ...) usually hand
+/// the URL over to an already-running instance and exit immediately, so the tab or window
+/// stays open. It only closes reliably when no other instance of the browser is running.
+pub fn launch_link_for(browser: &Browser, url: &str, options: &LaunchOptions, duration: Duration) -> Result<(), QuickTabsError> {
+    let Some(url) = prepare_urls(&[url], options).pop() else {
+        return Ok(());
+    };
+    let url = url.as_str();
+    say!("{} {} in {} ({}) for {:?}", launch_verb(options), url, browser.path.display(), mode_label(options.mode), duration);
+
+    let Launched { child, container, .. } = spawn(browser, &[url], options)?;
+    let Some(mut child) = child else {
+        return Ok(());
+    };
+
+    thread::sleep(duration);
+
+    match child.try_wait() {
+        Ok(Some(_)) => {
+            say!("⚠️ Browser process already exited; it probably handed the URL to a running instance, which was left open.");
+        }
+        _ => {
+            if let Err(e) = child.kill() {
+                esay!("⚠️ Failed to close browser process: {}", e);
+            } else {
+                let _ = child.wait();
+                say!("🛑 Closed browser after {:?}", duration);
+            }
+        }
+    }
+    drop(container);
+    Ok(())
+}
+
+/// Launch multiple URLs together in a new browser window.
+pub fn launch_urls_in_new_window(browser: &Browser, urls: &[&str], options: &LaunchOptions) -> Result<(), QuickTabsError> {
+    let prepared = prepare_urls(urls, options);
+    if prepared.is_empty() {
+        return Ok(());
+    }
+    let urls: &[&str] = &prepared.iter().map(String::as_str).collect::<Vec<_>>();
+    say!("{} {} link(s) in a new window of {} ({})", launch_verb(options), urls.len(), browser.path.display(), mode_label(options.mode));
+
+    let options = &LaunchOptions { new_window: true, ..options.clone() };
+    spawn(browser, urls, options)?.finish()
+}
+
+/// Launches already-arranged URLs as one batch, or one at a time with `--window-each`
+/// or `--delay`.
+pub fn launch_batch(browser: &Browser, urls: &[&str], options: &LaunchOptions, batch: &BatchOptions) -> Result<(), QuickTabsError> {
+    if batch.window_each {
+        launch_urls_each_in_new_window(browser, urls, options, batch.delay)
+    } else if batch.delay.is_some() {
+        launch_urls_one_by_one(browser, urls, options, batch.delay)
+    } else {
+        launch_urls_simultaneously(browser, urls, options)
+    }
+}
+
+/// Opens every URL in its own new window, one launch per URL, pausing `delay`
+/// between windows. Stops at the first window that fails to open.
+pub fn launch_urls_each_in_new_window(browser: &Browser, urls: &[&str], options: &LaunchOptions, delay: Option<Duration>) -> Result<(), QuickTabsError> {
+    if urls.len() > WINDOW_EACH_WARN_AT {
+        say!("⚠️ This opens {} separate windows. Use groups with `open-group --window-per-group` to open fewer.", urls.len());
+    }
+
+    let options = LaunchOptions { new_window: true, ..options.clone() };
+    launch_urls_one_by_one(browser, urls, &options, delay)
+}
+
+/// Spawns the browser once per URL, pausing `delay` between launches, for browsers
+/// that drop tabs when handed many URLs at once. Stops at the first failed launch.
+pub fn launch_urls_one_by_one(browser: &Browser, urls: &[&str], options: &LaunchOptions, delay: Option<Duration>) -> Result<(), QuickTabsError> {
+    // In container mode each launch is its own browser instance; start them all before waiting
+    let mut launched = vec![];
+    let mut failed = None;
+    for (i, url) in prepare_urls(urls, options).into_iter().enumerate() {
+        if let Some(delay) = delay.filter(|_| i > 0) {
+            thread::sleep(delay);
+        }
+        say!("{} {} in {} ({})", launch_verb(options), url, browser.path.display(), mode_label(options.mode));
+        match spawn(browser, &[url.as_str()], options) {
+            Ok(l) => launched.push(l),
+            Err(e) => {
+                failed = Some(e);
+                break;
+            }
+        }
+    }
+    // Windows that did open still get their profiles cleaned up
+    for l in launched {
+        if let Err(e) = l.finish() {
+            failed.get_or_insert(e);
+        }
+    }
+    failed.map_or(Ok(()), Err)
+}
+
+/// Launch multiple URLs in the selected browser instance. With `options.new_window`,
+/// each URL gets a window of its own rather than all sharing one.
+pub fn launch_urls_simultaneously(browser: &Browser, urls: &[&str], options: &LaunchOptions) -> Result<(), QuickTabsError> {
+    if options.new_window && urls.len() > 1 {
+        return launch_urls_each_in_new_window(browser, urls, options, None);
+    }
+    let prepared = prepare_urls(urls, options);
+    if prepared.is_empty() {
+        return Ok(());
+    }
+    let urls: &[&str] = &prepared.iter().map(String::as_str).collect::<Vec<_>>();
+    say!("{} {} link(s) in {} ({})", launch_verb(options), urls.len(), browser.path.display(), mode_label(options.mode));
+
+    spawn(browser, urls, options)?.finish()
+}
+
+/// The platform's "open with the default application" command for one URL:
+/// `xdg-open` on Linux and other Unix systems, `open` on macOS, `cmd /C start` on Windows.
+fn system_open_command(url: &str) -> Command {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        // `start` takes the first quoted argument as a window title, hence the empty one;
+        // the URL is escaped for cmd itself so `&` and `%` reach the handler intact
+        let mut command = Command::new("cmd");
+        command.raw_arg(format!("/C start \"\" {}", crate::commands::quote::shell_quote(url)));
+        command
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let mut command = Command::new("open");
+        command.arg(url);
+        command
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        let mut command = Command::new("xdg-open");
+        command.arg(url);
+        command
+    }
+}
+
+/// Opens URLs with the OS default handler (`--system`) instead of a detected browser.
+/// Browser-specific options (private mode, profiles, clean launch, new window) cannot
+/// be passed on; the blocklist, `{date}` tokens and the post-launch hook still apply.
+pub fn launch_with_system_opener(urls: &[&str], options: &LaunchOptions) -> Result<(), QuickTabsError> {
+    let prepared = prepare_urls(urls, options);
+    if prepared.is_empty() {
+        return Ok(());
+    }
+    say!("{} {} link(s) with the system default handler", launch_verb(options), prepared.len());
+
+    for url in &prepared {
+        let mut command = system_open_command(url);
+        let line = command_line(&command);
+        if options.dry_run {
+            say!("🔍 Dry run: {}", line);
+            continue;
+        }
+        verbose!("Running {}", line);
+        // The opener hands the URL over and exits; its status tells whether a handler was found
+        let status = command.status().map_err(|e| {
+            QuickTabsError::Launch(format!("could not start the system opener: {} (command: {})", e, line))
+        })?;
+        if !status.success() {
+            return Err(QuickTabsError::Launch(format!("the system opener failed for {} ({}; command: {})", url, status, line)));
+        }
+    }
+
+    if let Some(hook) = options.post_launch_hook.as_ref().filter(|_| !options.dry_run) {
+        let opener = Browser {
+            name: "System default".to_string(),
+            path: system_open_command("").get_program().into(),
+            version: None,
+            ..Default::default()
+        };
+        let urls: Vec<&str> = prepared.iter().map(String::as_str).collect();
+        run_post_launch_hook(hook, &opener, &urls, LaunchMode::Normal);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use indexmap::IndexMap;
+    use std::path::PathBuf;
+
+    fn edge() -> Browser {
+        Browser { name: "Microsoft Edge".to_string(), path: PathBuf::from("/usr/bin/msedge"), version: None, ..Default::default() }
+    }
+
+    #[test]
+    fn import_lines_parse_as_tag_and_urls() {
+        let link = Link::parse_line("  gh\thttps://github.com  https://gist.github.com ").unwrap().unwrap();
+        assert_eq!((link.tag.as_str(), link.urls.as_slice()), ("gh", ["https://github.com".to_string(), "https://gist.github.com".to_string()].as_slice()));
+        assert!(Link::parse_line("   ").is_none());
+        assert!(Link::parse_line("# exported 2024-05-01").is_none());
+        assert!(Link::parse_line("lonely").unwrap().is_err());
+    }
+
+    #[test]
+    fn explicit_flag_beats_browser_default() {
+        let defaults = HashMap::from([("msedge".to_string(), LaunchMode::Private)]);
+        assert_eq!(effective_mode(Some(LaunchMode::Normal), &edge(), &defaults), LaunchMode::Normal);
+        assert_eq!(effective_mode(Some(LaunchMode::Private), &edge(), &HashMap::new()), LaunchMode::Private);
+    }
+
+    #[test]
+    fn browser_default_applies_without_flag() {
+        let defaults = HashMap::from([("microsoft edge".to_string(), LaunchMode::Private)]);
+        assert_eq!(effective_mode(None, &edge(), &defaults), LaunchMode::Private);
+
+        let other = HashMap::from([("firefox".to_string(), LaunchMode::Private)]);
+        assert_eq!(effective_mode(None, &edge(), &other), LaunchMode::Normal);
+    }
+
+    #[test]
+    fn saved_flags_replace_private_flags_and_are_appended() {
+        let browser = Browser {
+            extra_args: vec!["--profile-directory=Profile 2".to_string()],
+            private_args: Some(vec!["--guest".to_string()]),
+            ..edge()
+        };
+        let options = LaunchOptions { mode: LaunchMode::Private, ..Default::default() };
+        let command = build_command(&browser, &options, None);
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, ["--guest", "--profile-directory=Profile 2"]);
+
+        let command = build_command(&edge(), &options, None);
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["--inprivate"]);
+    }
+
+    #[test]
+    fn packaged_browsers_run_through_their_package_manager() {
+        let flatpak = Browser {
+            name: "Firefox".to_string(),
+            path: PathBuf::from("/var/lib/flatpak/exports/bin/org.mozilla.firefox"),
+            kind: InstallKind::Flatpak,
+            ..Default::default()
+        };
+        let options = LaunchOptions { mode: LaunchMode::Private, ..Default::default() };
+        let command = launch_command(&flatpak, &["https://a.com"], &options, None);
+        assert_eq!(command.get_program(), "flatpak");
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["run", "org.mozilla.firefox", "-private-window", "https://a.com"]);
+
+        let edge = Browser { path: PathBuf::from("/var/lib/flatpak/exports/bin/com.microsoft.Edge"), ..flatpak };
+        let command = launch_command(&edge, &["https://a.com"], &options, None);
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["run", "com.microsoft.Edge", "--inprivate", "https://a.com"]);
+
+        let snap = Browser { path: PathBuf::from("/snap/bin/chromium"), kind: InstallKind::Snap, ..edge };
+        let command = build_command(&snap, &LaunchOptions::default(), None);
+        assert_eq!(command.get_program(), "snap");
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["run", "chromium"]);
+    }
+
+    #[test]
+    fn new_windows_stay_private() {
+        let options = LaunchOptions { mode: LaunchMode::Private, new_window: true, ..Default::default() };
+        let args = |path: &str| {
+            let browser = Browser { name: "b".to_string(), path: PathBuf::from(path), ..Default::default() };
+            build_command(&browser, &options, None).get_args().map(|a| a.to_string_lossy().into_owned()).collect::<Vec<_>>()
+        };
+        assert_eq!(args("/usr/bin/google-chrome"), ["--new-window", "--incognito"]);
+        assert_eq!(args("/usr/bin/firefox"), ["-private-window"]);
+
+        let normal = LaunchOptions { new_window: true, ..Default::default() };
+        let firefox = Browser { name: "b".to_string(), path: PathBuf::from("/usr/bin/firefox"), ..Default::default() };
+        assert_eq!(build_command(&firefox, &normal, None).get_args().collect::<Vec<_>>(), ["-new-window"]);
+    }
+
+    #[test]
+    fn profiles_use_each_family_flag() {
+        let options = LaunchOptions { profile: Some("Profile 1".to_string()), ..Default::default() };
+        let args = |path: &str| {
+            let browser = Browser { name: "b".to_string(), path: PathBuf::from(path), ..Default::default() };
+            build_command(&browser, &options, None).get_args().map(|a| a.to_string_lossy().into_owned()).collect::<Vec<_>>()
+        };
+        assert_eq!(args("/usr/bin/google-chrome"), ["--profile-directory=Profile 1"]);
+        assert_eq!(args("/usr/bin/msedge"), ["--profile-directory=Profile 1"]);
+        assert_eq!(args("/usr/bin/firefox"), ["-P", "Profile 1"]);
+        // Unsupported families warn and launch without a profile
+        assert!(args("/usr/bin/lynx").is_empty());
+    }
+
+    #[test]
+    fn safari_private_script_opens_every_url_quoted() {
+        let script = safari_private_script(&["https://a.com", "https://b.com/?q=\"x\""]);
+        assert!(script.contains("keystroke \"n\" using {command down, shift down}"));
+        assert!(script.contains("set URL of current tab of front window to \"https://a.com\""));
+        assert!(script.contains("make new tab with properties {URL:\"https://b.com/?q=\\\"x\\\"\"}"));
+        assert!(script.ends_with("end tell"));
+    }
+
+    #[test]
+    fn niche_browsers_map_to_their_family() {
+        let family = |path: &str| browser_family(Path::new(path));
+        assert_eq!(family("/usr/bin/librewolf"), BrowserFamily::Firefox);
+        assert_eq!(family("/Applications/LibreWolf.app/Contents/MacOS/librewolf"), BrowserFamily::Firefox);
+        assert_eq!(family("/opt/waterfox/waterfox"), BrowserFamily::Firefox);
+        assert_eq!(family("/Applications/Zen.app/Contents/MacOS/zen"), BrowserFamily::Firefox);
+        assert_eq!(family("/usr/bin/vivaldi"), BrowserFamily::Chromium);
+        assert_eq!(family("/Applications/Tor Browser.app/Contents/MacOS/firefox"), BrowserFamily::Tor);
+        assert_eq!(family("/home/me/tor-browser/Browser/start-tor-browser"), BrowserFamily::Tor);
+        assert_eq!(family("/usr/bin/zenity"), BrowserFamily::Unknown);
+
+        assert_eq!(get_private_flags(BrowserFamily::Firefox), ["-private-window"]);
+        assert!(get_private_flags(BrowserFamily::Tor).is_empty());
+    }
+
+    #[test]
+    fn rename_link_updates_alias_references() {
+        let mut links = LinkConfig { links: vec![Link::new("docs", "https://docs.rs")] };
+        let mut aliases = AliasConfig {
+            aliases: IndexMap::from([
+                ("d".to_string(), "docs".to_string()),
+                ("web".to_string(), "https://docs.example.com".to_string()),
+            ]),
+        };
+
+        let changes = links.rename_link("docs", "rust-docs", &mut aliases).unwrap();
+        assert_eq!(changes.summary(), "1 updated, 1 renamed");
+        assert_eq!(links.get_urls("rust-docs"), Some(vec!["https://docs.rs".to_string()]));
+        assert_eq!(aliases.resolve("d").as_deref(), Some("rust-docs"));
+        assert_eq!(aliases.resolve("web").as_deref(), Some("https://docs.example.com"));
+
+        assert!(matches!(links.rename_link("docs", "x", &mut aliases), Err(QuickTabsError::NotFound(_))));
+    }
+
+    #[test]
+    fn rename_link_refuses_to_overwrite() {
+        let mut links = LinkConfig { links: vec![Link::new("a", "https://a.com"), Link::new("b", "https://b.com")] };
+        let mut aliases = AliasConfig { aliases: IndexMap::new() };
+        assert!(matches!(links.rename_link("a", "b", &mut aliases), Err(QuickTabsError::Other(_))));
+        assert_eq!(links.get_urls("a"), Some(vec!["https://a.com".to_string()]));
+        assert_eq!(links.get_urls("b"), Some(vec!["https://b.com".to_string()]));
+    }
+
+    #[test]
+    fn add_link_reports_replacements() {
+        let mut links = LinkConfig { links: vec![] };
+        assert_eq!(
+            links.add_link(Link::new("a", "https://a.com"), false).unwrap(),
+            Change::Added { kind: "link", tag: "a".to_string(), value: "https://a.com".to_string() }.into()
+        );
+        assert!(links.add_link(Link::new("a", "https://b.com"), true).unwrap().has_updates());
+        assert_eq!(links.get_urls("a"), Some(vec!["https://b.com".to_string()]));
+        assert!(links.remove_link("missing").is_empty());
+    }
+
+    #[test]
+    fn cleared_configs_save_as_valid_empty_json() {
+        let dir = std::env::temp_dir().join(format!("quick_tabs_clear_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (link_path, alias_path) = (dir.join("links.json"), dir.join("aliases.json"));
+
+        let mut links = LinkConfig { links: vec![Link::new("a", "https://a.com"), Link::new("b", "https://b.com")] };
+        assert_eq!(links.clear().changes.len(), 2);
+        links.save(&link_path).unwrap();
+        let mut aliases = AliasConfig { aliases: [("x".to_string(), "a".to_string())].into_iter().collect() };
+        assert_eq!(aliases.clear().changes.len(), 1);
+        aliases.save(&alias_path).unwrap();
+
+        let link_json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&link_path).unwrap()).unwrap();
+        assert_eq!(link_json, serde_json::json!({ "version": schema::SCHEMA_VERSION, "links": [] }));
+        let alias_json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&alias_path).unwrap()).unwrap();
+        assert_eq!(alias_json, serde_json::json!({ "version": schema::SCHEMA_VERSION, "aliases": {} }));
+        assert!(LinkConfig::load(&link_path).unwrap().links.is_empty());
+        assert!(AliasConfig::load(&alias_path).unwrap().aliases.is_empty());
+        assert!(links.clear().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn filters_match_tag_or_url_ignoring_case() {
+        assert!(matches_filter("Docs", "https://a.example", "doc"));
+        assert!(matches_filter("a", "https://GitHub.com/x", "github"));
+        assert!(!matches_filter("a", "https://a.example", "rust"));
+    }
+
+    #[test]
+    fn single_url_links_keep_the_old_format() {
+        let old = r#"{"links":[{"tag":"a","url":"https://a.com","modified":1700000000}]}"#;
+        let links: LinkConfig = serde_json::from_str(old).unwrap();
+        assert_eq!(links.get_urls("a"), Some(vec!["https://a.com".to_string()]));
+        assert_eq!(serde_json::to_string(&links).unwrap(), old);
+    }
+
+    #[test]
+    fn links_survive_a_save_and_reload() {
+        let path = std::env::temp_dir().join(format!("quick_tabs_round_trip_{}.json", std::process::id()));
+        let mut links = LinkConfig { links: vec![] };
+        for link in [
+            Link::new("gh", "https://github.com"),
+            Link { description: Some("API docs".to_string()), browser: Some("firefox".to_string()), ..Link::new("docs", "https://docs.rs") },
+            Link { urls: vec!["https://mail.com".to_string(), "https://cal.com".to_string()], ..Link::new("morning", "") },
+        ] {
+            links.add_link(link, false).unwrap();
+        }
+        links.save(&path).unwrap();
+
+        let mut loaded = LinkConfig::load(&path).unwrap();
+        assert_eq!(loaded.links, links.links);
+        assert!(!loaded.remove_link("gh").is_empty());
+        assert!(loaded.remove_link("gh").is_empty());
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(crate::commands::config::backup_path(&path, 1));
+    }
+
+    #[test]
+    fn links_group_by_category_with_uncategorized_last() {
+        let in_category = |tag: &str, category: &str| Link { category: Some(category.to_string()), ..Link::new(tag, "https://a.example") };
+        let mut links = LinkConfig {
+            links: vec![
+                Link::new("misc", "https://m.example"),
+                in_category("jira", "work"),
+                in_category("arxiv", "research"),
+                in_category("ci", "work"),
+            ],
+        };
+        let grouped: Vec<(Option<&str>, Vec<&str>)> = by_category(links.links.iter().collect()).into_iter()
+            .map(|(category, links)| (category, links.iter().map(|l| l.tag.as_str()).collect()))
+            .collect();
+        assert_eq!(grouped, [
+            (Some("research"), vec!["arxiv"]),
+            (Some("work"), vec!["jira", "ci"]),
+            (None, vec!["misc"]),
+        ]);
+
+        assert!(matches!(links.keep_category("personal"), Err(QuickTabsError::NotFound(_))));
+        links = LinkConfig { links: vec![in_category("jira", "work"), Link::new("misc", "https://m.example")] };
+        links.keep_category("Work").unwrap();
+        assert_eq!(links.links.len(), 1);
+    }
+
+    #[test]
+    fn corrupt_links_are_set_aside_not_overwritten() {
+        let dir = std::env::temp_dir().join(format!("quick_tabs_corrupt_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("links.json");
+        let damaged = r#"{"links":[{"tag":"gh","url":"https://github.com"},"#;
+        fs::write(&path, damaged).unwrap();
+
+        let mut links = LinkConfig::load(&path).unwrap();
+        assert!(links.links.is_empty());
+        let corrupt = dir.join("links.json.corrupt");
+        assert_eq!(fs::read_to_string(&corrupt).unwrap(), damaged);
+        assert!(!path.exists());
+
+        // Saving the empty config cannot touch the damaged data
+        links.add_link(Link::new("docs", "https://docs.rs"), false).unwrap();
+        links.save(&path).unwrap();
+        assert_eq!(fs::read_to_string(&corrupt).unwrap(), damaged);
+
+        // A second corrupt file does not replace the first
+        fs::write(&path, "not json").unwrap();
+        LinkConfig::load(&path).unwrap();
+        assert_eq!(fs::read_to_string(&corrupt).unwrap(), damaged);
+        assert_eq!(fs::read_to_string(dir.join("links.json.corrupt.2")).unwrap(), "not json");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn multi_url_links_round_trip_as_arrays() {
+        let links = LinkConfig {
+            links: vec![Link {
+                urls: vec!["https://mail.com".to_string(), "https://cal.com".to_string()],
+                ..Link::new("morning", "")
+            }],
+        };
+        let json: serde_json::Value = serde_json::to_value(&links).unwrap();
+        assert_eq!(json["links"][0]["url"], serde_json::json!(["https://mail.com", "https://cal.com"]));
+
+        let back: LinkConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(back.get_urls("morning"), links.get_urls("morning"));
+        let aliased: LinkConfig = serde_json::from_str(r#"{"links":[{"tag":"m","urls":["https://a.com"]}]}"#).unwrap();
+        assert_eq!(aliased.get_urls("m"), Some(vec!["https://a.com".to_string()]));
+    }
+
+    #[test]
+    fn add_link_refuses_existing_tags_without_force() {
+        let mut links = LinkConfig { links: vec![Link::new("a", "https://a.com")] };
+        let err = links.add_link(Link::new("a", "https://b.com"), false).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+        assert_eq!(links.links.len(), 1);
+        assert_eq!(links.get_urls("a"), Some(vec!["https://a.com".to_string()]));
+    }
+
+    #[test]
+    fn edit_link_keeps_position_and_requires_the_tag() {
+        let mut links = LinkConfig { links: vec![Link::new("a", "https://a.com"), Link::new("b", "https://b.com")] };
+        assert!(links.edit_link("a", vec!["https://a.org".to_string()]).unwrap().has_updates());
+        assert_eq!(links.links[0].tag, "a");
+        assert_eq!(links.links[0].urls, ["https://a.org"]);
+        assert!(links.links[0].modified.is_some());
+
+        assert!(links.edit_link("a", vec!["https://a.org".to_string()]).unwrap().is_empty());
+        assert!(matches!(links.edit_link("c", vec!["https://c.com".to_string()]), Err(QuickTabsError::NotFound(_))));
+        assert_eq!(links.links.len(), 2);
+    }
+
+    #[test]
+    fn links_move_within_the_saved_order() {
+        let mut config = LinkConfig { links: ["a", "b", "c"].iter().map(|t| Link::new(*t, format!("https://{}.com", t))).collect() };
+        let order = |c: &LinkConfig| c.links.iter().map(|l| l.tag.clone()).collect::<Vec<_>>().join(",");
+
+        assert_eq!(config.move_link("c", 1).unwrap().changes.len(), 1);
+        assert_eq!(order(&config), "c,a,b");
+        config.move_link("c", 99).unwrap();
+        assert_eq!(order(&config), "a,b,c");
+        assert!(config.move_link("c", 3).unwrap().is_empty());
+        assert!(matches!(config.move_link("x", 1), Err(QuickTabsError::NotFound(_))));
+    }
+
+    #[test]
+    fn count_caps_how_many_entries_open() {
+        let batch = BatchOptions { count: Some(2), ..Default::default() };
+        assert_eq!(batch.limit(5), 2);
+        assert_eq!(batch.limit(1), 1);
+        assert_eq!(BatchOptions::default().limit(5), 5);
+    }
+
+    #[test]
+    fn entries_are_reordered_before_the_count_applies() {
+        let entries = ["a", "b", "c", "d", "e"];
+        let reversed = BatchOptions { reverse: true, count: Some(3), ..Default::default() };
+        assert_eq!(reversed.pick(&entries), [&"e", &"d", &"c"]);
+
+        let shuffled = BatchOptions { shuffle_seed: Some(7), count: Some(3), ..Default::default() };
+        let picked = shuffled.pick(&entries);
+        assert_eq!(picked.len(), 3);
+        assert_eq!(picked, shuffled.pick(&entries));
+        let mut all: Vec<&&str> = BatchOptions { shuffle_seed: Some(7), ..Default::default() }.pick(&entries);
+        assert_eq!(&all[..3], &picked[..]);
+        all.sort();
+        assert_eq!(all, [&"a", &"b", &"c", &"d", &"e"]);
+    }
+
+    #[test]
+    fn duplicates_are_found_under_other_tags() {
+        let links = LinkConfig { links: vec![Link::new("gh", "https://github.com/"), Link::new("docs", "https://docs.rs")] };
+        let urls = |url: &str| vec![url.to_string()];
+        assert_eq!(links.find_duplicate("hub", &urls("https://GitHub.com")), Some(("gh", "https://github.com/")));
+        // Replacing a link with --force is not a duplicate of itself
+        assert_eq!(links.find_duplicate("gh", &urls("https://github.com")), None);
+        assert_eq!(links.find_duplicate("hub", &urls("https://github.com/rust-lang")), None);
+
+        let mut links = LinkConfig { links: vec![Link::new("zz", "https://GitHub.com/"), Link::new("aa", "https://github.com")] };
+        assert_eq!(links.dedupe_urls(), ["aa"]);
+    }
+
+    #[test]
+    fn descriptions_are_optional_and_editable() {
+        let mut links: LinkConfig = serde_json::from_str(r#"{"links":[{"tag":"a","url":"https://a.com"}]}"#).unwrap();
+        assert_eq!(links.links[0].description, None);
+
+        let changes = links.describe_link("a", Some("Team dashboard".to_string())).unwrap();
+        assert_eq!(changes.changes[0].to_string(), "+ description [a] Team dashboard");
+        assert!(serde_json::to_string(&links).unwrap().contains(r#""description":"Team dashboard""#));
+        assert!(links.describe_link("a", Some("Team dashboard".to_string())).unwrap().is_empty());
+        assert!(links.describe_link("a", None).unwrap().changes[0].to_string().starts_with("- description"));
+        assert!(matches!(links.describe_link("b", None), Err(QuickTabsError::NotFound(_))));
+    }
+
+    #[test]
+    fn dry_run_commit_writes_nothing() {
+        let path = std::env::temp_dir().join(format!("quick_tabs_dry_run_{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut links = LinkConfig { links: vec![] };
+        let changes = links.add_link(Link::new("a", "https://a.com"), false).unwrap();
+        assert!(!changes.commit(true, || links.save(&path)).unwrap());
+        assert!(!path.exists());
+
+        assert!(changes.commit(false, || links.save(&path)).unwrap());
+        assert!(path.exists());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn firefox_containers_wrap_the_urls() {
+        let options = LaunchOptions { firefox_container: Some("Work Stuff".to_string()), ..Default::default() };
+        let firefox = Browser { name: "Firefox".to_string(), path: "/usr/bin/firefox".into(), ..Default::default() };
+        let args = |browser: &Browser| -> Vec<String> {
+            launch_command(browser, &["https://a.com/?q=1"], &options, None).get_args().map(|a| a.to_string_lossy().into_owned()).collect()
+        };
+        assert_eq!(args(&firefox), ["ext+container:name=Work%20Stuff&url=https%3A%2F%2Fa%2Ecom%2F%3Fq%3D1"]);
+        assert_eq!(args(&edge()).last().unwrap(), "https://a.com/?q=1");
+    }
+
+    #[test]
+    fn strict_private_refuses_browsers_without_a_private_mode() {
+        let options = LaunchOptions { mode: LaunchMode::Private, strict_private: true, dry_run: true, ..Default::default() };
+        let unknown = Browser { name: "Custom Browser".to_string(), path: "/opt/netsurf/netsurf".into(), ..Default::default() };
+        assert!(!supports_private_mode(&unknown));
+        assert!(matches!(launch_link(&unknown, "https://a.com", &options), Err(QuickTabsError::Launch(_))));
+        assert!(launch_link(&unknown, "https://a.com", &LaunchOptions { mode: LaunchMode::Normal, ..options.clone() }).is_ok());
+
+        let flagged = Browser { private_args: Some(vec!["--private".to_string()]), ..unknown };
+        assert!(launch_link(&flagged, "https://a.com", &options).is_ok());
+        assert!(launch_link(&edge(), "https://a.com", &options).is_ok());
+    }
+
+    #[test]
+    fn container_args_use_throwaway_profile() {
+        let dir = Path::new("/tmp/qt-profile");
+        let chromium = get_container_args(BrowserFamily::Chromium, dir);
+        assert!(chromium.contains(&"--user-data-dir=/tmp/qt-profile".to_string()));
+        assert!(chromium.contains(&"--incognito".to_string()));
+
+        let firefox = get_container_args(BrowserFamily::Firefox, dir);
+        assert_eq!(&firefox[..2], ["-profile", "/tmp/qt-profile"]);
+        assert!(firefox.contains(&"-private".to_string()));
+        assert!(get_container_args(BrowserFamily::Safari, dir).is_empty());
+    }
+
+    #[test]
+    fn failed_spawns_are_errors() {
+        let missing = Browser { name: "Missing".to_string(), path: PathBuf::from("/nonexistent/quick_tabs/chrome"), version: None, ..Default::default() };
+        let options = LaunchOptions::default();
+
+        let err = launch_link(&missing, "https://a.com", &options).unwrap_err();
+        assert_eq!(err.code(), 8);
+        assert!(err.to_string().contains("/nonexistent/quick_tabs/chrome"), "{}", err);
+        assert!(launch_urls_simultaneously(&missing, &["https://a.com", "https://b.com"], &options).is_err());
+
+        // Nothing left to launch after filtering is not a failure
+        assert!(launch_urls_simultaneously(&missing, &[], &options).is_ok());
+    }
+
+    #[test]
+    fn dry_runs_never_spawn() {
+        let missing = Browser { name: "Missing".to_string(), path: PathBuf::from("/nonexistent/quick_tabs/chrome"), version: None, ..Default::default() };
+        let options = LaunchOptions { dry_run: true, mode: LaunchMode::Container, ..Default::default() };
+
+        assert!(launch_link(&missing, "https://a.com", &options).is_ok());
+        assert!(launch_link_for(&missing, "https://a.com", &options, Duration::from_secs(60)).is_ok());
+        assert!(launch_urls_simultaneously(&missing, &["https://a.com", "https://b.com"], &options).is_ok());
+    }
+}
@@ -0,0 +1,143 @@
+// src/commands/listing.rs
+use std::collections::{BTreeMap, HashMap};
+use crate::commands::aliases::AliasConfig;
+use crate::commands::groups::GroupConfig;
+use crate::commands::history::HistoryEntry;
+use crate::commands::links::LinkConfig;
+use crate::commands::time::format_timestamp;
+use crate::commands::url::render_tokens;
+
+/// One saved link or alias, as printed by `list-links --url-only`, `--tag-only`
+/// and `--template`.
+#[derive(Debug)]
+pub struct ListEntry<'a> {
+    pub tag: &'a str,
+    pub url: &'a str,
+    /// `Link::last_used`; aliases have no timestamps
+    pub last_used: Option<u64>,
+}
+
+/// Links, then aliases, each in saved order. A link with several URLs gives one
+/// entry per URL.
+pub fn list_entries<'a>(links: &'a LinkConfig, aliases: &'a AliasConfig) -> Vec<ListEntry<'a>> {
+    let alias_entries = aliases.aliases.iter()
+        .map(|(tag, url)| ListEntry { tag, url, last_used: None });
+
+    links.links.iter()
+        .flat_map(|l| l.urls.iter().map(|url| ListEntry { tag: &l.tag, url, last_used: l.last_used }))
+        .chain(alias_entries)
+        .collect()
+}
+
+/// Every saved tag once, sorted, for pickers such as rofi, dmenu or Alfred (`tags`).
+/// With `with_url`, each line is `tag<TAB>url`: a link's URLs are space-separated and
+/// an alias shows its target. An alias shadows a link with the same tag, as it does
+/// when launching.
+pub fn tag_lines(links: &LinkConfig, aliases: &AliasConfig, with_url: bool) -> Vec<String> {
+    let mut tags: BTreeMap<&str, String> = links.links.iter()
+        .map(|l| (l.tag.as_str(), l.urls_text()))
+        .collect();
+    tags.extend(aliases.aliases.iter().map(|(tag, target)| (tag.as_str(), target.clone())));
+
+    tags.into_iter()
+        .map(|(tag, url)| if with_url { format!("{}\t{}", tag, url) } else { tag.to_string() })
+        .collect()
+}
+
+/// Renders one line per entry from a `--template` such as `{tag}\t{url}`.
+///
+/// Tokens: `{tag}`, `{url}`, `{group}` (groups containing the tag, comma-separated),
+/// `{note}`, `{hits}` (launches recorded in the history) and `{last_used}`. Values
+/// that are not known render empty; unknown tokens are an error even when there is
+/// nothing to list. A literal `\t` or `\n` in the template becomes a tab or newline.
+pub fn render_entries(
+    template: &str,
+    entries: &[ListEntry],
+    groups: &GroupConfig,
+    history: &[HistoryEntry],
+) -> Result<Vec<String>, String> {
+    let template = template.replace("\\t", "\t").replace("\\n", "\n");
+
+    let mut hits: HashMap<&str, (usize, u64)> = HashMap::new();
+    for entry in history {
+        if let Some(tag) = &entry.tag {
+            let usage = hits.entry(tag).or_default();
+            usage.0 += 1;
+            usage.1 = usage.1.max(entry.timestamp);
+        }
+    }
+
+    render_tokens(&template, &fields(&ListEntry { tag: "", url: "", last_used: None }, "", None))?;
+    entries.iter()
+        .map(|entry| {
+            let in_groups: Vec<&str> = groups.groups.iter()
+                .filter(|(_, tags)| tags.iter().any(|t| t == entry.tag))
+                .map(|(name, _)| name.as_str())
+                .collect();
+            let usage = hits.get(entry.tag).copied();
+            render_tokens(&template, &fields(entry, &in_groups.join(","), usage))
+        })
+        .collect()
+}
+
+fn fields(entry: &ListEntry, group: &str, usage: Option<(usize, u64)>) -> [(&'static str, String); 6] {
+    let last_used = entry.last_used.max(usage.map(|(_, last)| last));
+    [
+        ("tag", entry.tag.to_string()),
+        ("url", entry.url.to_string()),
+        ("group", group.to_string()),
+        // Links have no notes yet
+        ("note", String::new()),
+        ("hits", usage.map(|(n, _)| n.to_string()).unwrap_or_default()),
+        ("last_used", last_used.map(format_timestamp).unwrap_or_default()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indexmap::IndexMap;
+    use crate::commands::links::Link;
+
+    #[test]
+    fn templates_render_groups_and_usage() {
+        let links = LinkConfig { links: vec![Link::new("ci", "https://ci.example"), Link::new("docs", "https://docs.rs")] };
+        let aliases = AliasConfig { aliases: IndexMap::from([("d".to_string(), "https://d.example".to_string())]) };
+        let groups = GroupConfig {
+            groups: BTreeMap::from([
+                ("work".to_string(), vec!["ci".to_string(), "docs".to_string()]),
+                ("daily".to_string(), vec!["ci".to_string()]),
+            ]),
+        };
+        let history = vec![
+            HistoryEntry { timestamp: 10, tag: Some("ci".to_string()), url: "https://ci.example".to_string() },
+            HistoryEntry { timestamp: 20, tag: Some("ci".to_string()), url: "https://ci.example".to_string() },
+        ];
+        let entries = list_entries(&links, &aliases);
+
+        let lines = render_entries(r"{tag}\t{url}\t{group}", &entries, &groups, &history).unwrap();
+        assert_eq!(lines, ["ci\thttps://ci.example\tdaily,work", "docs\thttps://docs.rs\twork", "d\thttps://d.example\t"]);
+
+        let lines = render_entries("{{{tag}}} hits={hits} note={note}", &entries, &groups, &history).unwrap();
+        assert_eq!(lines, ["{ci} hits=2 note=", "{docs} hits= note=", "{d} hits= note="]);
+
+        assert!(render_entries("{tag} {nope}", &[], &groups, &history).is_err());
+    }
+
+    #[test]
+    fn tags_are_merged_and_sorted() {
+        let links = LinkConfig {
+            links: vec![Link::new("zeta", "https://z.example"), Link::new("docs", "https://docs.rs")],
+        };
+        let aliases = AliasConfig {
+            aliases: IndexMap::from([
+                ("docs".to_string(), "https://doc.rust-lang.org".to_string()),
+                ("api".to_string(), "docs".to_string()),
+            ]),
+        };
+        assert_eq!(tag_lines(&links, &aliases, false), ["api", "docs", "zeta"]);
+        assert_eq!(tag_lines(&links, &aliases, true), [
+            "api\tdocs", "docs\thttps://doc.rust-lang.org", "zeta\thttps://z.example",
+        ]);
+    }
+}
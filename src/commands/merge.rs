@@ -0,0 +1,137 @@
+// src/commands/merge.rs
+use clap::ValueEnum;
+use crate::commands::links::{Change, ChangeSet, Link, LinkConfig};
+
+/// What `import` does when an imported tag is already saved.
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum MergeStrategy {
+    /// Keep the saved link
+    #[default]
+    Skip,
+    /// Replace the saved link with the imported one
+    Overwrite,
+    /// Save the imported link under a free tag (`tag-2`, `tag-3`, ...)
+    Rename,
+    /// Keep whichever link was used or modified more recently. The saved link wins
+    /// ties and whenever either side has no timestamp.
+    Newest,
+}
+
+/// How one imported link was merged.
+#[derive(Debug, PartialEq)]
+pub enum Merged {
+    /// The tag was free, so the link was added
+    Added,
+    /// Conflict: the saved link stayed
+    KeptLocal,
+    /// Conflict: the imported link replaced the saved one
+    TookImported,
+    /// Conflict: the imported link was saved under this tag instead
+    Renamed(String),
+}
+
+/// Merges `incoming` into `links`, resolving a tag conflict with `strategy`.
+pub fn merge_link(links: &mut LinkConfig, incoming: Link, strategy: MergeStrategy) -> (Merged, ChangeSet) {
+    let Some(pos) = links.links.iter().position(|l| l.tag == incoming.tag) else {
+        let change = Change::Added { kind: "link", tag: incoming.tag.clone(), value: incoming.urls_text() };
+        links.links.push(incoming);
+        return (Merged::Added, change.into());
+    };
+
+    let take_imported = match strategy {
+        MergeStrategy::Skip => false,
+        MergeStrategy::Overwrite => true,
+        MergeStrategy::Newest => match (links.links[pos].last_touched(), incoming.last_touched()) {
+            (Some(local), Some(imported)) => imported > local,
+            _ => false,
+        },
+        MergeStrategy::Rename => {
+            let tag = free_tag(links, &incoming.tag);
+            let change = Change::Added { kind: "link", tag: tag.clone(), value: incoming.urls_text() };
+            links.links.push(Link { tag: tag.clone(), ..incoming });
+            return (Merged::Renamed(tag), change.into());
+        }
+    };
+
+    if !take_imported {
+        return (Merged::KeptLocal, ChangeSet::default());
+    }
+    let old = std::mem::replace(&mut links.links[pos], incoming);
+    let new = &links.links[pos];
+    let change = Change::Updated { kind: "link", tag: new.tag.clone(), old: old.urls_text(), new: new.urls_text() };
+    (Merged::TookImported, change.into())
+}
+
+/// The first of `tag-2`, `tag-3`, ... that no saved link uses.
+fn free_tag(links: &LinkConfig, tag: &str) -> String {
+    (2..)
+        .map(|n| format!("{}-{}", tag, n))
+        .find(|candidate| !links.contains(candidate))
+        .expect("unbounded range always yields a free tag")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(tag: &str, url: &str, modified: Option<u64>, last_used: Option<u64>) -> Link {
+        Link { modified, last_used, ..Link::new(tag, url) }
+    }
+
+    fn saved() -> LinkConfig {
+        LinkConfig { links: vec![link("docs", "https://local.example", Some(1_000), Some(5_000))] }
+    }
+
+    #[test]
+    fn newest_keeps_the_more_recently_touched_side() {
+        // Imported was modified after the local link was last used
+        let mut links = saved();
+        let (merged, changes) = merge_link(&mut links, link("docs", "https://imported.example", Some(6_000), None), MergeStrategy::Newest);
+        assert_eq!(merged, Merged::TookImported);
+        assert!(changes.has_updates());
+        assert_eq!(links.get_urls("docs"), Some(vec!["https://imported.example".to_string()]));
+        assert_eq!(links.links.len(), 1);
+
+        // Local last_used (5000) beats an imported modification at 4000
+        let mut links = saved();
+        let (merged, changes) = merge_link(&mut links, link("docs", "https://imported.example", Some(4_000), Some(2_000)), MergeStrategy::Newest);
+        assert_eq!(merged, Merged::KeptLocal);
+        assert!(changes.is_empty());
+        assert_eq!(links.get_urls("docs"), Some(vec!["https://local.example".to_string()]));
+    }
+
+    #[test]
+    fn newest_keeps_local_on_ties_and_missing_timestamps() {
+        let mut links = saved();
+        let (merged, _) = merge_link(&mut links, link("docs", "https://imported.example", Some(5_000), None), MergeStrategy::Newest);
+        assert_eq!(merged, Merged::KeptLocal);
+
+        let (merged, _) = merge_link(&mut links, link("docs", "https://imported.example", None, None), MergeStrategy::Newest);
+        assert_eq!(merged, Merged::KeptLocal);
+
+        let mut untimed = LinkConfig { links: vec![Link::new("docs", "https://local.example")] };
+        let (merged, _) = merge_link(&mut untimed, link("docs", "https://imported.example", Some(9_000), None), MergeStrategy::Newest);
+        assert_eq!(merged, Merged::KeptLocal);
+    }
+
+    #[test]
+    fn other_strategies_resolve_conflicts_without_timestamps() {
+        let incoming = || link("docs", "https://imported.example", Some(1), None);
+
+        let mut links = saved();
+        assert_eq!(merge_link(&mut links, incoming(), MergeStrategy::Skip).0, Merged::KeptLocal);
+
+        let mut links = saved();
+        assert_eq!(merge_link(&mut links, incoming(), MergeStrategy::Overwrite).0, Merged::TookImported);
+        assert_eq!(links.get_urls("docs"), Some(vec!["https://imported.example".to_string()]));
+
+        let mut links = saved();
+        links.links.push(Link::new("docs-2", "https://taken.example"));
+        assert_eq!(merge_link(&mut links, incoming(), MergeStrategy::Rename).0, Merged::Renamed("docs-3".to_string()));
+        assert_eq!(links.get_urls("docs"), Some(vec!["https://local.example".to_string()]));
+        assert_eq!(links.get_urls("docs-3"), Some(vec!["https://imported.example".to_string()]));
+
+        let (merged, _) = merge_link(&mut links, link("new", "https://new.example", None, None), MergeStrategy::Skip);
+        assert_eq!(merged, Merged::Added);
+    }
+}
@@ -0,0 +1,33 @@
+pub mod detect;
+pub mod links;
+pub mod aliases;
+pub mod time;
+pub mod config;
+pub mod groups;
+pub mod bookmarks;
+pub mod error;
+pub mod hooks;
+pub mod csv;
+pub mod history;
+pub mod glob;
+pub mod blocklist;
+pub mod cache;
+pub mod resolve;
+pub mod snapshot;
+pub mod url;
+pub mod prompt;
+pub mod random;
+pub mod version;
+pub mod profile;
+pub mod project;
+pub mod ratelimit;
+pub mod quote;
+pub mod merge;
+pub mod listing;
+pub mod clipboard;
+pub mod doctor;
+pub mod bundle;
+pub mod output;
+pub mod stats;
+pub mod check;
+pub mod schema;
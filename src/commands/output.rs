@@ -0,0 +1,146 @@
+// src/commands/output.rs
+use std::borrow::Cow;
+use std::env;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// How much the run prints, from `-q`/`-v`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Errors only
+    Quiet,
+    /// Progress, results and warnings
+    Normal,
+    /// Also the paths probed, registry keys read and commands run
+    Verbose,
+}
+
+/// ASCII stand-ins for the icons user-facing messages start with.
+const PLAIN_ICONS: &[(char, &str)] = &[
+    ('✅', "[ok]"),
+    ('❎', "[skip]"),
+    ('⚠', "[warn]"),
+    ('❌', "[error]"),
+    ('⛔', "[blocked]"),
+    ('🛑', "[stop]"),
+    ('⏳', "[wait]"),
+    ('🔒', "[wait]"),
+    ('🚀', "[launch]"),
+    ('💾', "[saved]"),
+    ('📋', "[copied]"),
+    ('🧹', "[clean]"),
+    ('📦', "[moved]"),
+    ('🔍', "[info]"),
+    ('⚡', "[info]"),
+    ('ℹ', "[info]"),
+    ('📌', "[info]"),
+    ('🔀', "[info]"),
+    ('⚖', "[info]"),
+    ('📚', "[info]"),
+    ('🔄', "[info]"),
+    ('🏷', "[info]"),
+    ('🩺', "[info]"),
+    ('🖊', "[input]"),
+    ('🔎', "[debug]"),
+    ('❓', "[?]"),
+    ('🌐', "[info]"),
+    ('✨', "[info]"),
+    ('📄', "[info]"),
+    ('📁', "[info]"),
+];
+
+/// Switches output to plain ASCII for the rest of the run (`--plain`).
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// Sets the verbosity for the rest of the run (`-q`/`-v`).
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        _ => Verbosity::Verbose,
+    }
+}
+
+/// Whether to print without emoji and colors: `--plain` was given or `NO_COLOR`
+/// is set to a non-empty value (https://no-color.org).
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed) || env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+}
+
+/// The line as it should be printed: unchanged normally, with icons replaced by
+/// `[ok]`, `[warn]`, `[error]`, ... in plain mode.
+pub fn decorate(line: &str) -> Cow<'_, str> {
+    if is_plain() {
+        Cow::Owned(plain_icons(line))
+    } else {
+        Cow::Borrowed(line)
+    }
+}
+
+fn plain_icons(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match PLAIN_ICONS.iter().find(|(icon, _)| *icon == c) {
+            Some((_, text)) => {
+                out.push_str(text);
+                // Drop the emoji presentation selector that follows ⚠, ℹ, ...
+                chars.next_if_eq(&'\u{FE0F}');
+            }
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+/// `println!` for user-facing messages; see [`decorate`]. Silent with `--quiet`.
+#[macro_export]
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if $crate::output::verbosity() > $crate::output::Verbosity::Quiet {
+            println!("{}", $crate::output::decorate(&format!($($arg)*)))
+        }
+    };
+}
+
+/// `eprintln!` for warnings; see [`decorate`]. Silent with `--quiet`.
+#[macro_export]
+macro_rules! esay {
+    ($($arg:tt)*) => {
+        if $crate::output::verbosity() > $crate::output::Verbosity::Quiet {
+            eprintln!("{}", $crate::output::decorate(&format!($($arg)*)))
+        }
+    };
+}
+
+/// Extra detail on stderr, printed only with `--verbose`.
+#[macro_export]
+macro_rules! verbose {
+    ($($arg:tt)*) => {
+        if $crate::output::verbosity() == $crate::output::Verbosity::Verbose {
+            eprintln!("{}", $crate::output::decorate(&format!("🔎 {}", format_args!($($arg)*))))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn icons_become_ascii_prefixes() {
+        assert_eq!(plain_icons("✅ Link saved!"), "[ok] Link saved!");
+        assert_eq!(plain_icons("⚠️ No links saved."), "[warn] No links saved.");
+        assert_eq!(plain_icons("❌ Error: nope"), "[error] Error: nope");
+        assert_eq!(plain_icons("\n📄 Saved links:"), "\n[info] Saved links:");
+        assert_eq!(plain_icons("[a] -> https://a.example"), "[a] -> https://a.example");
+    }
+}
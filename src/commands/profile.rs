@@ -0,0 +1,41 @@
+// src/commands/profile.rs
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::{say, esay};
+
+/// A throwaway browser profile directory, removed again when dropped.
+///
+/// Used by `--incognito-container` so each launch starts from an empty profile: no
+/// extensions, cookies or incognito session shared with any other window.
+#[derive(Debug)]
+pub struct TempProfile {
+    dir: PathBuf,
+}
+
+impl TempProfile {
+    /// Creates an empty directory under the system temp dir.
+    pub fn create() -> io::Result<Self> {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or_default();
+        let dir = env::temp_dir().join(format!("quick_tabs_profile_{}_{}", process::id(), nanos));
+        fs::create_dir_all(&dir)?;
+        Ok(TempProfile { dir })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Drop for TempProfile {
+    fn drop(&mut self) {
+        match fs::remove_dir_all(&self.dir) {
+            Ok(()) => say!("🧹 Removed temporary profile {}", self.dir.display()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => esay!("⚠️ Failed to remove temporary profile {}: {}", self.dir.display(), e),
+        }
+    }
+}
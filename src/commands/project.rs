@@ -0,0 +1,141 @@
+// src/commands/project.rs
+use indexmap::IndexMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Serialize, Deserialize};
+use crate::commands::aliases::AliasConfig;
+use crate::commands::error::QuickTabsError;
+use crate::commands::links::{Link, LinkConfig};
+
+/// File name looked for in the current directory and its ancestors.
+pub const PROJECT_FILE: &str = ".quick_tabs.json";
+
+/// Per-project overrides, read from the nearest `.quick_tabs.json`.
+///
+/// Precedence: the project's links and aliases are merged over the global ones, so a
+/// project tag replaces a global tag of the same name and all other global tags stay
+/// available. `browser` is used instead of the saved browser; `--browser` still wins.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    /// Browser name or path, in the same forms `--browser` accepts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub browser: Option<String>,
+    #[serde(default)]
+    pub links: Vec<Link>,
+    #[serde(default)]
+    pub aliases: IndexMap<String, String>,
+}
+
+/// A project config together with the file it came from.
+#[derive(Debug)]
+pub struct Project {
+    pub path: PathBuf,
+    pub config: ProjectConfig,
+}
+
+impl ProjectConfig {
+    pub fn load(path: &Path) -> Result<Self, QuickTabsError> {
+        let data = fs::read_to_string(path)?;
+        serde_json::from_str(&data)
+            .map_err(|e| QuickTabsError::Other(format!("Failed to parse project config {}: {}", path.display(), e)))
+    }
+
+    /// Overlays the project's links and aliases onto the global ones (in memory only).
+    pub fn merge_into(&self, links: &mut LinkConfig, aliases: &mut AliasConfig) {
+        for link in &self.links {
+            links.links.retain(|l| l.tag != link.tag);
+            links.links.push(link.clone());
+        }
+        for (tag, target) in &self.aliases {
+            aliases.aliases.insert(tag.clone(), target.clone());
+        }
+    }
+}
+
+/// Walks up from `start` and returns the first `.quick_tabs.json` found.
+pub fn find_project_file(start: &Path) -> Option<PathBuf> {
+    start.ancestors()
+        .map(|dir| dir.join(PROJECT_FILE))
+        .find(|path| path.is_file())
+}
+
+/// Loads the project config that applies to the current directory, if any.
+pub fn discover() -> Result<Option<Project>, QuickTabsError> {
+    let Some(path) = find_project_file(&env::current_dir()?) else {
+        return Ok(None);
+    };
+    let config = ProjectConfig::load(&path)?;
+    Ok(Some(Project { path, config }))
+}
+
+/// Writes an empty `.quick_tabs.json` into `dir`, refusing to overwrite one.
+pub fn init(dir: &Path, browser: Option<String>) -> Result<PathBuf, QuickTabsError> {
+    let path = dir.join(PROJECT_FILE);
+    if path.exists() {
+        return Err(QuickTabsError::Other(format!("{} already exists", path.display())));
+    }
+    let config = ProjectConfig { browser, ..ProjectConfig::default() };
+    let json = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    fs::write(&path, json)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh `<tmp>/quick_tabs_project_<name>_<pid>/a/b` tree; returns its root.
+    fn temp_tree(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("quick_tabs_project_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        root
+    }
+
+    #[test]
+    fn finds_project_file_in_an_ancestor() {
+        let root = temp_tree("find");
+        assert_eq!(find_project_file(&root.join("a/b")), None);
+
+        let path = init(&root.join("a"), Some("firefox".to_string())).unwrap();
+        assert_eq!(find_project_file(&root.join("a/b")), Some(path.clone()));
+        assert_eq!(find_project_file(&root.join("a")), Some(path.clone()));
+        assert_eq!(ProjectConfig::load(&path).unwrap().browser.as_deref(), Some("firefox"));
+
+        // The nearest file wins
+        let inner = init(&root.join("a/b"), None).unwrap();
+        assert_eq!(find_project_file(&root.join("a/b")), Some(inner));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn init_refuses_to_overwrite() {
+        let root = temp_tree("init");
+        init(&root, None).unwrap();
+        assert!(init(&root, None).is_err());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn project_entries_override_global_ones() {
+        let mut links = LinkConfig {
+            links: vec![
+                Link::new("docs", "https://global.example/docs"),
+                Link::new("mail", "https://mail.example"),
+            ],
+        };
+        let mut aliases = AliasConfig { aliases: IndexMap::from([("d".to_string(), "docs".to_string())]) };
+        let project = ProjectConfig {
+            browser: None,
+            links: vec![Link::new("docs", "https://project.example/docs")],
+            aliases: IndexMap::from([("d".to_string(), "https://project.example".to_string())]),
+        };
+
+        project.merge_into(&mut links, &mut aliases);
+        assert_eq!(links.get_urls("docs"), Some(vec!["https://project.example/docs".to_string()]));
+        assert_eq!(links.get_urls("mail"), Some(vec!["https://mail.example".to_string()]));
+        assert_eq!(links.links.len(), 2);
+        assert_eq!(aliases.resolve("d").as_deref(), Some("https://project.example"));
+    }
+}
@@ -0,0 +1,53 @@
+// src/commands/prompt.rs
+use std::io::{self, IsTerminal, Write};
+
+/// Reply to a per-item confirmation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Answer {
+    Yes,
+    No,
+    /// Stop asking; treat every remaining item as declined
+    Quit,
+}
+
+/// Prints `question` without a newline and reads one trimmed line from stdin.
+/// End of input reads as an empty answer.
+pub fn ask(question: &str) -> io::Result<String> {
+    print!("{}", question);
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_string())
+}
+
+/// Asks `<question> [y/N]`. Only `y`/`yes` confirms; a read error or a closed
+/// stdin declines. Piping `yes` into the command answers every prompt.
+pub fn confirm(question: &str) -> bool {
+    matches!(ask(&format!("{} [y/N] ", question)).map(|a| a.to_lowercase()).as_deref(), Ok("y" | "yes"))
+}
+
+/// Asks `<question> [y/N/q]`. Anything but `y`/`yes`/`q`/`quit` means no, and so
+/// does a read error, so a closed stdin never confirms anything.
+pub fn confirm_each(question: &str) -> Answer {
+    match ask(&format!("{} [y/N/q] ", question)).map(|a| a.to_lowercase()) {
+        Ok(a) if a == "y" || a == "yes" => Answer::Yes,
+        Ok(a) if a == "q" || a == "quit" => Answer::Quit,
+        _ => Answer::No,
+    }
+}
+
+/// Whether stdin is a terminal, i.e. someone is there to answer a prompt.
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal()
+}
+
+/// Lists `items` numbered from 1 and asks for one. Anything but a listed number,
+/// including an empty answer or a closed stdin, picks nothing.
+pub fn choose(question: &str, items: &[String]) -> Option<usize> {
+    for (i, item) in items.iter().enumerate() {
+        println!("  [{}] {}", i + 1, item);
+    }
+    let answer = ask(&format!("{} [1-{}, Enter to abort] ", question, items.len())).ok()?;
+    answer.parse::<usize>().ok().filter(|n| (1..=items.len()).contains(n)).map(|n| n - 1)
+}
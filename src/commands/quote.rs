@@ -0,0 +1,115 @@
+// src/commands/quote.rs
+use std::process::Command;
+
+/// Quotes `arg` so it survives being pasted into this platform's shell: `cmd.exe`
+/// on Windows, a POSIX shell everywhere else.
+pub fn shell_quote(arg: &str) -> String {
+    if cfg!(target_os = "windows") {
+        quote_cmd(arg)
+    } else {
+        quote_posix(arg)
+    }
+}
+
+/// Quotes each argument and joins them with spaces.
+pub fn shell_join(args: &[String]) -> String {
+    args.iter().map(|a| shell_quote(a)).collect::<Vec<_>>().join(" ")
+}
+
+/// Renders a command as a copy-pasteable line for this platform's shell.
+pub fn command_line(command: &Command) -> String {
+    let program = command.get_program().to_string_lossy();
+    let mut line = shell_quote(&program);
+    for arg in command.get_args() {
+        line.push(' ');
+        line.push_str(&shell_quote(&arg.to_string_lossy()));
+    }
+    line
+}
+
+/// POSIX `sh` quoting: plain words stay as they are, anything else is wrapped in
+/// single quotes, with embedded `'` written as `'\''`.
+pub fn quote_posix(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg.chars().all(|c| c.is_ascii_alphanumeric() || "_@%+=:,./-".contains(c));
+    if plain {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+/// `cmd.exe` quoting. The argument is first quoted the way Windows programs split
+/// their command line (`CommandLineToArgvW`), then every character `cmd` itself
+/// would interpret (`&`, `|`, `%`, `"`, ...) is escaped with `^`.
+pub fn quote_cmd(arg: &str) -> String {
+    let needs_quotes = arg.is_empty() || arg.contains([' ', '\t', '"']);
+    let mut argv = String::new();
+    if needs_quotes {
+        argv.push('"');
+        let mut backslashes = 0;
+        for c in arg.chars() {
+            match c {
+                '\\' => backslashes += 1,
+                '"' => {
+                    // Backslashes before a quote are doubled, plus one to escape it
+                    argv.push_str(&"\\".repeat(backslashes * 2 + 1));
+                    argv.push('"');
+                    backslashes = 0;
+                }
+                _ => {
+                    argv.push_str(&"\\".repeat(backslashes));
+                    argv.push(c);
+                    backslashes = 0;
+                }
+            }
+        }
+        // Doubled so they don't escape the closing quote
+        argv.push_str(&"\\".repeat(backslashes * 2));
+        argv.push('"');
+    } else {
+        argv.push_str(arg);
+    }
+
+    let mut quoted = String::with_capacity(argv.len());
+    for c in argv.chars() {
+        if "()%!^\"<>&|".contains(c) {
+            quoted.push('^');
+        }
+        quoted.push(c);
+    }
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn posix_leaves_plain_urls_alone() {
+        assert_eq!(quote_posix("https://example.com/a-b_c"), "https://example.com/a-b_c");
+        assert_eq!(quote_posix("--incognito"), "--incognito");
+    }
+
+    #[test]
+    fn posix_quotes_spaces_ampersands_and_quotes() {
+        assert_eq!(quote_posix("https://x.com/?q=a b"), "'https://x.com/?q=a b'");
+        assert_eq!(quote_posix("https://x.com/?a=1&b=2"), "'https://x.com/?a=1&b=2'");
+        assert_eq!(quote_posix(r#"https://x.com/?q="it's""#), r#"'https://x.com/?q="it'\''s"'"#);
+        assert_eq!(quote_posix(""), "''");
+    }
+
+    #[test]
+    fn cmd_escapes_metacharacters() {
+        assert_eq!(quote_cmd("https://example.com/page"), "https://example.com/page");
+        assert_eq!(quote_cmd("https://x.com/?a=1&b=2"), "https://x.com/?a=1^&b=2");
+        assert_eq!(quote_cmd("https://x.com/?q=100%"), "https://x.com/?q=100^%");
+    }
+
+    #[test]
+    fn cmd_quotes_spaces_and_embedded_quotes() {
+        assert_eq!(quote_cmd("https://x.com/?q=a b&c"), r#"^"https://x.com/?q=a b^&c^""#);
+        assert_eq!(quote_cmd(r#"say "hi""#), r#"^"say \^"hi\^"^""#);
+        assert_eq!(quote_cmd(r"C:\Program Files\x\"), r#"^"C:\Program Files\x\\^""#);
+        assert_eq!(quote_cmd(""), r#"^"^""#);
+    }
+}
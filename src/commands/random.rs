@@ -0,0 +1,58 @@
+// src/commands/random.rs
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Tiny 64-bit linear congruential generator (Knuth's MMIX constants). Good enough to
+/// vary tab order; not suitable for anything security-related.
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new(seed: u64) -> Self {
+        Lcg(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        // The high bits of an LCG are the most random
+        self.0.rotate_left(32)
+    }
+
+    /// A value in `0..n`. `n` must be non-zero.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// Shuffles `items` in place (Fisher-Yates). The same seed always gives the same order.
+pub fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut rng = Lcg::new(seed);
+    for i in (1..items.len()).rev() {
+        items.swap(i, rng.below(i + 1));
+    }
+}
+
+/// A seed that differs between runs, taken from the clock and process id.
+pub fn random_seed() -> u64 {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or_default();
+    nanos ^ (std::process::id() as u64).rotate_left(32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_order() {
+        let mut a: Vec<u32> = (0..20).collect();
+        let mut b = a.clone();
+        shuffle(&mut a, 42);
+        shuffle(&mut b, 42);
+        assert_eq!(a, b);
+
+        let mut sorted = a.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+        assert_ne!(a, sorted);
+    }
+}
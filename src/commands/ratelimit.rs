@@ -0,0 +1,169 @@
+// src/commands/ratelimit.rs
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde::{Serialize, Deserialize};
+use crate::commands::config::{app_config_dir, write_atomic};
+use crate::commands::error::QuickTabsError;
+use crate::say;
+
+const MINUTE_MS: f64 = 60_000.0;
+
+/// Source of the current time, injectable so the bucket can be tested.
+pub trait Clock {
+    /// Milliseconds since the Unix epoch.
+    fn now_ms(&self) -> u64;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or_default()
+    }
+}
+
+/// Token-bucket state persisted between invocations in `ratelimit.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Bucket {
+    /// Opens still available right now
+    pub tokens: f64,
+    /// When `tokens` was last refilled (ms since the epoch); 0 means never, i.e. full
+    pub updated_ms: u64,
+}
+
+impl Bucket {
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string(&self)?;
+        write_atomic(path, json)
+    }
+
+    /// Takes `count` opens from a bucket that holds `per_minute` and refills at
+    /// `per_minute` per minute. On refusal nothing is taken and the wait until
+    /// enough opens are available is returned.
+    pub fn try_take(&mut self, per_minute: u32, count: u32, clock: &impl Clock) -> Result<(), Duration> {
+        let capacity = per_minute as f64;
+        let now = clock.now_ms();
+        let elapsed = now.saturating_sub(self.updated_ms) as f64;
+        self.tokens = if self.updated_ms == 0 {
+            capacity
+        } else {
+            (self.tokens + elapsed * capacity / MINUTE_MS).min(capacity)
+        };
+        self.updated_ms = now;
+
+        let needed = count as f64;
+        // Tolerate float drift so a wait of exactly the returned delay succeeds
+        if self.tokens + 1e-9 >= needed {
+            self.tokens = (self.tokens - needed).max(0.0);
+            Ok(())
+        } else {
+            let missing = needed - self.tokens;
+            Err(Duration::from_millis((missing * MINUTE_MS / capacity).ceil() as u64))
+        }
+    }
+}
+
+/// Gets the rate limiter state path (~/.config/quick_tabs/ratelimit.json)
+pub fn ratelimit_path() -> Result<PathBuf, QuickTabsError> {
+    Ok(app_config_dir()?.join("ratelimit.json"))
+}
+
+/// Reserves `count` opens under a limit of `per_minute` shared by every invocation.
+/// When the limit is reached this either sleeps until the opens are available
+/// (`wait`) or fails with the time left.
+pub fn acquire(per_minute: u32, count: u32, wait: bool) -> Result<(), QuickTabsError> {
+    if per_minute == 0 {
+        return Err(QuickTabsError::Other("max_opens_per_minute is 0, so nothing may be opened".to_string()));
+    }
+    if count > per_minute {
+        return Err(QuickTabsError::Other(format!(
+            "Opening {} links at once exceeds the limit of {} per minute",
+            count, per_minute
+        )));
+    }
+
+    let path = ratelimit_path()?;
+    loop {
+        let mut bucket = Bucket::load(&path);
+        let result = bucket.try_take(per_minute, count, &SystemClock);
+        bucket.save(&path)?;
+        match result {
+            Ok(()) => return Ok(()),
+            Err(delay) if wait => {
+                say!("⏳ Rate limit of {} opens per minute reached; waiting {:.1}s...", per_minute, delay.as_secs_f64());
+                thread::sleep(delay);
+            }
+            Err(delay) => {
+                return Err(QuickTabsError::Other(format!(
+                    "Rate limit of {} opens per minute reached. Next launch allowed in {:.1}s (or pass --wait-for-slot).",
+                    per_minute,
+                    delay.as_secs_f64()
+                )));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct FakeClock(Cell<u64>);
+
+    impl FakeClock {
+        fn advance(&self, ms: u64) {
+            self.0.set(self.0.get() + ms);
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now_ms(&self) -> u64 {
+            self.0.get()
+        }
+    }
+
+    #[test]
+    fn new_bucket_allows_a_full_minute_then_refuses() {
+        let clock = FakeClock(Cell::new(1_000_000));
+        let mut bucket = Bucket::default();
+        assert!(bucket.try_take(3, 2, &clock).is_ok());
+        assert!(bucket.try_take(3, 1, &clock).is_ok());
+        // Empty: one open refills every 20s at 3 per minute
+        assert_eq!(bucket.try_take(3, 1, &clock), Err(Duration::from_secs(20)));
+    }
+
+    #[test]
+    fn tokens_refill_over_time_up_to_capacity() {
+        let clock = FakeClock(Cell::new(1_000_000));
+        let mut bucket = Bucket::default();
+        bucket.try_take(6, 6, &clock).unwrap();
+
+        clock.advance(10_000);
+        assert!(bucket.try_take(6, 1, &clock).is_ok());
+        assert_eq!(bucket.try_take(6, 1, &clock), Err(Duration::from_secs(10)));
+
+        clock.advance(10 * 60_000);
+        assert!(bucket.try_take(6, 6, &clock).is_ok());
+        assert!(bucket.try_take(6, 1, &clock).is_err());
+    }
+
+    #[test]
+    fn refusal_takes_nothing() {
+        let clock = FakeClock(Cell::new(1_000_000));
+        let mut bucket = Bucket::default();
+        bucket.try_take(4, 3, &clock).unwrap();
+        assert!(bucket.try_take(4, 2, &clock).is_err());
+        assert!(bucket.try_take(4, 1, &clock).is_ok());
+    }
+}
@@ -0,0 +1,419 @@
+// src/commands/resolve.rs
+use std::env;
+use indexmap::IndexMap;
+use serde::Serialize;
+use crate::commands::aliases::AliasConfig;
+use crate::commands::error::QuickTabsError;
+use crate::commands::groups::GroupConfig;
+use crate::commands::links::{Link, LinkConfig};
+use crate::commands::url::{fill_query, QUERY_PLACEHOLDER};
+use crate::esay;
+
+/// How many alias hops `launch` and previews follow before giving up.
+pub const MAX_ALIAS_DEPTH: usize = 8;
+
+/// How many near-miss tags `launch` offers for an unknown tag.
+const MAX_SUGGESTIONS: usize = 5;
+
+// --- Data Structures ---
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagKind {
+    Link,
+    Alias,
+}
+
+impl TagKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            TagKind::Link => "link",
+            TagKind::Alias => "alias",
+        }
+    }
+}
+
+/// An environment variable referenced by a saved URL (`$NAME` or `${NAME}`).
+#[derive(Debug, Serialize)]
+pub struct EnvRef {
+    pub name: String,
+    pub set: bool,
+}
+
+/// How a saved tag resolves, for auditing with `resolve-all`.
+#[derive(Debug, Serialize)]
+pub struct Resolution {
+    pub tag: String,
+    pub kind: TagKind,
+    /// The URLs that `launch <tag>` would open, space-separated, placeholders left intact
+    pub resolved: Option<String>,
+    /// `{name}` template placeholders found in the URL
+    pub placeholders: Vec<String>,
+    /// Environment variables the URL refers to, and whether each is currently set
+    pub env_vars: Vec<EnvRef>,
+    /// Why the tag does not resolve to something launchable
+    pub error: Option<String>,
+}
+
+// --- Resolution ---
+
+/// Resolves a launch target: aliases win over links, and anything else is
+/// passed through as a literal URL. An alias may point at another alias or a link
+/// tag, which is followed for up to `MAX_ALIAS_DEPTH` hops; a chain that loops back
+/// on itself or runs longer is an error. A link with several URLs ends the chain
+/// and resolves to all of them, as does a `group:<name>` reference to the URLs of
+/// the group's links.
+pub fn resolve_target(target: &str, aliases: &AliasConfig, links: &LinkConfig, groups: &GroupConfig) -> Result<Vec<String>, QuickTabsError> {
+    let mut chain = vec![target.to_string()];
+    let mut current = target.to_string();
+
+    loop {
+        if let Some(name) = current.strip_prefix("group:") {
+            return match groups.resolve(name, links) {
+                Some(urls) if urls.is_empty() => Err(QuickTabsError::Other(format!("Group '{}' has no links to open", name))),
+                Some(urls) => Ok(urls),
+                None => Err(QuickTabsError::NotFound(format!("Group '{}'", name))),
+            };
+        }
+        let next = match aliases.resolve(&current) {
+            Some(value) => value,
+            None => match links.get_urls(&current) {
+                Some(mut urls) if urls.len() == 1 => urls.remove(0),
+                Some(urls) if urls.is_empty() => {
+                    return Err(QuickTabsError::Other(format!("Link '{}' has no URLs", current)));
+                }
+                Some(urls) => return Ok(urls),
+                None => return Ok(vec![current]),
+            },
+        };
+        if chain.contains(&next) {
+            chain.push(next);
+            return Err(QuickTabsError::Other(format!("Alias cycle: {}", chain.join(" -> "))));
+        }
+        if chain.len() > MAX_ALIAS_DEPTH {
+            return Err(QuickTabsError::Other(format!(
+                "'{}' goes through more than {} aliases: {} -> ...", target, MAX_ALIAS_DEPTH, chain.join(" -> ")
+            )));
+        }
+        chain.push(next.clone());
+        current = next;
+    }
+}
+
+/// The link `target` ends up at, following aliases the way `resolve_target` does.
+/// `None` when it is not saved, resolves to a literal URL or goes around in circles.
+pub fn target_link<'a>(target: &str, aliases: &AliasConfig, links: &'a LinkConfig) -> Option<&'a Link> {
+    let mut current = target.to_string();
+    for _ in 0..=MAX_ALIAS_DEPTH {
+        match aliases.resolve(&current) {
+            Some(next) => current = next,
+            None => return links.links.iter().find(|l| l.tag == current),
+        }
+    }
+    None
+}
+
+/// Expands a `!bang` launch target into a search URL. `bang` is the target without
+/// its `!`: the search tag, optionally followed by words when the whole target was
+/// quoted (`"!g rust traits"`). Those words and `words` are percent-encoded into the
+/// template's `{}`; with no words at all the `{}` is left empty.
+pub fn expand_search(bang: &str, words: &[String], searches: &IndexMap<String, String>) -> Result<String, QuickTabsError> {
+    let mut parts = bang.split_whitespace();
+    let tag = parts.next().unwrap_or_default();
+    let Some(template) = searches.get(tag) else {
+        if searches.is_empty() {
+            esay!("ℹ️ No searches defined yet; add one with `set-search <tag> <url-with-{{}}>`.");
+        } else {
+            let defined: Vec<String> = searches.keys().map(|tag| format!("!{}", tag)).collect();
+            esay!("ℹ️ Defined searches: {}", defined.join(", "));
+        }
+        return Err(QuickTabsError::NotFound(format!("Search '!{}'", tag)));
+    };
+    let words: Vec<String> = parts.map(str::to_string).chain(words.iter().cloned()).collect();
+    if words.is_empty() {
+        return Ok(template.replace(QUERY_PLACEHOLDER, ""));
+    }
+    fill_query(template, &words).map_err(QuickTabsError::Other)
+}
+
+/// Whether `target` is a saved alias or link tag.
+pub fn is_saved_tag(target: &str, aliases: &AliasConfig, links: &LinkConfig) -> bool {
+    aliases.aliases.contains_key(target) || links.contains(target)
+}
+
+/// The saved tags closest to `target`, best first: those within a small
+/// case-insensitive edit distance (a third of the typed length, at least 2) or
+/// starting with what was typed. Ties are broken alphabetically.
+pub fn suggest_tags(target: &str, aliases: &AliasConfig, links: &LinkConfig) -> Vec<String> {
+    let typed = target.to_lowercase();
+    let max_distance = (typed.chars().count() / 3).max(2);
+
+    let mut scored: Vec<(usize, &String)> = aliases.aliases.keys()
+        .chain(links.links.iter().map(|l| &l.tag))
+        .filter_map(|tag| {
+            let lower = tag.to_lowercase();
+            let distance = strsim::levenshtein(&typed, &lower);
+            (distance <= max_distance || lower.starts_with(&typed)).then_some((distance, tag))
+        })
+        .collect();
+    scored.sort();
+    scored.dedup_by(|a, b| a.1 == b.1);
+    scored.into_iter().take(MAX_SUGGESTIONS).map(|(_, tag)| tag.clone()).collect()
+}
+
+/// Explains how a single saved tag resolves.
+pub fn explain(tag: &str, kind: TagKind, aliases: &AliasConfig, links: &LinkConfig, groups: &GroupConfig) -> Resolution {
+    // Aliases shadow links, so a link tag may actually launch an alias's URL
+    let chained = match kind {
+        TagKind::Alias if !aliases.aliases.contains_key(tag) => None,
+        _ => Some(resolve_target(tag, aliases, links, groups)),
+    };
+    let (urls, chain_error) = match chained {
+        Some(Ok(urls)) => (Some(urls), None),
+        Some(Err(e)) => (None, Some(e.to_string())),
+        None => (None, None),
+    };
+    let resolved = urls.as_ref().map(|urls| urls.join(" "));
+
+    let (placeholders, env_vars) = match &resolved {
+        Some(url) => (find_placeholders(url), find_env_refs(url)),
+        None => (vec![], vec![]),
+    };
+
+    let error = chain_error.or_else(|| match &urls {
+        None => Some("tag not found".to_string()),
+        Some(urls) if urls.iter().any(|url| url.trim().is_empty()) => Some("empty URL".to_string()),
+        Some(urls) if urls.iter().any(|url| url.chars().any(char::is_whitespace)) => {
+            Some("URL contains whitespace".to_string())
+        }
+        Some(_) => env_vars.iter()
+            .find(|v| !v.set)
+            .map(|v| format!("environment variable ${} is not set", v.name)),
+    });
+
+    Resolution { tag: tag.to_string(), kind, resolved, placeholders, env_vars, error }
+}
+
+/// Explains every saved link and alias, links first, each sorted by tag.
+pub fn resolve_all(aliases: &AliasConfig, links: &LinkConfig, groups: &GroupConfig) -> Vec<Resolution> {
+    let mut link_tags: Vec<&str> = links.links.iter().map(|l| l.tag.as_str()).collect();
+    link_tags.sort();
+    let mut alias_tags: Vec<&str> = aliases.aliases.keys().map(|t| t.as_str()).collect();
+    alias_tags.sort();
+
+    link_tags.into_iter()
+        .map(|t| explain(t, TagKind::Link, aliases, links, groups))
+        .chain(alias_tags.into_iter().map(|t| explain(t, TagKind::Alias, aliases, links, groups)))
+        .collect()
+}
+
+/// The hops an alias goes through, for `list-links --resolve`. Follows aliases that
+/// point at other aliases or link tags and `group:<name>` references, without
+/// launching anything. The last element is the final URL or a `(...)` note such
+/// as `(3 urls)` or `(cycle)`.
+pub fn alias_steps(tag: &str, aliases: &AliasConfig, links: &LinkConfig, groups: &GroupConfig) -> Vec<String> {
+    let mut steps = vec![];
+    let mut seen = vec![tag.to_string()];
+    let mut current = match aliases.resolve(tag) {
+        Some(value) => value,
+        None => return vec!["(missing)".to_string()],
+    };
+
+    loop {
+        steps.push(current.clone());
+
+        if let Some(name) = current.strip_prefix("group:") {
+            let note = match groups.resolve(name, links) {
+                Some(urls) => url_count_note(urls.len()),
+                None => "(missing group)".to_string(),
+            };
+            steps.push(note);
+            break;
+        }
+
+        let next = match aliases.resolve(&current) {
+            Some(value) => Some(value),
+            None => match links.get_urls(&current) {
+                Some(mut urls) if urls.len() == 1 => Some(urls.remove(0)),
+                Some(urls) => {
+                    steps.push(url_count_note(urls.len()));
+                    break;
+                }
+                None => None,
+            },
+        };
+        match next {
+            Some(_) if seen.contains(&current) => {
+                steps.push("(cycle)".to_string());
+                break;
+            }
+            Some(_) if seen.len() > MAX_ALIAS_DEPTH => {
+                steps.push("(too deep)".to_string());
+                break;
+            }
+            Some(value) => {
+                seen.push(current);
+                current = value;
+            }
+            None => break,
+        }
+    }
+    steps
+}
+
+// --- Utility Functions ---
+
+fn url_count_note(count: usize) -> String {
+    format!("({} url{})", count, if count == 1 { "" } else { "s" })
+}
+
+/// Names of `{name}` placeholders, in order of appearance. `${NAME}` is an env reference.
+fn find_placeholders(url: &str) -> Vec<String> {
+    let mut found = vec![];
+    let mut rest = url;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) => {
+                let name = &after[..end];
+                let is_env = rest[..start].ends_with('$');
+                if !is_env && !name.is_empty() && !found.iter().any(|f| f == name) {
+                    found.push(name.to_string());
+                }
+                rest = &after[end + 1..];
+            }
+            None => break,
+        }
+    }
+    found
+}
+
+/// Environment variables referenced as `$NAME` or `${NAME}`.
+fn find_env_refs(url: &str) -> Vec<EnvRef> {
+    let mut refs: Vec<EnvRef> = vec![];
+    let mut rest = url;
+    while let Some(start) = rest.find('$') {
+        let after = &rest[start + 1..];
+        let (name, consumed) = match after.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => ("", 1),
+            },
+            None => {
+                let end = after.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(after.len());
+                (&after[..end], end)
+            }
+        };
+        if !name.is_empty() && !refs.iter().any(|r| r.name == name) {
+            refs.push(EnvRef { name: name.to_string(), set: env::var_os(name).is_some() });
+        }
+        rest = &after[consumed.min(after.len())..];
+    }
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(aliases: &[(&str, &str)]) -> (AliasConfig, LinkConfig) {
+        let aliases = AliasConfig {
+            aliases: aliases.iter().map(|(t, v)| (t.to_string(), v.to_string())).collect::<IndexMap<_, _>>(),
+        };
+        let links = LinkConfig { links: vec![Link::new("dashboard", "https://dash.example")] };
+        (aliases, links)
+    }
+
+    #[test]
+    fn chained_aliases_resolve_to_the_final_url() {
+        let (aliases, links) = config(&[("work", "board"), ("board", "dashboard")]);
+        assert_eq!(resolve_target("work", &aliases, &links, &GroupConfig::default()).unwrap(), ["https://dash.example"]);
+        assert_eq!(resolve_target("https://x.example", &aliases, &links, &GroupConfig::default()).unwrap(), ["https://x.example"]);
+    }
+
+    #[test]
+    fn multi_url_links_resolve_to_every_url() {
+        let (aliases, mut links) = config(&[("start", "morning")]);
+        links.links.push(Link {
+            urls: vec!["https://mail.example".to_string(), "https://cal.example".to_string()],
+            ..Link::new("morning", "")
+        });
+        assert_eq!(resolve_target("start", &aliases, &links, &GroupConfig::default()).unwrap(), ["https://mail.example", "https://cal.example"]);
+    }
+
+    #[test]
+    fn group_references_resolve_to_the_group_urls() {
+        let (aliases, mut links) = config(&[("morning", "group:work"), ("gone", "group:nope"), ("empty", "group:empty")]);
+        links.links.push(Link::new("mail", "https://mail.example"));
+        let mut groups = GroupConfig::default();
+        groups.groups.insert("work".to_string(), vec!["dashboard".to_string(), "mail".to_string()]);
+        groups.groups.insert("empty".to_string(), vec![]);
+
+        assert_eq!(resolve_target("morning", &aliases, &links, &groups).unwrap(), ["https://dash.example", "https://mail.example"]);
+        assert_eq!(resolve_target("group:work", &aliases, &links, &groups).unwrap().len(), 2);
+        assert!(matches!(resolve_target("gone", &aliases, &links, &groups), Err(QuickTabsError::NotFound(_))));
+        assert!(resolve_target("empty", &aliases, &links, &groups).is_err());
+    }
+
+    #[test]
+    fn resolve_all_expands_group_aliases() {
+        let (aliases, links) = config(&[("morning", "group:work"), ("gone", "group:nope")]);
+        let mut groups = GroupConfig::default();
+        groups.groups.insert("work".to_string(), vec!["dashboard".to_string()]);
+
+        let resolutions = resolve_all(&aliases, &links, &groups);
+        let morning = resolutions.iter().find(|r| r.tag == "morning").unwrap();
+        assert_eq!(morning.resolved.as_deref(), Some("https://dash.example"));
+        assert!(morning.error.is_none());
+        let gone = resolutions.iter().find(|r| r.tag == "gone").unwrap();
+        assert!(gone.resolved.is_none());
+        assert_eq!(gone.error.as_deref(), Some("Group 'nope' not found"));
+    }
+
+    #[test]
+    fn aliases_lead_to_the_link_they_open() {
+        let (aliases, links) = config(&[("work", "board"), ("board", "dashboard"), ("web", "https://example.com"), ("loop", "loop")]);
+        assert_eq!(target_link("work", &aliases, &links).map(|l| l.tag.as_str()), Some("dashboard"));
+        assert_eq!(target_link("dashboard", &aliases, &links).map(|l| l.tag.as_str()), Some("dashboard"));
+        assert!(target_link("web", &aliases, &links).is_none());
+        assert!(target_link("loop", &aliases, &links).is_none());
+    }
+
+    #[test]
+    fn aliases_win_over_links_with_the_same_tag() {
+        let (aliases, links) = config(&[("dashboard", "https://other.example")]);
+        assert_eq!(resolve_target("dashboard", &aliases, &links, &GroupConfig::default()).unwrap(), ["https://other.example"]);
+    }
+
+    #[test]
+    fn bangs_fill_the_search_template() {
+        let searches = IndexMap::from([("g".to_string(), "https://www.google.com/search?q={}".to_string())]);
+        let words = ["traits".to_string()];
+        assert_eq!(expand_search("g rust", &words, &searches).unwrap(), "https://www.google.com/search?q=rust%20traits");
+        assert_eq!(expand_search("g", &[], &searches).unwrap(), "https://www.google.com/search?q=");
+        assert!(matches!(expand_search("x rust", &[], &searches), Err(QuickTabsError::NotFound(_))));
+    }
+
+    #[test]
+    fn alias_cycles_are_errors() {
+        let (aliases, links) = config(&[("me", "me")]);
+        let err = resolve_target("me", &aliases, &links, &GroupConfig::default()).unwrap_err();
+        assert_eq!(err.to_string(), "Alias cycle: me -> me");
+
+        let (aliases, links) = config(&[("a", "b"), ("b", "a")]);
+        assert!(resolve_target("a", &aliases, &links, &GroupConfig::default()).is_err());
+    }
+
+    #[test]
+    fn typos_suggest_the_closest_tags() {
+        let (aliases, links) = config(&[("dash", "dashboard"), ("docs", "https://docs.example"), ("mail", "https://mail.example")]);
+        assert!(is_saved_tag("dash", &aliases, &links));
+        assert!(is_saved_tag("dashboard", &aliases, &links));
+        assert!(!is_saved_tag("dashbord", &aliases, &links));
+
+        assert_eq!(suggest_tags("dashbord", &aliases, &links), ["dashboard"]);
+        assert_eq!(suggest_tags("Doc", &aliases, &links), ["docs"]);
+        assert_eq!(suggest_tags("dashb", &aliases, &links), ["dash", "dashboard"]);
+        assert!(suggest_tags("calendar", &aliases, &links).is_empty());
+    }
+}
@@ -0,0 +1,155 @@
+// src/commands/schema.rs
+use std::borrow::Cow;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::commands::config::report_corrupt;
+use crate::commands::error::QuickTabsError;
+use crate::esay;
+
+/// Upgrades a file from the version at its index to the next one. `key` names the
+/// top-level field holding the entries (`links`, `aliases` or `groups`).
+type Migration = fn(data: &str, key: &str) -> Result<String, serde_json::Error>;
+
+/// One step per schema version, oldest first.
+const MIGRATIONS: &[Migration] = &[v0_to_v1];
+
+/// The schema version this build writes into `links.json`, `aliases.json` and
+/// `groups.json` as a top-level `"version"`.
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Why a config file could not be read.
+#[derive(Debug)]
+pub enum SchemaError {
+    /// The file is not valid JSON for its schema
+    Parse(serde_json::Error),
+    /// The file was written by a newer build, with this schema version
+    Unsupported(u32),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Parse(e) => write!(f, "{}", e),
+            SchemaError::Unsupported(version) => write!(
+                f,
+                "saved by a newer Quick Tabs (schema version {}; this build reads up to {}). Upgrade Quick Tabs to use it; the file was left untouched",
+                version, SCHEMA_VERSION
+            ),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Versioned<'a, T> {
+    version: u32,
+    #[serde(flatten)]
+    config: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    version: Option<u32>,
+}
+
+/// Pretty-printed `config` with `"version": SCHEMA_VERSION` as its first field.
+pub fn to_json<T: Serialize>(config: &T) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&Versioned { version: SCHEMA_VERSION, config })
+}
+
+/// Parses a config file of any schema version up to `SCHEMA_VERSION`, upgrading older
+/// ones on the way; files without a `"version"` are version 0. The file itself is only
+/// rewritten, at the current version, by the next save.
+pub fn from_json<T: DeserializeOwned>(data: &str, key: &str) -> Result<T, SchemaError> {
+    // Anything but an object with a numeric version (a bare array, say) predates versions
+    let version = serde_json::from_str::<Header>(data).ok().and_then(|h| h.version).unwrap_or(0);
+    if version > SCHEMA_VERSION {
+        return Err(SchemaError::Unsupported(version));
+    }
+    let mut data = Cow::Borrowed(data);
+    for migrate in &MIGRATIONS[version as usize..] {
+        data = Cow::Owned(migrate(&data, key).map_err(SchemaError::Parse)?);
+    }
+    serde_json::from_str(&data).map_err(SchemaError::Parse)
+}
+
+/// Loads the `kind` config (`link`, `alias`, `group`) whose entries sit under `key`.
+/// A missing file gives `empty()`, as does one that cannot be read (with a warning) or
+/// does not parse (moved aside by `report_corrupt`). A file from a newer schema is an
+/// error, so it is never replaced by an empty config.
+pub fn load_config<T: DeserializeOwned>(path: &Path, key: &str, kind: &str, empty: impl FnOnce() -> T) -> Result<T, QuickTabsError> {
+    if !path.exists() {
+        return Ok(empty());
+    }
+    let data = match fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(e) => {
+            esay!("⚠️ Failed to read {} config {}: {}", kind, path.display(), e);
+            return Ok(empty());
+        }
+    };
+    match from_json(&data, key) {
+        Ok(config) => Ok(config),
+        Err(SchemaError::Parse(e)) => {
+            report_corrupt(kind, path, &e);
+            Ok(empty())
+        }
+        Err(e) => Err(QuickTabsError::Other(format!("{} was {}", path.display(), e))),
+    }
+}
+
+/// Version 0 is every file written before versions existed. Most already hold
+/// `{"<key>": ...}`; the earliest were the bare list or map, which gets wrapped.
+/// Wrapping the text rather than a parsed `Value` keeps the entries in saved order.
+fn v0_to_v1(data: &str, key: &str) -> Result<String, serde_json::Error> {
+    let value: Value = serde_json::from_str(data)?;
+    let wrapped = value.as_object()
+        .and_then(|object| object.get(key))
+        .is_some_and(|entries| entries.is_array() || entries.is_object());
+    Ok(if wrapped {
+        data.to_string()
+    } else {
+        format!("{{\"{}\": {}}}", key, data.trim())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::aliases::AliasConfig;
+    use crate::commands::links::LinkConfig;
+
+    #[test]
+    fn unversioned_files_are_read_as_version_0() {
+        let wrapped = r#"{"links": [{"tag": "gh", "url": "https://github.com"}, {"tag": "m", "urls": ["https://a.com", "https://b.com"]}]}"#;
+        let links: LinkConfig = from_json(wrapped, "links").unwrap();
+        assert_eq!(links.links.len(), 2);
+        assert_eq!(links.links[1].urls, ["https://a.com", "https://b.com"]);
+
+        let bare = r#"[{"tag": "gh", "url": "https://github.com"}]"#;
+        let links: LinkConfig = from_json(bare, "links").unwrap();
+        assert_eq!(links.get_urls("gh"), Some(vec!["https://github.com".to_string()]));
+
+        // A bare alias map, even one with an alias called "version", keeps its order
+        let bare = r#"{"z": "https://z.example", "version": "https://v.example", "a": "gh"}"#;
+        let aliases: AliasConfig = from_json(bare, "aliases").unwrap();
+        let tags: Vec<&str> = aliases.aliases.keys().map(String::as_str).collect();
+        assert_eq!(tags, ["z", "version", "a"]);
+    }
+
+    #[test]
+    fn saved_files_carry_the_version_and_newer_ones_are_refused() {
+        let links = LinkConfig { links: vec![crate::commands::links::Link::new("gh", "https://github.com")] };
+        let json = to_json(&links).unwrap();
+        assert!(json.starts_with("{\n  \"version\": 1,\n  \"links\": ["), "{}", json);
+        let loaded: LinkConfig = from_json(&json, "links").unwrap();
+        assert_eq!(loaded.links, links.links);
+
+        let future = r#"{"version": 99, "links": []}"#;
+        assert!(matches!(from_json::<LinkConfig>(future, "links"), Err(SchemaError::Unsupported(99))));
+        assert!(matches!(from_json::<LinkConfig>("{", "links"), Err(SchemaError::Parse(_))));
+    }
+}
@@ -0,0 +1,49 @@
+// src/commands/snapshot.rs
+
+/// DevTools port used when none is given; matches `--remote-debugging-port=9222`.
+pub const DEFAULT_DEVTOOLS_PORT: u16 = 9222;
+
+/// Reads the URLs of all open tabs from a Chromium browser's DevTools `/json`
+/// endpoint. Only http(s) pages are kept, duplicates (compared with `comparable_url`)
+/// are dropped and tab order is preserved.
+#[cfg(feature = "devtools")]
+pub fn capture_tabs(port: u16) -> Result<Vec<String>, String> {
+    use serde_json::Value;
+
+    let endpoint = format!("http://127.0.0.1:{}/json", port);
+    let targets: Value = ureq::get(&endpoint)
+        .timeout(std::time::Duration::from_secs(3))
+        .call()
+        .map_err(|e| unreachable_help(&e.to_string(), port))?
+        .into_json()
+        .map_err(|e| format!("Unexpected response from {}: {}", endpoint, e))?;
+
+    let mut seen = std::collections::HashSet::new();
+    Ok(targets.as_array()
+        .map(|targets| {
+            targets.iter()
+                .filter(|t| t.get("type").and_then(Value::as_str) == Some("page"))
+                .filter_map(|t| t.get("url").and_then(Value::as_str))
+                .filter(|url| url.starts_with("http://") || url.starts_with("https://"))
+                .filter(|url| seen.insert(crate::commands::url::comparable_url(url)))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+#[cfg(not(feature = "devtools"))]
+pub fn capture_tabs(_port: u16) -> Result<Vec<String>, String> {
+    Err("Snapshots read tabs over the DevTools protocol and require building with `--features devtools`.".to_string())
+}
+
+#[cfg(feature = "devtools")]
+fn unreachable_help(error: &str, port: u16) -> String {
+    format!(
+        "Could not reach DevTools: {}\n\
+         Start the browser with remote debugging enabled, after closing its other windows:\n\
+         \x20   google-chrome --remote-debugging-port={}\n\
+         Then run the snapshot again (pass --port if you used a different port).",
+        error, port
+    )
+}
@@ -0,0 +1,108 @@
+// src/commands/stats.rs
+use std::collections::BTreeSet;
+use crate::commands::aliases::AliasConfig;
+use crate::commands::detect::Browser;
+use crate::commands::links::LinkConfig;
+use crate::commands::time::format_timestamp;
+use crate::commands::url::url_host;
+use crate::say;
+
+/// Aggregates over the saved links and aliases, for `quick_tabs stats`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Stats {
+    pub links: usize,
+    pub aliases: usize,
+    /// Distinct hosts across link URLs and aliases that point at a URL
+    pub domains: usize,
+    /// Tags of links and aliases whose URL has no host to count
+    pub unparsed: Vec<String>,
+    /// The most recently saved or changed link and when
+    pub newest: Option<(String, u64)>,
+}
+
+impl Stats {
+    pub fn collect(links: &LinkConfig, aliases: &AliasConfig) -> Self {
+        let mut domains = BTreeSet::new();
+        let mut unparsed = vec![];
+        // Aliases may also point at another tag; only URLs have a domain
+        let urls = links.links.iter().flat_map(|l| l.urls.iter().map(move |url| (&l.tag, url)))
+            .chain(aliases.aliases.iter().filter(|(_, target)| target.contains("://")));
+        for (tag, url) in urls {
+            match domain(url) {
+                Some(d) => { domains.insert(d); }
+                None => unparsed.push(tag.clone()),
+            }
+        }
+
+        Stats {
+            links: links.links.len(),
+            aliases: aliases.aliases.len(),
+            domains: domains.len(),
+            unparsed,
+            newest: links.links.iter()
+                .filter_map(|l| Some((l.tag.clone(), l.modified?)))
+                .max_by_key(|(_, t)| *t),
+        }
+    }
+
+    pub fn print(&self, browser: Option<&Browser>) {
+        say!("📚 Links: {}", self.links);
+        say!("📚 Aliases: {}", self.aliases);
+        say!("🌐 Unique domains: {}", self.domains);
+        if !self.unparsed.is_empty() {
+            say!("⚠️ No host in {} URL(s): {}", self.unparsed.len(), self.unparsed.join(", "));
+        }
+        match &self.newest {
+            Some((tag, at)) => say!("✨ Most recently added: {} ({})", tag, format_timestamp(*at)),
+            None => say!("✨ Most recently added: unknown"),
+        }
+        match browser {
+            Some(b) => say!("🚀 Preferred browser: {} ({})", b.name, b.path.display()),
+            None => say!("🚀 Preferred browser: none saved; run `quick_tabs detect`"),
+        }
+    }
+}
+
+/// The lowercased host of a URL without a leading `www.`, or `None` when there is
+/// no `scheme://host` to read one from (`mailto:`, `file:///`, typos, ...).
+fn domain(url: &str) -> Option<String> {
+    if !url.contains("://") {
+        return None;
+    }
+    let host = url_host(url).trim_end_matches('.').to_lowercase();
+    let host = host.strip_prefix("www.").map(str::to_string).unwrap_or(host);
+    (!host.is_empty()).then_some(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indexmap::IndexMap;
+    use crate::commands::links::Link;
+
+    fn link(tag: &str, url: &str, modified: Option<u64>) -> Link {
+        Link { modified, ..Link::new(tag, url) }
+    }
+
+    #[test]
+    fn counts_domains_and_skips_malformed_urls() {
+        let links = LinkConfig { links: vec![
+            link("a", "https://www.Example.com/a", Some(10)),
+            link("b", "http://example.com:8080/b?x=1", Some(30)),
+            link("c", "https://docs.rs", None),
+            link("d", "example.org/no-scheme", Some(20)),
+            link("e", "file:///tmp/notes.html", None),
+        ] };
+        let aliases = AliasConfig { aliases: IndexMap::from([
+            ("x".to_string(), "https://user@crates.io/".to_string()),
+            ("y".to_string(), "a".to_string()),
+        ]) };
+
+        let stats = Stats::collect(&links, &aliases);
+        assert_eq!(stats.links, 5);
+        assert_eq!(stats.aliases, 2);
+        assert_eq!(stats.domains, 3);
+        assert_eq!(stats.unparsed, ["d", "e"]);
+        assert_eq!(stats.newest, Some(("b".to_string(), 30)));
+    }
+}
@@ -0,0 +1,98 @@
+// src/commands/time.rs
+use std::thread;
+use std::time::Duration;
+use chrono::{Local, NaiveDateTime, NaiveTime, TimeZone};
+use crate::say;
+
+/// Parses a human duration such as `500ms`, `30s`, `5m`, `1h` or `7d`.
+/// A bare number is read as seconds.
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let s = input.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+
+    let value: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration '{}' (expected e.g. 30s, 5m, 500ms)", input))?;
+
+    match unit.trim() {
+        "" | "s" => Ok(Duration::from_secs(value)),
+        "ms" => Ok(Duration::from_millis(value)),
+        "m" => Ok(Duration::from_secs(value * 60)),
+        "h" => Ok(Duration::from_secs(value * 60 * 60)),
+        "d" => Ok(Duration::from_secs(value * 60 * 60 * 24)),
+        other => Err(format!("unknown duration unit '{}' (use ms, s, m, h or d)", other)),
+    }
+}
+
+/// Formats Unix seconds as local `YYYY-MM-DD HH:MM`.
+pub fn format_timestamp(secs: u64) -> String {
+    Local.timestamp_opt(secs as i64, 0)
+        .single()
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| secs.to_string())
+}
+
+/// Parses a 24-hour wall-clock time such as `09:30` or `17:05`.
+pub fn parse_clock_time(input: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(input.trim(), "%H:%M")
+        .map_err(|_| format!("invalid time '{}' (expected HH:MM, e.g. 09:30)", input))
+}
+
+/// The next time the clock reads `at` after `now`: later today, or tomorrow when
+/// `at` has already passed (or is right now).
+pub fn next_occurrence(now: NaiveDateTime, at: NaiveTime) -> NaiveDateTime {
+    let today = now.date().and_time(at);
+    if today > now {
+        today
+    } else {
+        today + chrono::Duration::days(1)
+    }
+}
+
+/// Formats a wait as `2h 05m`, `4m 30s` or `12s`.
+fn format_wait(wait: Duration) -> String {
+    let secs = wait.as_secs();
+    match (secs / 3600, secs % 3600 / 60, secs % 60) {
+        (0, 0, s) => format!("{}s", s),
+        (0, m, s) => format!("{}m {:02}s", m, s),
+        (h, m, _) => format!("{}h {:02}m", h, m),
+    }
+}
+
+/// Blocks until the local clock next reads `at` (see `next_occurrence`), after
+/// printing when that is. The clock is re-read every minute, so a suspended laptop
+/// still wakes up on time. Ctrl-C simply ends the process before anything opens.
+pub fn sleep_until(at: NaiveTime) {
+    let target = next_occurrence(Local::now().naive_local(), at);
+    let remaining = || (target - Local::now().naive_local()).to_std().unwrap_or_default();
+    say!("⏰ Opening at {} (in {}); press Ctrl-C to cancel.", target.format("%a %H:%M"), format_wait(remaining()));
+    loop {
+        let left = remaining();
+        if left.is_zero() {
+            break;
+        }
+        thread::sleep(left.min(Duration::from_secs(60)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn times_already_passed_roll_over_to_tomorrow() {
+        let at = parse_clock_time("09:30").unwrap();
+        let day = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let early = day.and_hms_opt(8, 0, 0).unwrap();
+        assert_eq!(next_occurrence(early, at), day.and_hms_opt(9, 30, 0).unwrap());
+        let late = day.and_hms_opt(9, 30, 0).unwrap();
+        assert_eq!(next_occurrence(late, at), NaiveDate::from_ymd_opt(2025, 1, 1).unwrap().and_hms_opt(9, 30, 0).unwrap());
+
+        assert!(parse_clock_time("25:00").is_err());
+        assert!(parse_clock_time("9").is_err());
+        assert_eq!(format_wait(Duration::from_secs(2 * 3600 + 5 * 60 + 7)), "2h 05m");
+        assert_eq!(format_wait(Duration::from_secs(270)), "4m 30s");
+    }
+}
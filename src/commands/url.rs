@@ -0,0 +1,308 @@
+// src/commands/url.rs
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, TimeZone};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+/// Format used by a bare `{date}` token.
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Placeholder in a saved URL that `launch <tag> <query...>` fills in.
+pub const QUERY_PLACEHOLDER: &str = "{}";
+
+/// Everything but unreserved characters (RFC 3986) is escaped in a query.
+const QUERY_ESCAPES: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.').remove(b'~');
+
+/// Expands the launch-time tokens in a stored URL:
+/// - `{date}`: the current date as `YYYY-MM-DD`
+/// - `{date:<fmt>}`: the current time formatted with a strftime-style `<fmt>`
+///   (e.g. `{date:%Y/%m}`)
+///
+/// Any other `{...}` text is left untouched, so other placeholders can be filled in
+/// by later steps. Fails on an unterminated `{date` token or an invalid format.
+pub fn render_template<Tz: TimeZone>(url: &str, now: &DateTime<Tz>) -> Result<String, String>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let mut out = String::with_capacity(url.len());
+    let mut rest = url;
+
+    while let Some(start) = rest.find("{date") {
+        let after = &rest[start + "{date".len()..];
+        let format = match after.chars().next() {
+            Some('}') => Some(DEFAULT_DATE_FORMAT),
+            Some(':') => None,
+            // e.g. `{dates}`: not our token
+            _ => {
+                out.push_str(&rest[..start + "{date".len()]);
+                rest = after;
+                continue;
+            }
+        };
+
+        let end = after.find('}')
+            .ok_or_else(|| format!("unterminated date token in '{}' (expected `{{date:<format>}}`)", url))?;
+        let format = match format {
+            Some(default) => default,
+            None => &after[1..end],
+        };
+        out.push_str(&rest[..start]);
+        out.push_str(&format_date(now, format)?);
+        rest = &after[end + 1..];
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Expands a leading `~` and `$VAR`/`${VAR}` references in a stored value, e.g.
+/// `file://$HOME/notes.html`. Done at launch time only, so saved values stay portable
+/// across machines. `http(s)://` URLs are returned unchanged. Fails on an unset variable.
+pub fn expand_env(value: &str) -> Result<String, String> {
+    if value.starts_with("http://") || value.starts_with("https://") {
+        return Ok(value.to_string());
+    }
+    shellexpand::full(value)
+        .map(|expanded| expanded.into_owned())
+        .map_err(|e| format!("environment variable ${} is not set", e.var_name))
+}
+
+/// Substitutes the words after `launch <tag>` into every `{}` in `url`, joined with
+/// spaces and percent-encoded (`rust lifetimes` becomes `rust%20lifetimes`). Without
+/// words the URL is returned unchanged; words for a URL without `{}` are an error,
+/// so a mistyped tag doesn't silently open the bare URL.
+pub fn fill_query(url: &str, words: &[String]) -> Result<String, String> {
+    if words.is_empty() {
+        return Ok(url.to_string());
+    }
+    if !url.contains(QUERY_PLACEHOLDER) {
+        return Err(format!("'{}' has no {} placeholder for the query '{}'", url, QUERY_PLACEHOLDER, words.join(" ")));
+    }
+    let query = utf8_percent_encode(&words.join(" "), QUERY_ESCAPES).to_string();
+    Ok(url.replace(QUERY_PLACEHOLDER, &query))
+}
+
+/// Normalizes a URL typed on the command line before it is saved: a value without a
+/// scheme (`github.com/rust-lang`) gets `https://`, so browsers don't take it for a
+/// file name. Values with a scheme (`chrome://settings`, `mailto:`) and values that
+/// start with a `$VAR` or `{token}` placeholder are kept as they are.
+///
+/// Fails when the value has no scheme and does not start with a plausible host.
+pub fn normalize_url(input: &str) -> Result<String, String> {
+    let value = input.trim();
+    if has_scheme(value) || value.starts_with('$') || value.starts_with('{') {
+        return Ok(value.to_string());
+    }
+
+    let host = value.split(['/', '?', '#']).next().unwrap_or_default();
+    let name = host.rsplit_once(':').map_or(host, |(name, _)| name);
+    let valid_chars = !host.is_empty()
+        && host.chars().all(|c| c.is_ascii_alphanumeric() || "-.:_[]".contains(c))
+        && !value.chars().any(char::is_whitespace);
+    if valid_chars && (name.contains('.') || name == "localhost" || name.starts_with('[')) {
+        Ok(format!("https://{}", value))
+    } else {
+        Err(format!("'{}' does not look like a URL (add a scheme such as https://, or pass --raw to save it as-is)", input))
+    }
+}
+
+/// Extracts the host from a URL without a full parser: drops the scheme, userinfo,
+/// port and everything from the first `/`, `?` or `#`.
+pub fn url_host(url: &str) -> &str {
+    let rest = url.split_once("://").map(|(_, r)| r).unwrap_or(url);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit_once('@').map(|(_, h)| h).unwrap_or(authority);
+    host.split(':').next().unwrap_or_default()
+}
+
+/// The form of `url` used to spot the same page saved twice: the scheme and host are
+/// lowercased and trailing slashes dropped from the path, so `HTTPS://GitHub.com/` and
+/// `https://github.com` compare equal. The path, query and fragment keep their case,
+/// and slashes in the query or fragment (`?next=/`, `#/`) are kept.
+pub fn comparable_url(url: &str) -> String {
+    let url = url.trim();
+    let (scheme, rest) = match url.split_once("://") {
+        Some((scheme, rest)) => (format!("{}://", scheme.to_ascii_lowercase()), rest),
+        None => (String::new(), url),
+    };
+    let authority_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (authority, tail) = rest.split_at(authority_end);
+    let path_end = tail.find(['?', '#']).unwrap_or(tail.len());
+    let (path, suffix) = tail.split_at(path_end);
+    format!("{}{}{}{}", scheme, authority.to_lowercase(), path.trim_end_matches('/'), suffix)
+}
+
+/// Whether `value` starts with `scheme:`. `localhost:3000` is a host and port, not a scheme.
+fn has_scheme(value: &str) -> bool {
+    let Some((scheme, rest)) = value.split_once(':') else {
+        return false;
+    };
+    let is_port = rest.split(['/', '?', '#']).next().is_some_and(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()));
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        && !is_port
+}
+
+/// Substitutes `{name}` tokens with the matching value from `fields`. `{{` and `}}`
+/// stand for literal braces. Fails on a token not in `fields` or an unmatched brace,
+/// so typos are reported instead of printed.
+pub fn render_tokens(template: &str, fields: &[(&str, String)]) -> Result<String, String> {
+    let mut out = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                out.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                out.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("unterminated token '{{{}' in template", name)),
+                    }
+                }
+                let value = fields.iter()
+                    .find(|(field, _)| *field == name)
+                    .map(|(_, value)| value)
+                    .ok_or_else(|| {
+                        let known: Vec<String> = fields.iter().map(|(f, _)| format!("{{{}}}", f)).collect();
+                        format!("unknown token '{{{}}}' in template (known: {})", name, known.join(", "))
+                    })?;
+                out.push_str(value);
+            }
+            '}' => return Err(format!("unmatched '}}' in template '{}' (write `}}}}` for a literal brace)", template)),
+            c => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+fn format_date<Tz: TimeZone>(now: &DateTime<Tz>, format: &str) -> Result<String, String>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let items: Vec<Item> = StrftimeItems::new(format).collect();
+    if format.is_empty() || items.iter().any(|i| matches!(i, Item::Error)) {
+        return Err(format!("invalid date format '{}' (use strftime specifiers such as %Y-%m-%d)", format));
+    }
+    Ok(now.format_with_items(items.into_iter()).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::env;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 5, 14, 7, 9).unwrap()
+    }
+
+    #[test]
+    fn comparable_urls_ignore_host_case_and_trailing_slashes() {
+        assert_eq!(comparable_url("HTTPS://GitHub.com/"), "https://github.com");
+        assert_eq!(comparable_url("https://github.com//"), comparable_url("https://github.com"));
+        assert_eq!(comparable_url("github.com/Rust-Lang/"), "github.com/Rust-Lang");
+        assert_eq!(comparable_url("https://Docs.rs/serde?Q=A"), "https://docs.rs/serde?Q=A");
+        assert_ne!(comparable_url("https://a.com/Path"), comparable_url("https://a.com/path"));
+        assert_ne!(comparable_url("http://a.com"), comparable_url("https://a.com"));
+        // Only the path loses its trailing slashes
+        assert_eq!(comparable_url("https://a.com/docs/?q=1"), "https://a.com/docs?q=1");
+        assert_ne!(comparable_url("https://app.example/#/"), comparable_url("https://app.example/#"));
+        assert_ne!(comparable_url("https://a.com/login?next=/"), comparable_url("https://a.com/login?next="));
+    }
+
+    #[test]
+    fn date_uses_default_format() {
+        let url = render_template("https://reports.example/{date}/daily", &now()).unwrap();
+        assert_eq!(url, "https://reports.example/2024-03-05/daily");
+    }
+
+    #[test]
+    fn date_accepts_custom_formats() {
+        assert_eq!(render_template("{date:%Y/%m}", &now()).unwrap(), "2024/03");
+        assert_eq!(render_template("?d={date:%d.%m.%y}&t={date:%H%M}", &now()).unwrap(), "?d=05.03.24&t=1407");
+        assert_eq!(render_template("w{date:%V}", &now()).unwrap(), "w10");
+    }
+
+    #[test]
+    fn other_placeholders_are_left_alone() {
+        let url = render_template("https://x.example/{}?q={query}&day={date}", &now()).unwrap();
+        assert_eq!(url, "https://x.example/{}?q={query}&day=2024-03-05");
+    }
+
+    #[test]
+    fn tokens_are_substituted_and_braces_escaped() {
+        let fields = [("tag", "docs".to_string()), ("url", "https://docs.rs".to_string()), ("note", String::new())];
+        assert_eq!(render_tokens("{tag}\t{url}", &fields).unwrap(), "docs\thttps://docs.rs");
+        assert_eq!(render_tokens("[{tag}] {note}|", &fields).unwrap(), "[docs] |");
+        assert_eq!(render_tokens("{{{tag}}} {{url}}", &fields).unwrap(), "{docs} {url}");
+        assert_eq!(render_tokens("no tokens", &fields).unwrap(), "no tokens");
+    }
+
+    #[test]
+    fn bad_tokens_error() {
+        let fields = [("tag", "docs".to_string())];
+        let err = render_tokens("{tga}", &fields).unwrap_err();
+        assert!(err.contains("{tga}") && err.contains("{tag}"), "{}", err);
+        assert!(render_tokens("{tag", &fields).is_err());
+        assert!(render_tokens("tag}", &fields).is_err());
+    }
+
+    #[test]
+    fn urls_without_a_scheme_get_https() {
+        assert_eq!(normalize_url("github.com").unwrap(), "https://github.com");
+        assert_eq!(normalize_url(" github.com/rust-lang?tab=repos ").unwrap(), "https://github.com/rust-lang?tab=repos");
+        assert_eq!(normalize_url("localhost:3000/admin").unwrap(), "https://localhost:3000/admin");
+        assert_eq!(normalize_url("192.168.1.1:8080").unwrap(), "https://192.168.1.1:8080");
+    }
+
+    #[test]
+    fn urls_with_a_scheme_or_placeholder_are_kept() {
+        assert_eq!(normalize_url("http://example.com").unwrap(), "http://example.com");
+        assert_eq!(normalize_url("chrome://settings").unwrap(), "chrome://settings");
+        assert_eq!(normalize_url("mailto:me@example.com").unwrap(), "mailto:me@example.com");
+        assert_eq!(normalize_url("${JIRA_URL}/browse").unwrap(), "${JIRA_URL}/browse");
+    }
+
+    #[test]
+    fn non_urls_are_rejected() {
+        assert!(normalize_url("github").is_err());
+        assert!(normalize_url("my notes.txt").is_err());
+        assert!(normalize_url("").is_err());
+    }
+
+    #[test]
+    fn queries_are_encoded_into_the_placeholder() {
+        let words = ["rust".to_string(), "lifetimes & co?".to_string()];
+        assert_eq!(
+            fill_query("https://google.com/search?q={}", &words).unwrap(),
+            "https://google.com/search?q=rust%20lifetimes%20%26%20co%3F"
+        );
+        assert_eq!(fill_query("https://a.example/{}", &[]).unwrap(), "https://a.example/{}");
+        assert!(fill_query("https://a.example", &words).is_err());
+    }
+
+    #[test]
+    fn invalid_formats_error() {
+        assert!(render_template("{date:%Q}", &now()).is_err());
+        assert!(render_template("{date:%Y", &now()).is_err());
+    }
+
+    #[test]
+    fn env_refs_expand_outside_web_urls() {
+        env::set_var("QT_TEST_NOTES", "/srv/notes");
+        assert_eq!(expand_env("file://$QT_TEST_NOTES/a.html").unwrap(), "file:///srv/notes/a.html");
+        assert_eq!(expand_env("file://${QT_TEST_NOTES}/b.html").unwrap(), "file:///srv/notes/b.html");
+        assert_eq!(expand_env("https://x.example/?q=$QT_TEST_NOTES").unwrap(), "https://x.example/?q=$QT_TEST_NOTES");
+        assert!(expand_env("file://$QT_TEST_UNSET_VAR/x").unwrap_err().contains("QT_TEST_UNSET_VAR"));
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(expand_env("~/notes").unwrap(), format!("{}/notes", home.display()));
+    }
+}
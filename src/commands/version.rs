@@ -0,0 +1,86 @@
+// src/commands/version.rs
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A dotted version number such as `120.0.6099.109`, compared numerically
+/// component by component (`1.10 > 1.9`, `1.2 == 1.2.0`).
+#[derive(Debug, Clone, Eq)]
+pub struct Version(Vec<u64>);
+
+impl Version {
+    /// Extracts the first version-looking token from `--version` output, e.g.
+    /// `Google Chrome 120.0.6099.109 unknown` or `Mozilla Firefox 121.0b3`.
+    /// Trailing non-numeric suffixes (`b3`, `esr`) are ignored.
+    pub fn parse(text: &str) -> Option<Version> {
+        text.split(|c: char| c.is_whitespace() || c == ',' || c == '(' || c == ')')
+            .map(|t| t.trim_start_matches(['v', 'V']))
+            .filter(|t| t.starts_with(|c: char| c.is_ascii_digit()))
+            .find_map(|token| {
+                let mut parts = vec![];
+                for piece in token.split('.') {
+                    let digits: String = piece.chars().take_while(|c| c.is_ascii_digit()).collect();
+                    match digits.parse() {
+                        Ok(n) => parts.push(n),
+                        Err(_) => break,
+                    }
+                    if digits.len() != piece.len() {
+                        break; // `0b3`: keep the 0, stop at the suffix
+                    }
+                }
+                (!parts.is_empty()).then_some(Version(parts))
+            })
+    }
+
+    fn component(&self, i: usize) -> u64 {
+        self.0.get(i).copied().unwrap_or(0)
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.0.len().max(other.0.len());
+        (0..len)
+            .map(|i| self.component(i).cmp(&other.component(i)))
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.0.iter().map(|n| n.to_string()).collect();
+        write!(f, "{}", parts.join("."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_browser_version_output() {
+        assert_eq!(Version::parse("Google Chrome 120.0.6099.109 ").unwrap().to_string(), "120.0.6099.109");
+        assert_eq!(Version::parse("Mozilla Firefox 121.0b3").unwrap().to_string(), "121.0");
+        assert!(Version::parse("unknown").is_none());
+    }
+
+    #[test]
+    fn compares_numerically() {
+        let v = |s: &str| Version::parse(s).unwrap();
+        assert!(v("1.10") > v("1.9"));
+        assert_eq!(v("1.2"), v("1.2.0"));
+        assert!(v("120.0.6099.109") > v("119.0.6045.199"));
+    }
+}
@@ -0,0 +1,31 @@
+//! Quick Tabs as a library: saved links, aliases and groups, browser detection and
+//! launching, as used by the `quick_tabs` binary.
+//!
+//! Every command module is re-exported at the crate root, so integrators can write:
+//!
+//! ```no_run
+//! use quick_tabs::{config, detect, links};
+//!
+//! let app_cfg = config::AppConfig::load_or_default();
+//! let links = links::LinkConfig::load(&config::config_paths()?.links)?;
+//! if let (Some(browser), Some(urls)) = (detect::run(&detect::DetectOptions::from_config(&app_cfg)), links.get_urls("docs")) {
+//!     let urls: Vec<&str> = urls.iter().map(String::as_str).collect();
+//!     links::launch_urls_simultaneously(&browser, &urls, &links::LaunchOptions::default())?;
+//! }
+//! # Ok::<(), quick_tabs::QuickTabsError>(())
+//! ```
+//!
+//! Browser discovery lives in [`detect`] (`detect_browsers`, `find_browser`) and
+//! launching in [`links`] (`launch_link`, `launch_urls_simultaneously`, ...).
+
+pub mod commands;
+
+pub use commands::{
+    aliases, blocklist, bookmarks, bundle, cache, check, clipboard, config, csv, detect, doctor, error,
+    glob, groups, history, hooks, links, listing, merge, output, profile, project, prompt, quote,
+    random, ratelimit, resolve, schema, snapshot, stats, time, url, version,
+};
+
+pub use commands::detect::{detect_all, detect_browsers, find_browser, Browser, DetectOptions};
+pub use commands::error::QuickTabsError;
+pub use commands::links::{launch_link, launch_urls_simultaneously, Link, LinkConfig};
@@ -1,20 +1,250 @@
-mod commands;
+use quick_tabs::links::{browser_family, effective_mode, matches_filter, BatchOptions, Change, ChangeSet, Link, LinkConfig, launch_batch, launch_link, launch_link_for, launch_urls_in_new_window, launch_with_system_opener, launch_urls_simultaneously, is_launchable, BrowserFamily, LaunchMode, LaunchOptions};
+use quick_tabs::aliases::AliasConfig;
+use quick_tabs::groups::GroupConfig;
+use quick_tabs::config::{browser_config_path, config_paths, migrate_dir, restore_backup, AppConfig, parse_group_color, settings_path};
+use quick_tabs::detect::{run as detect_and_select, find_browser, list_browsers, list_named_browsers, load_named_browser, probe_report, print_probe_report, read_saved_browser, save_default_browser, set_browser, update_saved_browser, Browser, DetectOptions, ListFormat};
+use quick_tabs::bundle::Bundle;
+use quick_tabs::time::{format_timestamp, parse_clock_time, parse_duration, sleep_until};
+use quick_tabs::bookmarks::import_from_browser;
+use quick_tabs::error::{ErrorFormat, QuickTabsError};
+use quick_tabs::history::{self, ExportFormat, HistoryEntry};
+use quick_tabs::blocklist::{blocklist_path, Blocklist};
+use quick_tabs::quote::{shell_join, shell_quote};
+use quick_tabs::merge::{merge_link, MergeStrategy, Merged};
+use quick_tabs::listing::{list_entries, render_entries, tag_lines};
+use quick_tabs::cache;
+use quick_tabs::schema;
+use quick_tabs::resolve::{expand_search, is_saved_tag, resolve_all, resolve_target, suggest_tags, target_link};
+use quick_tabs::snapshot::{capture_tabs, DEFAULT_DEVTOOLS_PORT};
+use quick_tabs::url::{expand_env, fill_query, normalize_url, render_template, QUERY_PLACEHOLDER};
+use quick_tabs::clipboard::copy_to_clipboard;
+use quick_tabs::prompt::{choose, confirm, confirm_each, is_interactive, Answer};
+use quick_tabs::random::random_seed;
+use quick_tabs::project;
+use quick_tabs::check;
+use quick_tabs::doctor;
+use quick_tabs::stats::Stats;
+use quick_tabs::output::Verbosity;
+use quick_tabs::ratelimit;
+use quick_tabs::{say, esay};
 
-use crate::commands::links::{LinkConfig, launch_link, LaunchMode};
-use crate::commands::aliases::AliasConfig;
-use crate::commands::detect::{run as detect_browsers, Browser};
-
-use std::path::{PathBuf, Path};
+use std::collections::BTreeMap;
 use std::env;
-use clap::{Parser, Subcommand, CommandFactory}; // <-- ADDED CommandFactory
+use std::path::{PathBuf, Path};
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::time::Duration;
+use chrono::{Local, NaiveTime};
+use clap::{Args, Parser, Subcommand, CommandFactory};
+use clap_complete::Shell;
 
 // --- CLI Structure using Clap ---
 
 #[derive(Parser, Debug)]
-#[clap(author, version, about, long_about = None)]
+#[clap(author, version, about, long_about = None, disable_help_subcommand = true)]
 struct Cli {
     #[clap(subcommand)]
     command: Commands,
+    /// How to report errors on stderr (json emits `{ "error": ..., "code": N }`)
+    #[arg(long, global = true, value_enum, default_value_t = ErrorFormat::Text)]
+    error_format: ErrorFormat,
+    /// Print `[ok]`, `[warn]`, `[error]`, ... instead of emoji, without colors (also when NO_COLOR is set)
+    #[arg(long, global = true, alias = "no-emoji")]
+    plain: bool,
+    /// Print errors only
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Also print the paths probed, registry keys read and browser commands run
+    #[arg(short, long, global = true)]
+    verbose: bool,
+    /// Keep all state (links, aliases, groups, browser config, settings, caches) in this directory
+    #[arg(long, global = true, value_name = "PATH")]
+    config_dir: Option<PathBuf>,
+}
+
+/// Flags shared by every command that opens a browser
+#[derive(Args, Debug)]
+struct LaunchArgs {
+    /// Open in incognito/private mode
+    #[arg(short, long)]
+    incognito: bool,
+    /// Open in normal mode, even if the browser defaults to private (browser_default_mode)
+    #[arg(long, conflicts_with = "incognito")]
+    normal: bool,
+    /// Open privately in a fresh throwaway profile, removed once the browser closes
+    #[arg(long, conflicts_with_all = ["incognito", "normal"])]
+    incognito_container: bool,
+    /// In private mode, fail instead of opening a normal window when the browser has no known private flag
+    #[arg(long, conflicts_with = "normal")]
+    strict_private: bool,
+    /// Open each link in a new window of its own instead of as tabs in an existing one
+    #[arg(long)]
+    new_window: bool,
+    /// Open in this Firefox Multi-Account Container (Firefox-family browsers only)
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["incognito", "incognito_container"])]
+    container: Option<String>,
+    /// Skip first-run and default-browser prompts (Chromium family)
+    #[arg(long)]
+    clean_launch: bool,
+    /// Refuse URLs that match the blocklist (see add-block)
+    #[arg(long)]
+    safe_launch: bool,
+    /// Use this browser for this run only: a detected browser's name, a command on PATH or a path
+    #[arg(long, value_name = "NAME_OR_PATH")]
+    browser: Option<String>,
+    /// Use a browser saved with `detect --save-as <name>`
+    #[arg(long, value_name = "NAME", conflicts_with = "browser")]
+    browser_config: Option<String>,
+    /// Limit links opened per minute across runs (overrides max_opens_per_minute)
+    #[arg(long, value_name = "N")]
+    open_limit_per_minute: Option<u32>,
+    /// When the open limit is reached, wait for a free slot instead of failing
+    #[arg(long)]
+    wait_for_slot: bool,
+    /// Print the full browser command and URL count instead of launching anything
+    #[arg(long)]
+    dry_run: bool,
+    /// Don't record this launch in the history
+    #[arg(long)]
+    no_history: bool,
+}
+
+impl LaunchArgs {
+    /// The `--browser` or `--browser-config` override if given, otherwise the
+    /// saved/detected browser. Detection only happens here, so commands that
+    /// launch nothing never scan for browsers.
+    fn browser(&self, app_cfg: &AppConfig) -> Result<Browser, QuickTabsError> {
+        if let Some(input) = &self.browser {
+            return find_browser(input, &DetectOptions::from_config(app_cfg));
+        }
+        if let Some(name) = &self.browser_config {
+            match load_named_browser(name)? {
+                Some(browser) => {
+                    say!("⚡ Using browser config '{}': {}", name, browser.path.display());
+                    return Ok(browser);
+                },
+                None => say!("⚠️ Browser config '{}' not found; using the default browser.", name),
+            }
+        }
+        get_browser_or_exit(detect_and_select(&DetectOptions::from_config(app_cfg)))
+    }
+
+    /// Reserves `count` opens under the per-minute limit, if one is set.
+    fn reserve(&self, app_cfg: &AppConfig, count: usize) -> Result<(), QuickTabsError> {
+        if self.dry_run {
+            // Nothing opens, so no slots are taken
+            return Ok(());
+        }
+        match self.open_limit_per_minute.or(app_cfg.max_opens_per_minute) {
+            Some(limit) => ratelimit::acquire(limit, count.try_into().unwrap_or(u32::MAX), self.wait_for_slot),
+            None => Ok(()),
+        }
+    }
+
+    /// Adds launched URLs to the history, unless `--no-history` or `--dry-run` is
+    /// given. URLs the launch skipped (blocked, or with a bad `{date}` token) are left
+    /// out. The launch already happened, so a history that cannot be written only warns.
+    fn record(&self, launched: impl IntoIterator<Item = HistoryEntry>, options: &LaunchOptions) {
+        if self.no_history || self.dry_run {
+            return;
+        }
+        let launched = launched.into_iter().filter(|e| is_launchable(&e.url, options));
+        if let Err(e) = history::history_path().and_then(|path| Ok(history::record(&path, launched)?)) {
+            esay!("⚠️ Could not record the launch in the history: {}", e);
+        }
+    }
+
+    /// Options for `--system`. The OS opener takes nothing but the URL, so browser
+    /// flags are reported as ignored rather than silently dropped; private mode is an
+    /// error with `--strict-private`.
+    fn system_options(&self, app_cfg: &AppConfig) -> Result<LaunchOptions, QuickTabsError> {
+        if self.strict_private && (self.incognito || self.incognito_container) {
+            return Err(QuickTabsError::Launch(
+                "the system default handler cannot open private windows; refusing to open a normal one (--strict-private)".to_string(),
+            ));
+        }
+        let ignored: Vec<&str> = [
+            (self.incognito, "--incognito"),
+            (self.incognito_container, "--incognito-container"),
+            (self.clean_launch, "--clean-launch"),
+            (self.new_window, "--new-window"),
+            (self.container.is_some(), "--container"),
+        ]
+        .into_iter()
+        .filter_map(|(given, flag)| given.then_some(flag))
+        .collect();
+        if !ignored.is_empty() {
+            say!("⚠️ The system default handler cannot open private windows or take browser flags; ignoring {}.", ignored.join(", "));
+        }
+        Ok(LaunchOptions {
+            mode: LaunchMode::Normal,
+            clean: false,
+            post_launch_hook: app_cfg.post_launch_hook.clone(),
+            blocklist: (self.safe_launch || app_cfg.safe_launch).then(Blocklist::load_or_default),
+            profile: None,
+            new_window: false,
+            firefox_container: None,
+            dry_run: self.dry_run,
+            wait: false,
+            strict_private: false,
+            fallback: None,
+        })
+    }
+
+    fn options(&self, app_cfg: &AppConfig, browser: &Browser) -> LaunchOptions {
+        let explicit = if self.incognito_container {
+            Some(LaunchMode::Container)
+        } else if self.incognito {
+            Some(LaunchMode::Private)
+        } else if self.normal {
+            Some(LaunchMode::Normal)
+        } else {
+            None
+        };
+        let is_firefox = browser_family(&browser.path) == BrowserFamily::Firefox;
+        if self.container.is_some() && !is_firefox {
+            esay!("⚠️ --container only works with Firefox-family browsers; opening {} without one.", browser.name);
+        }
+        LaunchOptions {
+            mode: effective_mode(explicit, browser, &app_cfg.browser_default_mode),
+            clean: self.clean_launch,
+            post_launch_hook: app_cfg.post_launch_hook.clone(),
+            blocklist: (self.safe_launch || app_cfg.safe_launch).then(Blocklist::load_or_default),
+            profile: None,
+            new_window: self.new_window,
+            firefox_container: self.container.clone().filter(|_| is_firefox),
+            dry_run: self.dry_run,
+            wait: false,
+            strict_private: self.strict_private,
+            // A browser named with --browser is used or nothing is
+            fallback: self.browser.is_none().then(|| DetectOptions::from_config(app_cfg)),
+        }
+    }
+}
+
+/// Ordering flags shared by the commands that open many links at once
+#[derive(Args, Debug)]
+struct OrderArgs {
+    /// Open the links in random order
+    #[arg(long)]
+    shuffle: bool,
+    /// Seed for --shuffle, to reproduce an order
+    #[arg(long, requires = "shuffle")]
+    seed: Option<u64>,
+    /// Open the links in reverse saved order, newest first
+    #[arg(long, conflicts_with = "shuffle")]
+    reverse: bool,
+}
+
+impl OrderArgs {
+    fn batch(&self, window_each: bool) -> BatchOptions {
+        let shuffle_seed = self.shuffle.then(|| {
+            let seed = self.seed.unwrap_or_else(random_seed);
+            say!("🔀 Shuffling with seed {} (pass --seed {} to repeat)", seed, seed);
+            seed
+        });
+        BatchOptions { window_each, shuffle_seed, reverse: self.reverse, delay: None, count: None }
+    }
 }
 
 #[derive(Subcommand, Debug)]
@@ -22,131 +252,1564 @@ enum Commands {
     /// Launch a tag or URL in the detected browser
     Launch {
         target: String,
-        /// Open the link in incognito/private mode
-        #[arg(short, long)]
-        incognito: bool,
+        /// Search words for a URL with a `{}` placeholder, e.g. `launch g rust lifetimes`
+        query: Vec<String>,
+        /// Open the target as given, without looking it up as a tag
+        #[arg(long)]
+        literal: bool,
+        #[command(flatten)]
+        launch: LaunchArgs,
+        /// Close the spawned browser after this long (e.g. 10s, 2m). Best-effort:
+        /// single-instance browsers may keep the tab open in their running instance
+        #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+        close_after: Option<Duration>,
+        /// Open the URL twice: once normally and once in a private window
+        #[arg(long, conflicts_with_all = ["incognito", "normal", "incognito_container", "close_after"])]
+        both: bool,
+        /// Block until the browser exits and exit with its status. Returns at once when
+        /// the URL is handed to an already-running browser
+        #[arg(long, conflicts_with_all = ["close_after", "both", "system"])]
+        wait: bool,
+        /// Open with the OS default handler (xdg-open, open, start) instead of a detected browser
+        #[arg(long, conflicts_with_all = ["browser", "browser_config", "close_after", "both"])]
+        system: bool,
+        /// Browser profile to open in: `--profile-directory` for Chromium/Edge, `-P` for Firefox
+        #[arg(long, value_name = "NAME", conflicts_with = "system")]
+        profile: Option<String>,
+        /// Print the resolved URL(s), one per line and nothing else, instead of opening them
+        #[arg(long, conflicts_with_all = ["dry_run", "close_after", "both", "wait", "system"])]
+        print_url: bool,
+    },
+    /// Copy the URL a tag or alias resolves to onto the clipboard instead of opening it
+    Copy {
+        target: String,
     },
     /// Add a new link tag
     AddLink {
         tag: String,
-        url: String,
+        /// URLs, opened together when the tag is launched; `https://` is added when there is no scheme
+        #[arg(required = true)]
+        urls: Vec<String>,
+        /// A note on what the link is for, shown under it in `list-links`
+        #[arg(long, value_name = "TEXT")]
+        desc: Option<String>,
+        /// Always open this link in this browser (name, command or path) instead of the preferred one
+        #[arg(long, value_name = "NAME")]
+        browser: Option<String>,
+        /// Category to list the link under, e.g. `work` or `research`
+        #[arg(long, value_name = "NAME")]
+        category: Option<String>,
+        /// Save the value exactly as given, without adding a scheme or checking it
+        #[arg(long)]
+        raw: bool,
+        /// Replace the link if the tag already exists
+        #[arg(long)]
+        force: bool,
+        /// Refuse URLs already saved under another tag instead of only warning
+        #[arg(long)]
+        no_duplicates: bool,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Add links in bulk from stdin, one `tag url [url...]` line each (`#` starts a comment)
+    ImportLinks {
+        /// Save the values exactly as given, without adding a scheme or checking them
+        #[arg(long)]
+        raw: bool,
+        /// Replace links whose tag already exists instead of skipping those lines
+        #[arg(long)]
+        force: bool,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Add a new alias shortcut
     AddAlias {
         tag: String,
+        /// URL, another tag, or `group:<name>`; `https://` is added to URLs without a scheme
         url: String,
+        /// Save the value exactly as given, without adding a scheme or checking it
+        #[arg(long)]
+        raw: bool,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
     },
-    /// Remove a saved link
-    RemoveLink {
+    /// Change the URLs or description of an existing link (fails if the tag is not saved)
+    EditLink {
         tag: String,
+        /// New URLs, replacing all saved ones; `https://` is added when there is no scheme
+        #[arg(required_unless_present = "desc")]
+        urls: Vec<String>,
+        /// New description; an empty one removes it
+        #[arg(long, value_name = "TEXT")]
+        desc: Option<String>,
+        /// Save the value exactly as given, without adding a scheme or checking it
+        #[arg(long)]
+        raw: bool,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Change the target of an existing alias (fails if the alias is not saved)
+    EditAlias {
+        tag: String,
+        /// New URL, tag or `group:<name>`
+        url: String,
+        /// Save the value exactly as given, without adding a scheme or checking it
+        #[arg(long)]
+        raw: bool,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Rename a link tag, updating aliases that point at it
+    #[command(visible_alias = "rename-link")]
+    TagRename {
+        old: String,
+        new: String,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Move a link to another place in the saved order, which `open-all` follows
+    MoveLink {
+        tag: String,
+        /// New position, counting from 1; past the end moves the link to the end
+        position: usize,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Rename an alias, keeping its target and updating aliases that point at it
+    RenameAlias {
+        old: String,
+        new: String,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Remove saved links by tag, glob pattern (`old-*`) or group membership
+    RemoveLink {
+        #[arg(required_unless_present = "group")]
+        tags: Vec<String>,
+        /// Also consider every link in this group
+        #[arg(long)]
+        group: Option<String>,
+        /// Ask before removing each link (y = remove, n = keep, q = stop asking)
+        #[arg(short, long)]
+        interactive: bool,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Remove every saved link
+    ClearLinks {
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Remove every saved alias
+    ClearAliases {
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Roll links.json (or aliases.json) back to one of the backups kept on every save
+    Restore {
+        /// Backup to restore: 1 is the most recent, up to 3
+        #[arg(default_value_t = 1)]
+        slot: usize,
+        /// Restore aliases.json instead of links.json
+        #[arg(long)]
+        aliases: bool,
     },
     /// Remove a saved alias
     RemoveAlias {
         tag: String,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Create or replace a group of link tags
+    AddGroup {
+        name: String,
+        /// Link tags that belong to the group
+        #[arg(required = true)]
+        tags: Vec<String>,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Remove a saved group
+    RemoveGroup {
+        name: String,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List saved groups and their link tags
+    ListGroups,
+    /// Open one or more saved groups
+    OpenGroup {
+        #[arg(required = true)]
+        groups: Vec<String>,
+        /// Open each group in its own browser window
+        #[arg(long)]
+        window_per_group: bool,
+        #[command(flatten)]
+        order: OrderArgs,
+        #[command(flatten)]
+        launch: LaunchArgs,
+    },
+    /// Save the tabs open in a Chromium browser as links in a new group (needs `devtools` feature)
+    Snapshot {
+        /// Group to store the captured tabs in (replaced if it exists)
+        #[arg(long)]
+        group: String,
+        /// Remote debugging port the browser was started with
+        #[arg(long, default_value_t = DEFAULT_DEVTOOLS_PORT)]
+        port: u16,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Set display metadata (color, icon) for a group
+    SetGroupMeta {
+        group: String,
+        /// Header color: grey, blue, red, yellow, green, pink, purple, cyan or orange
+        #[arg(long, value_parser = parse_group_color)]
+        color: Option<String>,
+        /// Icon or emoji shown before the group name
+        #[arg(long)]
+        icon: Option<String>,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Always open a group in the given browser profile (omit the profile to clear it)
+    SetGroupProfile {
+        group: String,
+        /// Profile directory name, e.g. `Default` or `Profile 1` (Firefox: profile name)
+        profile: Option<String>,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Save a search for `launch "!tag words"`, or remove it when the URL is omitted
+    SetSearch {
+        /// Search tag, used as `!tag`
+        tag: String,
+        /// URL with `{}` where the query goes, e.g. `https://duckduckgo.com/?q={}`
+        url: Option<String>,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List the searches saved with `set-search`
+    ListSearches,
+    /// Print every link and alias tag, sorted, one per line (for rofi, dmenu, Alfred, ...)
+    Tags {
+        /// Print `tag<TAB>url` instead of just the tag
+        #[arg(long)]
+        with_url: bool,
     },
     /// List saved links and aliases
-    ListLinks,
+    ListLinks {
+        /// Only show entries whose tag or URL contains this text (case-insensitive)
+        filter: Option<String>,
+        /// Group links under their (colored) group headers
+        #[arg(long)]
+        by_group: bool,
+        /// Show what each alias ultimately points to (alias chains, group:<name> references)
+        #[arg(long, conflicts_with = "filter")]
+        resolve: bool,
+        /// Print `{ "links": [...], "aliases": {...} }` as JSON instead
+        #[arg(long, conflicts_with_all = ["by_group", "resolve"])]
+        json: bool,
+        /// Print only the URLs, one per line
+        #[arg(long, conflicts_with_all = ["by_group", "resolve", "json", "tag_only"])]
+        url_only: bool,
+        /// Print only the tags, one per line
+        #[arg(long, conflicts_with_all = ["by_group", "resolve", "json"])]
+        tag_only: bool,
+        /// Only list links and aliases that are members of this group
+        #[arg(long, conflicts_with_all = ["by_group", "resolve"])]
+        group: Option<String>,
+        /// Only list the links in this category (aliases have none)
+        #[arg(long, value_name = "NAME", conflicts_with_all = ["by_group", "resolve"])]
+        category: Option<String>,
+        /// Print each entry with a format such as `{tag}\t{url}\t{group}`
+        /// (tokens: tag, url, group, note, hits, last_used; `{{` for a literal brace)
+        #[arg(long, value_name = "FORMAT", conflicts_with_all = ["by_group", "resolve", "json", "url_only", "tag_only"])]
+        template: Option<String>,
+    },
+    /// Open the given saved links together (can use --incognito)
+    OpenLinks {
+        #[arg(required = true)]
+        tags: Vec<String>,
+        #[command(flatten)]
+        launch: LaunchArgs,
+    },
     /// Open all saved links (can use --incognito)
     OpenAllLinks {
-        /// Open links in incognito/private mode
+        /// Open every link in its own new window
+        #[arg(long)]
+        window_each: bool,
+        /// Open with the OS default handler (xdg-open, open, start) instead of a detected browser
+        #[arg(long, conflicts_with_all = ["browser", "browser_config", "window_each"])]
+        system: bool,
+        /// Open the links one at a time, waiting this many milliseconds between them
+        #[arg(long, value_name = "MS", conflicts_with = "system")]
+        delay: Option<u64>,
+        /// Browser profile to open in: `--profile-directory` for Chromium/Edge, `-P` for Firefox
+        #[arg(long, value_name = "NAME", conflicts_with = "system")]
+        profile: Option<String>,
+        /// Don't ask before opening more than `confirm_open_above` links
         #[arg(short, long)]
-        incognito: bool,
+        yes: bool,
+        /// Only open the first N links, after --reverse/--shuffle
+        #[arg(long, value_name = "N")]
+        count: Option<usize>,
+        /// Only open the links in this category
+        #[arg(long, value_name = "NAME")]
+        category: Option<String>,
+        /// Wait until this local time (HH:MM, tomorrow if already past) before opening
+        #[arg(long, value_name = "HH:MM", value_parser = parse_clock_time)]
+        at: Option<NaiveTime>,
+        #[command(flatten)]
+        order: OrderArgs,
+        #[command(flatten)]
+        launch: LaunchArgs,
     },
     /// Open all saved aliases (can use --incognito)
     OpenAllAliases {
-        /// Open aliases in incognito/private mode
+        /// Open every alias in its own new window
+        #[arg(long)]
+        window_each: bool,
+        /// Open the aliases one at a time, waiting this many milliseconds between them
+        #[arg(long, value_name = "MS")]
+        delay: Option<u64>,
+        /// Don't ask before opening more than `confirm_open_above` aliases
         #[arg(short, long)]
-        incognito: bool,
+        yes: bool,
+        /// Only open the first N aliases, after --reverse/--shuffle
+        #[arg(long, value_name = "N")]
+        count: Option<usize>,
+        #[command(flatten)]
+        order: OrderArgs,
+        #[command(flatten)]
+        launch: LaunchArgs,
+    },
+    /// Show what every saved link and alias resolves to (never launches or detects)
+    ResolveAll {
+        /// Print the resolutions as a JSON array
+        #[arg(long)]
+        json: bool,
+    },
+    /// Write links, aliases and the preferred browser to one JSON file for `import`
+    Export {
+        path: PathBuf,
+    },
+    /// Restore a file written by `export`, or import bookmarks straight from a
+    /// browser profile with --from-browser (folders become groups)
+    Import {
+        /// File written by `export`; replaces the saved links and aliases unless --merge is given
+        #[arg(required_unless_present = "from_browser", conflicts_with = "from_browser")]
+        path: Option<PathBuf>,
+        /// Keep saved tags and only add new ones from the file, reporting collisions
+        #[arg(long, requires = "path")]
+        merge: bool,
+        /// Browser to read from: chrome, chromium, edge, brave or firefox
+        #[arg(long, value_name = "BROWSER")]
+        from_browser: Option<String>,
+        /// Profile directory name to read (prompted for when there are several)
+        #[arg(long, requires = "from_browser")]
+        profile: Option<String>,
+        /// What to do when an imported tag is already saved (--from-browser only)
+        #[arg(long, value_enum, default_value_t = MergeStrategy::Skip, conflicts_with = "path")]
+        strategy: MergeStrategy,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show or change app settings (e.g. `config dedupe_on_save true`)
+    Config {
+        /// Setting to show or change; omit to print all settings
+        key: Option<String>,
+        /// New value (JSON such as `true` or `5`, otherwise a plain string)
+        value: Option<String>,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
     },
+    /// Block a domain or URL glob from being opened with --safe-launch
+    AddBlock {
+        /// Domain (`example.com`, also blocks subdomains) or URL glob (`https://*/admin*`)
+        pattern: String,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Remove a pattern from the blocklist
+    RemoveBlock {
+        pattern: String,
+        /// Print what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List recent launches, newest first, or work with the launch history
+    History {
+        #[command(subcommand)]
+        action: Option<HistoryAction>,
+    },
+    /// Open a recent launch again by its number in `history` (1 is the most recent)
+    Replay {
+        #[arg(default_value_t = 1)]
+        index: usize,
+        #[command(flatten)]
+        launch: LaunchArgs,
+    },
+    /// Clear and rebuild derived caches
+    Reindex {
+        /// Cache to rebuild (e.g. `detection`); all caches when omitted
+        what: Option<String>,
+    },
+    /// Save the preferred browser without prompting (a command on PATH or a full path)
+    SetBrowser {
+        path_or_name: String,
+    },
+    /// Set extra launch arguments and private-mode flags for a saved browser
+    SetBrowserFlags {
+        /// `default` for the preferred browser, or a name saved with `detect --save-as`
+        name: String,
+        /// Arguments added to every launch, e.g. `--new-window`; replaces the saved ones
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+        /// Flag to use instead of the built-in private-mode flags (repeatable)
+        #[arg(long = "private", value_name = "FLAG", allow_hyphen_values = true)]
+        private: Vec<String>,
+        /// Go back to the built-in private-mode flags
+        #[arg(long, conflicts_with = "private")]
+        default_private: bool,
+        /// Remove all saved arguments and private-mode flags
+        #[arg(long, conflicts_with_all = ["args", "private", "default_private"])]
+        clear: bool,
+    },
+    /// Check the config files and the saved browser; exits non-zero when something is broken
+    Doctor,
+    /// Report saved URLs that are duplicated or malformed; exits non-zero when any are found
+    Check {
+        /// Also send a HEAD request to each http(s) URL and report errors (`http` feature)
+        #[arg(long)]
+        online: bool,
+    },
+    /// Summarize the saved links and aliases: counts, domains, newest link, preferred browser
+    Stats,
     /// Re-detect and select the preferred browser
-    Detect,
+    Detect {
+        /// Only print every candidate path checked per browser and whether it exists
+        #[arg(long)]
+        debug_paths: bool,
+        /// Print the --debug-paths report as JSON
+        #[arg(long, requires = "debug_paths")]
+        json: bool,
+        /// Only print the detected browsers; nothing is prompted for or saved
+        #[arg(long, conflicts_with_all = ["debug_paths", "save_as"])]
+        list_only: bool,
+        /// Output format for --list-only
+        #[arg(long, value_enum, default_value_t = ListFormat::Text, requires = "list_only")]
+        format: ListFormat,
+        /// Cap the number of threads used to probe browsers (default: detect_threads setting)
+        #[arg(long, value_name = "N")]
+        parallel_detect_threads: Option<usize>,
+        /// When a browser is installed more than once, keep only the newest version
+        #[arg(long)]
+        latest_only: bool,
+        /// Pick a browser and save it as a named config (use with --browser-config)
+        #[arg(long, value_name = "NAME")]
+        save_as: Option<String>,
+        /// Ignore the cached detection results and the saved browser, rescan and pick again
+        #[arg(long)]
+        refresh: bool,
+        /// Also search install directories recursively (slower; implies --refresh)
+        #[arg(long)]
+        deep: bool,
+        /// Also look for portable or unpacked browsers under this directory (repeatable; implies --refresh)
+        #[arg(long, value_name = "DIR")]
+        search_path: Vec<PathBuf>,
+    },
+    /// List browser configs saved with `detect --save-as`
+    ListBrowserConfigs,
+    /// Import the old interactive launcher's links.txt and browser, and state files
+    /// older builds left, from a directory (default: the current one)
+    MigrateLauncher {
+        dir: Option<PathBuf>,
+    },
+    /// Create a .quick_tabs.json in the current directory for project links and browser
+    Init {
+        /// Browser this project opens links in (name, command or path)
+        #[arg(long)]
+        browser: Option<String>,
+    },
+    /// Print a shell completion script (e.g. `quick_tabs completions bash > /etc/bash_completion.d/quick_tabs`)
+    Completions {
+        /// bash, zsh, fish, powershell or elvish
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// Print copy-pasteable examples, for every workflow or for one command
+    Examples {
+        /// Only show examples of this command, e.g. `add-link`
+        command: Option<String>,
+    },
     /// Print help information
     Help,
 }
 
+#[derive(Subcommand, Debug)]
+enum HistoryAction {
+    /// Write the history as a single CSV or JSON file
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+        /// File to write; prints to stdout when omitted
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Only include launches within this window (e.g. 24h, 7d)
+        #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+        since: Option<Duration>,
+    },
+}
+
 // --- Main Execution ---
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+fn main() {
     let cli = Cli::parse();
-    
+    let error_format = cli.error_format;
+    quick_tabs::output::set_plain(cli.plain);
+    if let Some(dir) = &cli.config_dir {
+        quick_tabs::config::set_config_dir(dir.clone());
+    }
+    quick_tabs::output::set_verbosity(match (cli.quiet, cli.verbose) {
+        (true, _) => Verbosity::Quiet,
+        (_, true) => Verbosity::Verbose,
+        _ => Verbosity::Normal,
+    });
+
+    if let Err(e) = run(cli) {
+        e.report(error_format);
+        std::process::exit(e.code());
+    }
+}
+
+fn run(cli: Cli) -> Result<(), QuickTabsError> {
     // 1. Config paths setup
-    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
-    let link_path = PathBuf::from(format!("{}/.quick_tabs_links.json", home));
-    let alias_path = PathBuf::from(format!("{}/.quick_tabs_aliases.json", home));
-    
-    // 2. Browser Detection (only required for launch/open commands)
-    let browser_result = detect_browsers();
+    let paths = config_paths()?;
+    let (link_path, alias_path, group_path) = (paths.links.clone(), paths.aliases.clone(), paths.groups.clone());
+    let app_cfg = AppConfig::load_or_default();
+
+    // Project overrides from the nearest .quick_tabs.json (launch and list only)
+    let project = match &cli.command {
+        Commands::Launch { .. } | Commands::Copy { .. } | Commands::ListLinks { .. } => project::discover()?,
+        _ => None,
+    };
+    // Machine-readable listings must print nothing but their data
+    let quiet = matches!(&cli.command, Commands::ListLinks { json, url_only, tag_only, template, .. }
+        if *json || *url_only || *tag_only || template.is_some())
+        || matches!(&cli.command, Commands::Launch { print_url: true, .. });
+    if let Some(p) = project.as_ref().filter(|_| !quiet) {
+        say!("📌 Using project config: {}", p.path.display());
+    }
+    let project_browser = project.as_ref().and_then(|p| p.config.browser.clone());
 
     match cli.command {
         // --- Commands requiring Config & Browser ---
-        Commands::Launch { target, incognito } => {
-            let browser = get_browser_or_exit(browser_result)?;
-            let link_cfg = LinkConfig::load(&link_path);
-            let alias_cfg = AliasConfig::load(&alias_path);
-            
-            let mode = if incognito { LaunchMode::Private } else { LaunchMode::Normal };
+        Commands::Launch { target, mut query, literal, mut launch, close_after, both, wait, system, profile, print_url } => {
+            let mut link_cfg = LinkConfig::load(&link_path)?;
+            let mut alias_cfg = AliasConfig::load(&alias_path)?;
+            if let Some(p) = &project {
+                p.config.merge_into(&mut link_cfg, &mut alias_cfg);
+            }
 
-            let url = alias_cfg.resolve(&target)
-                .or_else(|| link_cfg.get_url(&target))
-                .unwrap_or_else(|| target);
+            let (tag, urls) = if literal {
+                (None, vec![target])
+            } else if let Some(bang) = target.strip_prefix('!').filter(|_| !is_saved_tag(&target, &alias_cfg, &link_cfg)) {
+                // The words go into the search URL, so none are left for a `{}` below
+                (None, vec![expand_search(bang, &std::mem::take(&mut query), &app_cfg.searches)?])
+            } else {
+                let Some(tag) = saved_or_chosen_tag(target, &alias_cfg, &link_cfg)? else {
+                    say!("❎ Nothing opened.");
+                    return Ok(());
+                };
+                let urls = resolve_target(&tag, &alias_cfg, &link_cfg, &GroupConfig::load(&group_path)?)?;
+                (is_saved_tag(&tag, &alias_cfg, &link_cfg).then_some(tag), urls)
+            };
+            let now = Local::now();
+            let urls = urls.iter()
+                // Rendered here as well so a bad `{date:...}` fails the command instead of being skipped
+                .map(|url| render_template(url, &now))
+                // Filled in last, so a query is never read as a `{date}` token
+                .map(|url| fill_query(&url?, &query))
+                .collect::<Result<Vec<_>, _>>()?;
+            if print_url {
+                // `~` and `$VARS` are otherwise expanded just before spawning; nothing is recorded
+                for url in &urls {
+                    println!("{}", expand_env(url)?);
+                }
+                return Ok(());
+            }
+            let url_refs: Vec<&str> = urls.iter().map(String::as_str).collect();
+            let launched: Vec<HistoryEntry> = urls.iter().map(|url| HistoryEntry::now(tag.clone(), url.clone())).collect();
+            // Checked before anything is reserved, so a refused launch uses no rate-limit slot
+            if close_after.is_some() && urls.len() != 1 {
+                return Err(QuickTabsError::Other(
+                    "--close-after needs a single URL; this tag opens several".to_string(),
+                ));
+            }
 
-            launch_link(&browser, &url, mode);
+            if system {
+                let options = launch.system_options(&app_cfg)?;
+                launch.reserve(&app_cfg, urls.len())?;
+                launch_with_system_opener(&url_refs, &options)?;
+                launch.record(launched, &options);
+                return Ok(());
+            }
+
+            // A browser pinned on the link beats the project's, but not --browser or --browser-config
+            let pinned = tag.as_deref()
+                .and_then(|tag| target_link(tag, &alias_cfg, &link_cfg))
+                .and_then(|link| link.browser.clone())
+                .filter(|_| launch.browser.is_none() && launch.browser_config.is_none());
+            if launch.browser.is_none() {
+                launch.browser = project_browser;
+            }
+            let browser = match pinned {
+                Some(name) => match find_browser(&name, &DetectOptions::from_config(&app_cfg)) {
+                    Ok(browser) => {
+                        say!("📌 Using the link's browser: {}", browser.path.display());
+                        browser
+                    }
+                    Err(_) => {
+                        esay!("⚠️ The link's browser '{}' is not available; using the default browser.", name);
+                        launch.browser(&app_cfg)?
+                    }
+                },
+                None => launch.browser(&app_cfg)?,
+            };
+            let options = LaunchOptions { profile, wait, ..launch.options(&app_cfg, &browser) };
+            launch.reserve(&app_cfg, if both { 2 * urls.len() } else { urls.len() })?;
+
+            if both {
+                // Two separate invocations so each gets its own family-specific flags
+                for mode in [LaunchMode::Normal, LaunchMode::Private] {
+                    launch_urls(&browser, &url_refs, &LaunchOptions { mode, ..options.clone() })?;
+                }
+                launch.record(launched, &options);
+                return Ok(());
+            }
+
+            match (close_after, url_refs.as_slice()) {
+                (Some(duration), [url]) => launch_link_for(&browser, url, &options, duration)?,
+                _ => launch_urls(&browser, &url_refs, &options)?,
+            }
+            launch.record(launched, &options);
+        },
+
+        Commands::Copy { target } => {
+            let mut link_cfg = LinkConfig::load(&link_path)?;
+            let mut alias_cfg = AliasConfig::load(&alias_path)?;
+            if let Some(p) = &project {
+                p.config.merge_into(&mut link_cfg, &mut alias_cfg);
+            }
+
+            let urls = resolve_target(&target, &alias_cfg, &link_cfg, &GroupConfig::load(&group_path)?)?;
+            let now = Local::now();
+            let text = urls.iter()
+                .map(|url| render_template(url, &now))
+                .collect::<Result<Vec<_>, _>>()?
+                .join("\n");
+            copy_to_clipboard(&text)?;
+            say!("📋 Copied {} to the clipboard", text.replace('\n', " "));
         },
 
         // --- Commands requiring Config only ---
-        Commands::AddLink { tag, url } => {
-            let mut link_cfg = LinkConfig::load(&link_path);
-            link_cfg.add_link(tag, url);
-            link_cfg.save(&link_path)?;
-            println!("✅ Link saved!");
-        },
-        Commands::AddAlias { tag, url } => {
-            let mut alias_cfg = AliasConfig::load(&alias_path);
-            alias_cfg.add_alias(tag, url);
-            alias_cfg.save(&alias_path)?;
-            println!("✅ Alias saved!");
-        },
-        Commands::RemoveLink { tag } => {
-            let mut link_cfg = LinkConfig::load(&link_path);
-            if link_cfg.remove_link(&tag) {
+        Commands::AddLink { tag, urls, desc, browser, category, raw, force, no_duplicates, dry_run } => {
+            let mut link_cfg = LinkConfig::load(&link_path)?;
+            let urls = normalize_urls(urls, raw)?;
+            if let Some((other, url)) = link_cfg.find_duplicate(&tag, &urls) {
+                if no_duplicates {
+                    return Err(QuickTabsError::Other(format!("{} is already saved as '{}'", url, other)));
+                }
+                esay!("⚠️ {} is already saved as '{}'; saving it under '{}' too.", url, other, tag);
+            }
+            let link = Link { urls, description: desc, browser, category, ..Link::new(tag.clone(), "") };
+            let changes = link_cfg.add_link(link, force)?;
+            if !dry_run && changes.has_updates() {
+                say!("ℹ️ Replacing existing link for tag: {}", tag);
+            }
+            if changes.commit(dry_run, || save_links(&mut link_cfg, &link_path))? {
+                say!("✅ Link saved!");
+            }
+        },
+        Commands::ImportLinks { raw, force, dry_run } => {
+            if is_interactive() {
+                say!("⌨️ Reading `tag url` lines; end with Ctrl-D (Ctrl-Z then Enter on Windows).");
+            }
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+
+            // One load and one save for the whole batch; bad lines are reported and skipped
+            let mut link_cfg = LinkConfig::load(&link_path)?;
+            let mut changes = ChangeSet::default();
+            let mut skipped = 0;
+            for (i, line) in input.lines().enumerate() {
+                let added = Link::parse_line(line).map(|parsed| {
+                    let link = parsed.map_err(QuickTabsError::Other)?;
+                    let link = Link { urls: normalize_urls(link.urls, raw)?, ..link };
+                    if let Some((other, url)) = link_cfg.find_duplicate(&link.tag, &link.urls) {
+                        esay!("⚠️ Line {}: {} is already saved as '{}'; saving it under '{}' too.", i + 1, url, other, link.tag);
+                    }
+                    link_cfg.add_link(link, force)
+                });
+                match added {
+                    Some(Ok(added)) => changes.extend(added),
+                    Some(Err(e)) => {
+                        esay!("❌ Line {}: {}", i + 1, e);
+                        skipped += 1;
+                    }
+                    None => {}
+                }
+            }
+            let summary = changes.summary();
+            // Not `save_links`: imports are never deduplicated, whatever `dedupe_on_save` says
+            if changes.commit(dry_run, || link_cfg.save(&link_path))? || (changes.is_empty() && !dry_run) {
+                say!("✅ Imported links: {}; skipped {} line(s).", summary, skipped);
+            }
+        },
+        Commands::AddAlias { tag, url, raw, dry_run } => {
+            let mut alias_cfg = AliasConfig::load(&alias_path)?;
+            let url = alias_target(url, raw, &alias_cfg, &link_path)?;
+            let changes = alias_cfg.add_alias(tag, url);
+            if changes.commit(dry_run, || save_aliases(&mut alias_cfg, &alias_path))? {
+                say!("✅ Alias saved!");
+            }
+        },
+        Commands::RenameAlias { old, new, dry_run } => {
+            let mut alias_cfg = AliasConfig::load(&alias_path)?;
+            let changes = alias_cfg.rename_alias(&old, &new)?;
+            if changes.commit(dry_run, || save_aliases(&mut alias_cfg, &alias_path))? {
+                say!("✅ Renamed alias '{}' to '{}'", old, new);
+            }
+        },
+        Commands::EditLink { tag, urls, desc, raw, dry_run } => {
+            let mut link_cfg = LinkConfig::load(&link_path)?;
+            let mut changes = ChangeSet::default();
+            if !urls.is_empty() {
+                changes.extend(link_cfg.edit_link(&tag, normalize_urls(urls, raw)?)?);
+            }
+            if let Some(desc) = desc {
+                changes.extend(link_cfg.describe_link(&tag, Some(desc).filter(|d| !d.is_empty()))?);
+            }
+            if changes.is_empty() && !dry_run {
+                say!("ℹ️ Link '{}' is already up to date.", tag);
+            }
+            if changes.commit(dry_run, || save_links(&mut link_cfg, &link_path))? {
+                say!("✅ Link '{}' updated!", tag);
+            }
+        },
+        Commands::EditAlias { tag, url, raw, dry_run } => {
+            let mut alias_cfg = AliasConfig::load(&alias_path)?;
+            let url = alias_target(url, raw, &alias_cfg, &link_path)?;
+            let changes = alias_cfg.edit_alias(&tag, url)?;
+            if changes.is_empty() && !dry_run {
+                say!("ℹ️ Alias '{}' already points there.", tag);
+            }
+            if changes.commit(dry_run, || save_aliases(&mut alias_cfg, &alias_path))? {
+                say!("✅ Alias '{}' updated!", tag);
+            }
+        },
+        Commands::TagRename { old, new, dry_run } => {
+            let mut link_cfg = LinkConfig::load(&link_path)?;
+            let mut alias_cfg = AliasConfig::load(&alias_path)?;
+            let changes = link_cfg.rename_link(&old, &new, &mut alias_cfg)?;
+            let updated = changes.changes.len() - 1;
+            let saved = changes.commit(dry_run, || {
+                // Not `save_links`: deduplicating would drop tabs the group already lists
                 link_cfg.save(&link_path)?;
-                println!("✅ Link removed!");
+                if updated > 0 {
+                    save_aliases(&mut alias_cfg, &alias_path)?;
+                }
+                Ok::<_, QuickTabsError>(())
+            })?;
+            if saved {
+                say!("✅ Renamed link '{}' to '{}' ({} alias reference(s) updated)", old, new, updated);
+            }
+        },
+        Commands::MoveLink { tag, position, dry_run } => {
+            let mut link_cfg = LinkConfig::load(&link_path)?;
+            let changes = link_cfg.move_link(&tag, position)?;
+            if changes.is_empty() && !dry_run {
+                say!("ℹ️ Link '{}' is already at position {}.", tag, position.clamp(1, link_cfg.links.len()));
+            }
+            if changes.commit(dry_run, || save_links(&mut link_cfg, &link_path))? {
+                say!("✅ Moved link '{}'.", tag);
+            }
+        },
+        Commands::RemoveLink { tags, group, interactive, dry_run } => {
+            let mut link_cfg = LinkConfig::load(&link_path)?;
+
+            let mut candidates = link_cfg.matching_tags(&tags);
+            if let Some(name) = &group {
+                let group_cfg = GroupConfig::load(&group_path)?;
+                let members = group_cfg.groups.get(name)
+                    .ok_or_else(|| QuickTabsError::NotFound(format!("Group '{}'", name)))?;
+                for tag in members {
+                    if link_cfg.contains(tag) && !candidates.contains(tag) {
+                        candidates.push(tag.clone());
+                    }
+                }
+            }
+            if candidates.is_empty() {
+                return Err(QuickTabsError::NotFound(format!("Link tag '{}'", tags.join("', '"))));
+            }
+
+            let mut changes = ChangeSet::default();
+            for tag in &candidates {
+                if interactive {
+                    let urls = link_cfg.get_urls(tag).unwrap_or_default();
+                    match confirm_each(&format!("Remove '{}' ({})?", tag, urls.join(" "))) {
+                        Answer::Yes => {},
+                        Answer::No => continue,
+                        Answer::Quit => break,
+                    }
+                }
+                changes.extend(link_cfg.remove_link(tag));
+            }
+
+            // Saved once, after all prompts, so quitting keeps earlier confirmations
+            changes.commit(dry_run, || save_links(&mut link_cfg, &link_path))?;
+            if !dry_run {
+                match changes.changes.len() {
+                    0 => say!("❎ Nothing removed."),
+                    1 => say!("✅ Link removed!"),
+                    n => say!("✅ Removed {} links!", n),
+                }
+            }
+        },
+        Commands::ClearLinks { yes, dry_run } => {
+            let mut link_cfg = LinkConfig::load(&link_path)?;
+            let count = link_cfg.links.len();
+            if count == 0 {
+                say!("ℹ️ No links saved.");
+                return Ok(());
+            }
+            if !yes && !dry_run && !confirm(&format!("Remove all {} link(s)?", count)) {
+                say!("❎ Nothing removed.");
+                return Ok(());
+            }
+            let changes = link_cfg.clear();
+            if changes.commit(dry_run, || link_cfg.save(&link_path))? {
+                say!("✅ Removed {} link(s)!", count);
+            }
+        },
+        Commands::Restore { slot, aliases } => {
+            let (path, label) = if aliases { (&alias_path, "aliases") } else { (&link_path, "links") };
+            let backup = if aliases {
+                restore_backup(path, slot, |data| schema::from_json::<AliasConfig>(data, "aliases").map(drop).map_err(|e| e.to_string()))?
             } else {
-                println!("⚠️ Link tag '{}' not found.", tag);
+                restore_backup(path, slot, |data| schema::from_json::<LinkConfig>(data, "links").map(drop).map_err(|e| e.to_string()))?
+            };
+            say!("✅ Restored {} from {}. The replaced file is now backup 1.", label, backup.display());
+        },
+        Commands::ClearAliases { yes, dry_run } => {
+            let mut alias_cfg = AliasConfig::load(&alias_path)?;
+            let count = alias_cfg.aliases.len();
+            if count == 0 {
+                say!("ℹ️ No aliases saved.");
+                return Ok(());
+            }
+            if !yes && !dry_run && !confirm(&format!("Remove all {} alias(es)?", count)) {
+                say!("❎ Nothing removed.");
+                return Ok(());
+            }
+            let changes = alias_cfg.clear();
+            if changes.commit(dry_run, || alias_cfg.save(&alias_path))? {
+                say!("✅ Removed {} alias(es)!", count);
             }
         },
-        Commands::RemoveAlias { tag } => {
-            let mut alias_cfg = AliasConfig::load(&alias_path);
-            if alias_cfg.remove_alias(&tag) {
-                alias_cfg.save(&alias_path)?;
-                println!("✅ Alias removed!");
+        Commands::RemoveAlias { tag, dry_run } => {
+            let mut alias_cfg = AliasConfig::load(&alias_path)?;
+            let changes = alias_cfg.remove_alias(&tag);
+            if changes.is_empty() {
+                return Err(QuickTabsError::NotFound(format!("Alias tag '{}'", tag)));
+            }
+            if changes.commit(dry_run, || save_aliases(&mut alias_cfg, &alias_path))? {
+                say!("✅ Alias removed!");
+            }
+        },
+        Commands::AddGroup { name, tags, dry_run } => {
+            let link_cfg = LinkConfig::load(&link_path)?;
+            for tag in tags.iter().filter(|t| !link_cfg.contains(t)) {
+                say!("⚠️ Link tag '{}' not found (saved in group anyway).", tag);
+            }
+            let mut group_cfg = GroupConfig::load(&group_path)?;
+            let changes = group_cfg.add_group(name.clone(), tags, &link_cfg)?;
+            if !dry_run && changes.has_updates() {
+                say!("ℹ️ Replacing existing group: {}", name);
+            }
+            if changes.commit(dry_run, || group_cfg.save(&group_path))? {
+                say!("✅ Group saved!");
+            }
+        },
+        Commands::RemoveGroup { name, dry_run } => {
+            let mut group_cfg = GroupConfig::load(&group_path)?;
+            let changes = group_cfg.remove_group(&name);
+            if changes.is_empty() {
+                return Err(QuickTabsError::NotFound(format!("Group '{}'", name)));
+            }
+            if changes.commit(dry_run, || group_cfg.save(&group_path))? {
+                say!("✅ Group removed!");
+            }
+        },
+        Commands::ListGroups => {
+            GroupConfig::load(&group_path)?.list(&app_cfg.group_meta);
+        },
+        Commands::OpenGroup { groups, window_per_group, order, launch } => {
+            let browser = launch.browser(&app_cfg)?;
+            let link_cfg = LinkConfig::load(&link_path)?;
+            let group_cfg = GroupConfig::load(&group_path)?;
+            let options = launch.options(&app_cfg, &browser);
+
+            // (profile, urls) per group, in the order given
+            let mut resolved: Vec<(Option<&String>, Vec<String>)> = vec![];
+            for name in &groups {
+                let urls = group_cfg.resolve(name, &link_cfg)
+                    .ok_or_else(|| QuickTabsError::NotFound(format!("Group '{}'", name)))?;
+                let profile = app_cfg.group_profiles.get(name);
+                match profile {
+                    Some(p) => say!("📁 {}: {} link(s) (profile: {})", name, urls.len(), p),
+                    None => say!("📁 {}: {} link(s)", name, urls.len()),
+                }
+                resolved.push((profile, urls));
+            }
+
+            launch.reserve(&app_cfg, resolved.iter().map(|(_, urls)| urls.len()).sum())?;
+            let batch = order.batch(false);
+            if window_per_group {
+                for (profile, urls) in resolved.iter().filter(|(_, urls)| !urls.is_empty()) {
+                    let mut urls: Vec<&str> = urls.iter().map(|u| u.as_str()).collect();
+                    batch.arrange(&mut urls);
+                    let options = LaunchOptions { profile: profile.cloned(), ..options.clone() };
+                    launch_urls_in_new_window(&browser, &urls, &options)?;
+                }
             } else {
-                println!("⚠️ Alias tag '{}' not found.", tag);
+                // Groups sharing a profile open together; each profile needs its own launch
+                let mut profiles: Vec<Option<&String>> = vec![];
+                for (profile, _) in &resolved {
+                    if !profiles.contains(profile) {
+                        profiles.push(*profile);
+                    }
+                }
+
+                let mut opened = false;
+                for profile in profiles {
+                    let mut urls: Vec<&str> = resolved.iter()
+                        .filter(|(p, _)| *p == profile)
+                        .flat_map(|(_, urls)| urls.iter().map(|u| u.as_str()))
+                        .collect();
+                    batch.arrange(&mut urls);
+                    if !urls.is_empty() {
+                        let options = LaunchOptions { profile: profile.cloned(), ..options.clone() };
+                        launch_urls_simultaneously(&browser, &urls, &options)?;
+                        opened = true;
+                    }
+                }
+                if !opened {
+                    say!("⚠️ No links to open.");
+                }
             }
+            let launched = groups.iter().zip(resolved)
+                .flat_map(|(name, (_, urls))| urls.into_iter().map(move |url| HistoryEntry::now(Some(format!("group:{}", name)), url)));
+            launch.record(launched, &options);
         },
-        Commands::ListLinks => {
-            LinkConfig::load(&link_path).list();
-            AliasConfig::load(&alias_path).list();
+        Commands::Snapshot { group, port, dry_run } => {
+            let urls = capture_tabs(port)?;
+            if urls.is_empty() {
+                say!("⚠️ No open http(s) tabs found.");
+                return Ok(());
+            }
+
+            let mut link_cfg = LinkConfig::load(&link_path)?;
+            let mut group_cfg = GroupConfig::load(&group_path)?;
+            let mut changes = ChangeSet::default();
+            let mut tags = vec![];
+            let mut n = 1;
+            for url in urls {
+                // Number tags after the group, skipping any already in use
+                while link_cfg.contains(&format!("{}-{}", group, n)) {
+                    n += 1;
+                }
+                let tag = format!("{}-{}", group, n);
+                changes.extend(link_cfg.add_link(Link::new(tag.clone(), url), false)?);
+                tags.push(tag);
+            }
+
+            let count = tags.len();
+            changes.extend(group_cfg.add_group(group.clone(), tags, &link_cfg)?);
+            let saved = changes.commit(dry_run, || {
+                save_links(&mut link_cfg, &link_path)?;
+                group_cfg.save(&group_path)?;
+                Ok::<_, QuickTabsError>(())
+            })?;
+            if !saved {
+                return Ok(());
+            }
+            say!("✅ Saved {} tab(s) to group '{}'. Reopen with `quick_tabs open-group {}`.", count, group, shell_quote(&group));
+        },
+        Commands::SetGroupMeta { group, color, icon, dry_run } => {
+            if !GroupConfig::load(&group_path)?.contains(&group) {
+                say!("⚠️ Group '{}' does not exist yet; metadata saved for when it does.", group);
+            }
+            let settings = settings_path()?;
+            let mut app_cfg = AppConfig::load(&settings);
+            let meta = app_cfg.group_meta.entry(group.clone()).or_default();
+            let old = serde_json::to_string(meta).map_err(|e| e.to_string())?;
+            if color.is_some() {
+                meta.color = color;
+            }
+            if icon.is_some() {
+                meta.icon = icon;
+            }
+            let changes = ChangeSet::from(Change::Updated { kind: "group meta", tag: group, old, new: serde_json::to_string(meta).map_err(|e| e.to_string())? });
+            if changes.commit(dry_run, || app_cfg.save(&settings))? {
+                say!("✅ Group metadata saved!");
+            }
+        },
+        Commands::SetGroupProfile { group, profile, dry_run } => {
+            if !GroupConfig::load(&group_path)?.contains(&group) {
+                say!("⚠️ Group '{}' does not exist yet; profile saved for when it does.", group);
+            }
+            let settings = settings_path()?;
+            let mut app_cfg = AppConfig::load(&settings);
+            match profile {
+                Some(profile) => {
+                    let change = match app_cfg.group_profiles.insert(group.clone(), profile.clone()) {
+                        Some(old) => Change::Updated { kind: "group profile", tag: group, old, new: profile },
+                        None => Change::Added { kind: "group profile", tag: group, value: profile },
+                    };
+                    if ChangeSet::from(change).commit(dry_run, || app_cfg.save(&settings))? {
+                        say!("✅ Group profile saved!");
+                    }
+                },
+                None => {
+                    let old = app_cfg.group_profiles.remove(&group)
+                        .ok_or_else(|| QuickTabsError::NotFound(format!("Profile for group '{}'", group)))?;
+                    let change = Change::Removed { kind: "group profile", tag: group, value: old };
+                    if ChangeSet::from(change).commit(dry_run, || app_cfg.save(&settings))? {
+                        say!("✅ Group profile cleared; the group opens in the default profile.");
+                    }
+                },
+            }
+        },
+        Commands::SetSearch { tag, url, dry_run } => {
+            let tag = tag.trim_start_matches('!').to_string();
+            let settings = settings_path()?;
+            let mut app_cfg = AppConfig::load(&settings);
+            let change = match url {
+                Some(url) => {
+                    let url = normalize_url(&url)?;
+                    if !url.contains(QUERY_PLACEHOLDER) {
+                        return Err(QuickTabsError::Other(format!("'{}' has no {} placeholder for the query", url, QUERY_PLACEHOLDER)));
+                    }
+                    match app_cfg.searches.insert(tag.clone(), url.clone()) {
+                        Some(old) => Change::Updated { kind: "search", tag, old, new: url },
+                        None => Change::Added { kind: "search", tag, value: url },
+                    }
+                },
+                None => {
+                    let old = app_cfg.searches.shift_remove(&tag)
+                        .ok_or_else(|| QuickTabsError::NotFound(format!("Search '!{}'", tag)))?;
+                    Change::Removed { kind: "search", tag, value: old }
+                },
+            };
+            let removed = matches!(change, Change::Removed { .. });
+            if ChangeSet::from(change).commit(dry_run, || app_cfg.save(&settings))? {
+                say!("✅ Search {}!", if removed { "removed" } else { "saved" });
+            }
+        },
+        Commands::ListSearches => {
+            if app_cfg.searches.is_empty() {
+                say!("⚠️ No searches saved. Add one with `set-search <tag> <url-with-{{}}>`.");
+            }
+            for (tag, url) in &app_cfg.searches {
+                println!("  !{} {}", tag, url);
+            }
+        },
+        Commands::Tags { with_url } => {
+            let link_cfg = LinkConfig::load(&link_path)?;
+            let alias_cfg = AliasConfig::load(&alias_path)?;
+            for line in tag_lines(&link_cfg, &alias_cfg, with_url) {
+                println!("{}", line);
+            }
+        },
+        Commands::ListLinks { filter, by_group, resolve, json, url_only, tag_only, group, category, template } => {
+            let mut link_cfg = LinkConfig::load(&link_path)?;
+            let mut alias_cfg = AliasConfig::load(&alias_path)?;
+            if let Some(p) = &project {
+                p.config.merge_into(&mut link_cfg, &mut alias_cfg);
+            }
+            if let Some(name) = &group {
+                let members = GroupConfig::load(&group_path)?.groups.remove(name)
+                    .ok_or_else(|| QuickTabsError::NotFound(format!("Group '{}'", name)))?;
+                link_cfg.links.retain(|l| members.contains(&l.tag));
+                alias_cfg.aliases.retain(|tag, _| members.contains(tag));
+            }
+            if let Some(category) = &category {
+                link_cfg.keep_category(category)?;
+                alias_cfg.aliases.clear();
+            }
+            // The plain listing filters itself, so it can tell "none saved" from "none match"
+            let plain = !(json || url_only || tag_only || template.is_some() || by_group);
+            if let Some(needle) = filter.as_deref().filter(|_| !plain) {
+                link_cfg.links.retain(|l| matches_filter(&l.tag, &l.urls_text(), needle));
+                alias_cfg.aliases.retain(|tag, url| matches_filter(tag, url, needle));
+            }
+            let template = match (url_only, tag_only) {
+                (true, _) => Some("{url}".to_string()),
+                (_, true) => Some("{tag}".to_string()),
+                _ => template,
+            };
+            if let Some(template) = template {
+                let history = history::history_path().and_then(|p| Ok(history::load(&p)?)).unwrap_or_default();
+                let entries = list_entries(&link_cfg, &alias_cfg);
+                for line in render_entries(&template, &entries, &GroupConfig::load(&group_path)?, &history)? {
+                    println!("{}", line);
+                }
+                return Ok(());
+            }
+            if json {
+                // Sorted so the output is stable between runs
+                let aliases: BTreeMap<_, _> = alias_cfg.aliases.iter().collect();
+                let out = serde_json::json!({ "links": link_cfg.links, "aliases": aliases });
+                println!("{}", serde_json::to_string_pretty(&out).map_err(|e| e.to_string())?);
+                return Ok(());
+            }
+            if by_group {
+                let app_cfg = AppConfig::load(&settings_path()?);
+                GroupConfig::load(&group_path)?.list_by_group(&link_cfg, &app_cfg.group_meta);
+            } else {
+                link_cfg.list_filtered(filter.as_deref());
+            }
+            if resolve {
+                alias_cfg.list_resolved(&link_cfg, &GroupConfig::load(&group_path)?);
+            } else {
+                alias_cfg.list_filtered(filter.as_deref());
+            }
         },
         
         // --- Commands requiring Config & Browser, and Incognito flag ---
-        Commands::OpenAllLinks { incognito } => {
-            let browser = get_browser_or_exit(browser_result)?;
-            let link_cfg = LinkConfig::load(&link_path);
-            let mode = if incognito { LaunchMode::Private } else { LaunchMode::Normal };
-            link_cfg.open_all(&browser, mode);
+        Commands::OpenLinks { tags, launch } => {
+            let browser = launch.browser(&app_cfg)?;
+            let link_cfg = LinkConfig::load(&link_path)?;
+
+            let mut launched = vec![];
+            for tag in &tags {
+                match link_cfg.get_urls(tag) {
+                    Some(urls) => launched.extend(urls.into_iter().map(|url| HistoryEntry::now(Some(tag.clone()), url))),
+                    None => say!("⚠️ Link tag '{}' not found, skipping.", tag),
+                }
+            }
+            if launched.is_empty() {
+                return Err(QuickTabsError::NotFound(format!("Link tag '{}'", tags.join("', '"))));
+            }
+
+            launch.reserve(&app_cfg, launched.len())?;
+            let urls: Vec<&str> = launched.iter().map(|e| e.url.as_str()).collect();
+            let options = launch.options(&app_cfg, &browser);
+            launch_urls_simultaneously(&browser, &urls, &options)?;
+            launch.record(launched, &options);
+        },
+        Commands::OpenAllLinks { window_each, system, delay, profile, yes, count, category, at, order, launch } => {
+            let mut link_cfg = LinkConfig::load(&link_path)?;
+            if let Some(category) = &category {
+                link_cfg.keep_category(category)?;
+            }
+            let batch = BatchOptions { delay: delay.map(Duration::from_millis), count, ..order.batch(window_each) };
+            let links = batch.pick(&link_cfg.links);
+            let launched: Vec<HistoryEntry> = links.iter()
+                .flat_map(|l| l.urls.iter().map(|url| HistoryEntry::now(Some(l.tag.clone()), url.clone())))
+                .collect();
+            let url_count = launched.len();
+            if !confirm_batch(url_count, yes || launch.dry_run, &app_cfg) {
+                say!("❎ Nothing opened.");
+                return Ok(());
+            }
+            if system {
+                if links.is_empty() {
+                    say!("⚠️ No links to open.");
+                    return Ok(());
+                }
+                if let Some(at) = at.filter(|_| !launch.dry_run) {
+                    sleep_until(at);
+                }
+                let options = launch.system_options(&app_cfg)?;
+                launch.reserve(&app_cfg, url_count)?;
+                let urls: Vec<&str> = links.iter().flat_map(|l| &l.urls).map(String::as_str).collect();
+                launch_with_system_opener(&urls, &options)?;
+                launch.record(launched, &options);
+                return Ok(());
+            }
+
+            let browser = launch.browser(&app_cfg)?;
+            // Waiting after the confirmation and browser choice, so nothing is asked at the set time
+            if let Some(at) = at.filter(|_| !launch.dry_run) {
+                sleep_until(at);
+            }
+            launch.reserve(&app_cfg, url_count)?;
+            let options = LaunchOptions { profile, ..launch.options(&app_cfg, &browser) };
+            link_cfg.open_all(&browser, &options, &batch)?;
+            launch.record(launched, &options);
+        },
+        Commands::OpenAllAliases { window_each, delay, yes, count, order, launch } => {
+            let alias_cfg = AliasConfig::load(&alias_path)?;
+            let batch = BatchOptions { delay: delay.map(Duration::from_millis), count, ..order.batch(window_each) };
+            let urls = alias_cfg.picked_urls(&LinkConfig::load(&link_path)?, &GroupConfig::load(&group_path)?, &batch);
+            if urls.is_empty() {
+                say!("⚠️ No aliases to open.");
+                return Ok(());
+            }
+            if !confirm_batch(urls.len(), yes || launch.dry_run, &app_cfg) {
+                say!("❎ Nothing opened.");
+                return Ok(());
+            }
+            let browser = launch.browser(&app_cfg)?;
+            launch.reserve(&app_cfg, urls.len())?;
+            let options = launch.options(&app_cfg, &browser);
+            let url_refs: Vec<&str> = urls.iter().map(|(_, url)| url.as_str()).collect();
+            launch_batch(&browser, &url_refs, &options, &batch)?;
+            launch.record(urls.into_iter().map(|(tag, url)| HistoryEntry::now(Some(tag.clone()), url)), &options);
+        },
+
+        Commands::ResolveAll { json } => {
+            let link_cfg = LinkConfig::load(&link_path)?;
+            let alias_cfg = AliasConfig::load(&alias_path)?;
+            let resolutions = resolve_all(&alias_cfg, &link_cfg, &GroupConfig::load(&group_path)?);
+
+            if json {
+                let out = serde_json::to_string_pretty(&resolutions).map_err(|e| e.to_string())?;
+                println!("{}", out);
+            } else {
+                for r in &resolutions {
+                    println!("[{}] ({}) -> {}", r.tag, r.kind.label(), r.resolved.as_deref().unwrap_or("?"));
+                    if !r.placeholders.is_empty() {
+                        println!("    placeholders: {}", r.placeholders.join(", "));
+                    }
+                    for v in &r.env_vars {
+                        println!("    env ${}: {}", v.name, if v.set { "set" } else { "not set" });
+                    }
+                }
+            }
+
+            let mut failed = vec![];
+            for r in &resolutions {
+                if let Some(error) = &r.error {
+                    esay!("⚠️ [{}] does not resolve: {}", r.tag, error);
+                    failed.push(r.tag.as_str());
+                }
+            }
+            if !failed.is_empty() {
+                return Err(QuickTabsError::Other(format!("{} tag(s) failed to resolve: {}", failed.len(), failed.join(", "))));
+            }
         },
-        Commands::OpenAllAliases { incognito } => {
-            let browser = get_browser_or_exit(browser_result)?;
-            let alias_cfg = AliasConfig::load(&alias_path);
-            let mode = if incognito { LaunchMode::Private } else { LaunchMode::Normal };
-            alias_cfg.open_all(&browser, mode);
+
+        Commands::Export { path } => {
+            let link_cfg = LinkConfig::load(&link_path)?;
+            let alias_cfg = AliasConfig::load(&alias_path)?;
+            let browser = read_saved_browser(&browser_config_path()?)?;
+            Bundle::new(&link_cfg, &alias_cfg, browser).save(&path)?;
+            say!("✅ Exported {} link(s) and {} alias(es) to {}", link_cfg.links.len(), alias_cfg.aliases.len(), path.display());
         },
 
+        Commands::Import { path: Some(path), merge, dry_run, .. } => {
+            let bundle = Bundle::load(&path)?;
+            let mut link_cfg = LinkConfig::load(&link_path)?;
+            let mut alias_cfg = AliasConfig::load(&alias_path)?;
+
+            let changes = if merge {
+                let (changes, collisions) = bundle.merge_into(&mut link_cfg, &mut alias_cfg);
+                for (kind, tag) in &collisions {
+                    say!("⚖️ {} [{}] is already saved with a different value; kept the saved one", kind, tag);
+                }
+                changes
+            } else {
+                bundle.replace_into(&mut link_cfg, &mut alias_cfg)
+            };
+            if dry_run {
+                changes.commit(true, || Ok::<_, QuickTabsError>(()))?;
+                return Ok(());
+            }
+            // Replacing writes even without changes, so order and timestamps match the file exactly
+            if !merge || !changes.is_empty() {
+                link_cfg.save(&link_path)?;
+                alias_cfg.save(&alias_path)?;
+            }
+
+            if let Some(browser) = &bundle.browser {
+                let current = read_saved_browser(&browser_config_path()?).ok().flatten().filter(|b| b.path.exists());
+                if !browser.path.exists() {
+                    say!("⚠️ The exported browser {} ({}) is not installed here; keeping the current browser.", browser.name, browser.path.display());
+                } else if !merge || current.is_none() {
+                    save_default_browser(browser)?;
+                }
+            }
+            say!("✅ Imported {} from {} ({}).", if merge { "new tags" } else { "links and aliases" }, path.display(), changes.summary());
+        },
+        Commands::Import { from_browser: Some(from_browser), profile, strategy, dry_run, .. } => {
+            let bookmarks = import_from_browser(&from_browser, profile.as_deref())
+                .map_err(QuickTabsError::Import)?;
+            let mut link_cfg = LinkConfig::load(&link_path)?;
+            let mut group_cfg = GroupConfig::load(&group_path)?;
+
+            let mut changes = ChangeSet::default();
+            let (mut added, mut kept, mut replaced, mut renamed) = (0, 0, 0, 0);
+            for b in bookmarks {
+                let local_touched = link_cfg.links.iter().find(|l| l.tag == b.tag).and_then(Link::last_touched);
+                let incoming = Link { modified: b.modified, last_used: b.last_used, ..Link::new(b.tag.clone(), b.url) };
+                let imported_touched = incoming.last_touched();
+                let (merged, link_changes) = merge_link(&mut link_cfg, incoming, strategy);
+                changes.extend(link_changes);
+
+                let saved_as = match merged {
+                    Merged::Added => {
+                        added += 1;
+                        b.tag
+                    }
+                    Merged::KeptLocal => {
+                        kept += 1;
+                        if strategy != MergeStrategy::Skip {
+                            report_conflict(&b.tag, "kept the saved link", local_touched, imported_touched);
+                        }
+                        continue;
+                    }
+                    Merged::TookImported => {
+                        replaced += 1;
+                        report_conflict(&b.tag, "took the imported link", local_touched, imported_touched);
+                        b.tag
+                    }
+                    Merged::Renamed(tag) => {
+                        renamed += 1;
+                        say!("⚖️ [{}] is already saved; imported as [{}]", b.tag, tag);
+                        tag
+                    }
+                };
+                if let Some(folder) = &b.folder {
+                    changes.extend(group_cfg.add_to_group(folder, &saved_as));
+                }
+            }
+
+            changes.commit(dry_run, || {
+                link_cfg.save(&link_path)?;
+                group_cfg.save(&group_path)
+            })?;
+            if dry_run {
+                return Ok(());
+            }
+            say!("✅ Imported {} bookmark(s); existing tags: {} kept, {} replaced, {} renamed.", added, kept, replaced, renamed);
+        },
+        Commands::Import { .. } => unreachable!("clap requires a file or --from-browser"),
+
+        Commands::Config { key, value, dry_run } => {
+            let settings = settings_path()?;
+            let mut app_cfg = AppConfig::load(&settings);
+            match (key, value) {
+                (None, _) => {
+                    let json = serde_json::to_string_pretty(&app_cfg).map_err(|e| e.to_string())?;
+                    println!("{}", json);
+                },
+                (Some(key), None) => {
+                    let value = app_cfg.get_value(&key)
+                        .ok_or_else(|| QuickTabsError::NotFound(format!("Setting '{}'", key)))?;
+                    println!("{}", value);
+                },
+                (Some(key), Some(value)) => {
+                    let changes = app_cfg.set_value(&key, &value)?;
+                    if changes.commit(dry_run, || app_cfg.save(&settings))? {
+                        say!("✅ Setting '{}' saved!", key);
+                    }
+                },
+            }
+        },
+
+        Commands::AddBlock { pattern, dry_run } => {
+            let path = blocklist_path()?;
+            let mut blocklist = Blocklist::load(&path);
+            let changes = blocklist.add(pattern);
+            if changes.is_empty() {
+                say!("⚠️ Pattern is already blocked.");
+            } else if changes.commit(dry_run, || blocklist.save(&path))? {
+                say!("✅ Block pattern saved!");
+            }
+        },
+        Commands::RemoveBlock { pattern, dry_run } => {
+            let path = blocklist_path()?;
+            let mut blocklist = Blocklist::load(&path);
+            let changes = blocklist.remove(&pattern);
+            if changes.is_empty() {
+                return Err(QuickTabsError::NotFound(format!("Block pattern '{}'", pattern)));
+            }
+            if changes.commit(dry_run, || blocklist.save(&path))? {
+                say!("✅ Block pattern removed!");
+            }
+        },
+        Commands::History { action: None } => {
+            let entries = history::load(&history::history_path()?)?;
+            if entries.is_empty() {
+                say!("⚠️ No launches recorded.");
+            }
+            for (index, entry) in history::recent(&entries) {
+                match &entry.tag {
+                    Some(tag) => println!("{:>3}. {}  [{}] {}", index, format_timestamp(entry.timestamp), tag, entry.url),
+                    None => println!("{:>3}. {}  {}", index, format_timestamp(entry.timestamp), entry.url),
+                }
+            }
+        },
+        Commands::Replay { index, launch } => {
+            let entries = history::load(&history::history_path()?)?;
+            let entry = history::recent(&entries)
+                .find(|(i, _)| *i == index)
+                .map(|(_, entry)| entry.clone())
+                .ok_or_else(|| QuickTabsError::NotFound(format!("History entry {}", index)))?;
+
+            let browser = launch.browser(&app_cfg)?;
+            launch.reserve(&app_cfg, 1)?;
+            // The stored URL rather than the tag, so renamed or removed tags still replay
+            let options = launch.options(&app_cfg, &browser);
+            launch_link(&browser, &entry.url, &options)?;
+            launch.record([HistoryEntry::now(entry.tag, entry.url)], &options);
+        },
+        Commands::History { action: Some(HistoryAction::Export { format, output, since }) } => {
+            let mut entries = history::load(&history::history_path()?)?;
+            if let Some(window) = since {
+                entries = history::filter_since(entries, window);
+            }
+
+            match &output {
+                Some(path) => {
+                    let mut out = BufWriter::new(File::create(path)?);
+                    history::export(&entries, format, &mut out)?;
+                    out.flush()?;
+                    say!("✅ Exported {} history entr{} to {}", entries.len(),
+                        if entries.len() == 1 { "y" } else { "ies" }, path.display());
+                },
+                None => history::export(&entries, format, &mut io::stdout().lock())?,
+            }
+        },
+
+        Commands::Reindex { what } => cache::reindex(what.as_deref())?,
+
         // --- Browser Commands ---
-        Commands::Detect => {
-            // detect_browsers returns Option<Browser>, not Result. We ignore the return value.
-            let _ = detect_browsers(); 
+        Commands::SetBrowser { path_or_name } => {
+            let browser = set_browser(&path_or_name, &DetectOptions::from_config(&app_cfg))?;
+            say!("✅ Preferred browser set to {} ({})", browser.name, browser.path.display());
+        },
+        Commands::SetBrowserFlags { name, args, private, default_private, clear } => {
+            let browser = update_saved_browser(&name, |b| {
+                if clear {
+                    b.extra_args.clear();
+                    b.private_args = None;
+                }
+                if !args.is_empty() {
+                    b.extra_args = args;
+                }
+                if !private.is_empty() {
+                    b.private_args = Some(private);
+                }
+                if default_private {
+                    b.private_args = None;
+                }
+            })?;
+            let private = match &browser.private_args {
+                Some(flags) => shell_join(flags),
+                None => "built-in".to_string(),
+            };
+            say!("✅ {}: extra arguments: {}; private-mode flags: {}",
+                browser.name, if browser.extra_args.is_empty() { "none".to_string() } else { shell_join(&browser.extra_args) }, private);
+        },
+        Commands::Doctor => doctor::run(&paths, &DetectOptions::from_config(&app_cfg))?,
+        Commands::Check { online } => check::run(&LinkConfig::load(&link_path)?, &AliasConfig::load(&alias_path)?, online)?,
+        Commands::Stats => {
+            let stats = Stats::collect(&LinkConfig::load(&link_path)?, &AliasConfig::load(&alias_path)?);
+            // A broken browser config is `doctor`'s business; here it just counts as unset
+            let browser = read_saved_browser(&browser_config_path()?).ok().flatten();
+            stats.print(browser.as_ref());
+        },
+        Commands::Detect { debug_paths, json, list_only, format, parallel_detect_threads, latest_only, save_as, refresh, deep, search_path } => {
+            if debug_paths {
+                let reports = probe_report();
+                if json {
+                    let out = serde_json::to_string_pretty(&reports).map_err(|e| e.to_string())?;
+                    println!("{}", out);
+                } else {
+                    print_probe_report(&reports);
+                }
+            } else {
+                let mut options = DetectOptions::from_config(&app_cfg);
+                if let Some(threads) = parallel_detect_threads {
+                    options.threads = threads;
+                }
+                options.latest_only = latest_only;
+                options.save_as = save_as;
+                options.refresh = refresh || deep || !search_path.is_empty();
+                options.deep = deep;
+                options.search_paths = search_path;
+                if list_only {
+                    return list_browsers(&options, format);
+                }
+                // Provisioning scripts rely on a failing exit code when nothing usable was saved
+                if detect_and_select(&options).is_none() {
+                    esay!("ℹ️ Detection found no usable browser and saved none. Install one, or save one by path with `set-browser <path>`.");
+                    return Err(QuickTabsError::NoBrowser);
+                }
+            }
+        },
+        Commands::Init { browser } => {
+            let path = project::init(&env::current_dir()?, browser)?;
+            say!("✅ Created {}. Add links under \"links\" to use them in this directory tree.", path.display());
+        },
+        Commands::MigrateLauncher { dir } => {
+            let dir = dir.unwrap_or_else(|| PathBuf::from("."));
+            if !migrate_dir(&dir, &paths) {
+                let shown = std::fs::canonicalize(&dir).unwrap_or(dir);
+                say!("ℹ️ Nothing to migrate in {}", shown.display());
+            }
+        },
+        Commands::ListBrowserConfigs => {
+            let configs = list_named_browsers()?;
+            if configs.is_empty() {
+                say!("⚠️ No browser configs saved. Create one with `detect --save-as <name>`.");
+            }
+            for (name, browser) in configs {
+                println!("  [{}] {} ({})", name, browser.name, browser.path.display());
+            }
+        },
+        Commands::Completions { shell } => {
+            let mut command = Cli::command();
+            let name = command.get_name().to_string();
+            // Buffered so a closed pipe is a normal I/O error rather than a panic
+            let mut script = vec![];
+            clap_complete::generate(shell, &mut command, name, &mut script);
+            io::stdout().write_all(&script)?;
+        },
+        Commands::Examples { command } => {
+            print_examples(Cli::command().get_name(), command.as_deref())?;
         },
         Commands::Help => {
             Cli::command().print_help()?;
@@ -156,13 +1819,180 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn get_browser_or_exit(browser_result: Option<Browser>) -> Result<Browser, Box<dyn std::error::Error>> {
-    match browser_result {
-        Some(b) => Ok(b),
-        None => {
-            eprintln!("❌ Error: No browser configured. Run 'quick_tabs detect' or set manually.");
-            // We use standard library exit here since we cannot proceed without a browser
-            std::process::exit(1); 
+/// Asks `Open N links? [y/N]` when `count` exceeds `confirm_open_above`, unless
+/// `--yes` was given. Checked before detection and the open limit, so declining
+/// costs nothing.
+fn confirm_batch(count: usize, yes: bool, app_cfg: &AppConfig) -> bool {
+    let limit = app_cfg.confirm_open_above;
+    yes || limit == 0 || count <= limit || confirm(&format!("Open {} links?", count))
+}
+
+/// Saves links after a mutation, first dropping duplicate URLs when `dedupe_on_save` is on.
+/// Import writes through `LinkConfig::save` directly since its collision policy already applies.
+fn save_links(link_cfg: &mut LinkConfig, path: &Path) -> Result<(), QuickTabsError> {
+    if AppConfig::load_or_default().dedupe_on_save {
+        let removed = link_cfg.dedupe_urls();
+        if !removed.is_empty() {
+            say!("🧹 Removed {} duplicate link(s): {}", removed.len(), removed.join(", "));
+        }
+    }
+    link_cfg.save(path)?;
+    Ok(())
+}
+
+/// Normalizes an alias target like a link URL, unless it is `--raw` or refers to
+/// another tag or a group (`group:<name>`), which are not URLs.
+fn alias_target(url: String, raw: bool, aliases: &AliasConfig, link_path: &Path) -> Result<String, QuickTabsError> {
+    let is_reference = url.starts_with("group:")
+        || aliases.resolve(&url).is_some()
+        || LinkConfig::load(link_path)?.contains(&url);
+    if raw || is_reference {
+        Ok(url)
+    } else {
+        Ok(normalize_url(&url)?)
+    }
+}
+
+/// Normalizes each URL typed for a link, unless `--raw` is given.
+fn normalize_urls(urls: Vec<String>, raw: bool) -> Result<Vec<String>, QuickTabsError> {
+    if raw {
+        return Ok(urls);
+    }
+    urls.iter().map(|url| Ok(normalize_url(url)?)).collect()
+}
+
+/// Opens a link's URLs: one the usual way, several together in one browser invocation.
+fn launch_urls(browser: &Browser, urls: &[&str], options: &LaunchOptions) -> Result<(), QuickTabsError> {
+    match urls {
+        [url] => launch_link(browser, url, options),
+        _ => launch_urls_simultaneously(browser, urls, options),
+    }
+}
+
+/// The tag `launch` should open for `target`: `target` itself when it is a saved tag
+/// or a URL with a scheme. Otherwise the closest saved tags are offered to pick from,
+/// or, without a terminal to ask on, listed in the error. `None` when the user
+/// picks nothing.
+fn saved_or_chosen_tag(target: String, aliases: &AliasConfig, links: &LinkConfig) -> Result<Option<String>, QuickTabsError> {
+    if is_saved_tag(&target, aliases, links) || target.contains("://") {
+        return Ok(Some(target));
+    }
+    let suggestions = suggest_tags(&target, aliases, links);
+    if suggestions.is_empty() {
+        esay!("ℹ️ Use --literal to open '{}' as given.", target);
+        return Err(QuickTabsError::NotFound(format!("Tag '{}'", target)));
+    }
+    if !is_interactive() {
+        esay!("ℹ️ Did you mean: {}?", suggestions.join(", "));
+        return Err(QuickTabsError::NotFound(format!("Tag '{}'", target)));
+    }
+    say!("❓ No tag '{}'. Did you mean:", target);
+    Ok(choose("Open which?", &suggestions).map(|i| suggestions[i].clone()))
+}
+
+/// Reports which side won an import conflict, with both timestamps.
+fn report_conflict(tag: &str, outcome: &str, local: Option<u64>, imported: Option<u64>) {
+    let show = |t: Option<u64>| t.map(format_timestamp).unwrap_or_else(|| "no timestamp".to_string());
+    say!("⚖️ [{}] {} (saved: {}, imported: {})", tag, outcome, show(local), show(imported));
+}
+
+/// Saves aliases after a mutation, first dropping duplicate URLs when `dedupe_on_save` is on.
+fn save_aliases(alias_cfg: &mut AliasConfig, path: &Path) -> Result<(), QuickTabsError> {
+    if AppConfig::load_or_default().dedupe_on_save {
+        let removed = alias_cfg.dedupe_urls();
+        if !removed.is_empty() {
+            say!("🧹 Removed {} duplicate alias(es): {}", removed.len(), removed.join(", "));
+        }
+    }
+    alias_cfg.save(path)?;
+    Ok(())
+}
+
+/// One example: what it does, then the arguments after the binary name.
+type Example = (&'static str, &'static [&'static str]);
+
+/// Curated examples per workflow, under a heading. The arguments are checked against
+/// the CLI definition in the tests below.
+const EXAMPLES: &[(&str, &[Example])] = &[
+    ("Saving links", &[
+        ("Save a link under the tag gh (https:// is added)", &["add-link", "gh", "github.com"]),
+        ("Save two URLs that open together, with a note", &["add-link", "standup", "https://meet.example.com", "https://board.example.com", "--desc", "Daily standup"]),
+        ("Point a short alias at a saved link", &["add-alias", "g", "gh"]),
+        ("Add `tag url` lines from stdin in one go (e.g. `< links.txt`)", &["import-links"]),
+        ("Replace the URL of an existing link", &["edit-link", "gh", "https://github.com/notifications"]),
+        ("List what is saved", &["list-links"]),
+    ]),
+    ("Launching", &[
+        ("Open a saved link", &["launch", "gh"]),
+        ("Open it in a private window", &["launch", "gh", "--incognito"]),
+        ("Open it in another browser, just this once", &["launch", "gh", "--browser", "firefox"]),
+        ("Preview the browser command without opening anything", &["launch", "gh", "--dry-run"]),
+        ("Print the resolved URL for another program", &["launch", "gh", "--print-url"]),
+        ("Open several links together", &["open-links", "gh", "standup"]),
+    ]),
+    ("Groups", &[
+        ("Put links in a group", &["add-group", "work", "gh", "standup"]),
+        ("Open every link of the group", &["open-group", "work"]),
+        ("Open two groups, each in a window of its own", &["open-group", "work", "news", "--window-per-group"]),
+    ]),
+    ("Opening everything", &[
+        ("Open all saved links", &["open-all-links"]),
+        ("Open all links privately, each in its own window", &["open-all-links", "--incognito", "--window-each"]),
+        ("Open only the first five links", &["open-all-links", "--count", "5"]),
+        ("Open the standup links at 09:55", &["open-all-links", "--category", "standup", "--at", "09:55"]),
+        ("Open all aliases", &["open-all-aliases"]),
+    ]),
+    ("Browsers", &[
+        ("Detect installed browsers and pick one", &["detect"]),
+        ("Save the preferred browser without prompting", &["set-browser", "firefox"]),
+    ]),
+];
+
+/// Prints `EXAMPLES` with the binary's own name, or only those running `command`.
+fn print_examples(bin: &str, command: Option<&str>) -> Result<(), QuickTabsError> {
+    let mut printed = 0;
+    for (heading, examples) in EXAMPLES {
+        let examples: Vec<_> = examples.iter()
+            .filter(|(_, args)| command.is_none_or(|c| args[0] == c))
+            .collect();
+        if examples.is_empty() {
+            continue;
+        }
+        if printed > 0 {
+            println!();
+        }
+        println!("{}:", heading);
+        for (what, args) in examples {
+            println!("  # {}", what);
+            let args: Vec<String> = args.iter().map(|a| shell_quote(a)).collect();
+            println!("  {} {}", bin, args.join(" "));
+            printed += 1;
+        }
+    }
+    match (printed, command) {
+        (0, Some(command)) => Err(QuickTabsError::NotFound(format!("Examples for '{}'", command))),
+        _ => Ok(()),
+    }
+}
+
+fn get_browser_or_exit(browser_result: Option<Browser>) -> Result<Browser, QuickTabsError> {
+    // We cannot proceed without a browser; the caller exits with the error's code
+    browser_result.ok_or(QuickTabsError::NoBrowser)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_parse() {
+        for (_, examples) in EXAMPLES {
+            for (_, args) in *examples {
+                let argv = std::iter::once("quick_tabs").chain(args.iter().copied());
+                if let Err(e) = Cli::try_parse_from(argv) {
+                    panic!("example `{}` does not parse: {}", args.join(" "), e);
+                }
+            }
         }
     }
 }
@@ -0,0 +1,157 @@
+// tests/cli.rs
+//! End-to-end runs of the binary. Every test works in its own `--config-dir`, so the
+//! user's real links, aliases and browser are never read or touched.
+
+use std::fs;
+use std::path::PathBuf;
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_cmd::Command;
+
+/// A fresh config directory, removed when the test is done.
+struct ConfigDir(PathBuf);
+
+impl ConfigDir {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("quick_tabs_cli_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        ConfigDir(dir)
+    }
+
+    /// The binary with `--config-dir` set and plain output, so messages are ASCII.
+    fn command(&self, args: &[&str]) -> Command {
+        let mut cmd = cargo_bin_cmd!("quick_tabs");
+        cmd.arg("--config-dir").arg(&self.0).arg("--plain").args(args);
+        cmd
+    }
+
+    fn stdout(&self, args: &[&str]) -> String {
+        let output = self.command(args).assert().success();
+        String::from_utf8_lossy(&output.get_output().stdout).into_owned()
+    }
+}
+
+impl Drop for ConfigDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn links_are_added_listed_and_removed() {
+    let dir = ConfigDir::new("links");
+    dir.command(&["add-link", "gh", "github.com"]).assert().success();
+    dir.command(&["add-link", "docs", "https://docs.rs", "--desc", "API docs"]).assert().success();
+    assert!(dir.0.join("links.json").exists());
+
+    let listed = dir.stdout(&["list-links"]);
+    assert!(listed.contains("[gh] https://github.com"), "{}", listed);
+    assert!(listed.contains("[docs] https://docs.rs"), "{}", listed);
+    assert!(listed.contains("API docs"), "{}", listed);
+
+    dir.command(&["remove-link", "gh"]).assert().success();
+    let listed = dir.stdout(&["list-links", "--tag-only"]);
+    assert_eq!(listed.lines().collect::<Vec<_>>(), ["docs"]);
+}
+
+#[test]
+fn existing_tags_need_force() {
+    let dir = ConfigDir::new("force");
+    dir.command(&["add-link", "gh", "https://github.com"]).assert().success();
+    dir.command(&["add-link", "gh", "https://gitlab.com"]).assert().code(1);
+    let replaced = dir.stdout(&["add-link", "gh", "https://gitlab.com", "--force"]);
+    assert!(replaced.starts_with("[info] Replacing existing link for tag: gh"), "{}", replaced);
+    assert!(dir.stdout(&["-q", "add-link", "gh", "https://gitlab.com", "--force"]).is_empty());
+    assert_eq!(dir.stdout(&["list-links", "--url-only"]).trim(), "https://gitlab.com");
+}
+
+#[test]
+fn unknown_tags_fail_with_not_found() {
+    let dir = ConfigDir::new("missing");
+    dir.command(&["remove-link", "nope"]).assert().code(5);
+    dir.command(&["edit-link", "nope", "https://example.com"]).assert().code(5);
+}
+
+#[cfg(unix)]
+#[test]
+fn aliases_win_over_links_with_the_same_tag() {
+    let dir = ConfigDir::new("precedence");
+    dir.command(&["add-link", "docs", "https://docs.rs"]).assert().success();
+    dir.command(&["add-alias", "docs", "https://doc.rust-lang.org"]).assert().success();
+
+    // Any executable will do as the browser: nothing is launched with --dry-run
+    let preview = dir.stdout(&["launch", "docs", "--dry-run", "--no-history", "--browser", "/bin/sh"]);
+    assert!(preview.contains("/bin/sh https://doc.rust-lang.org"), "{}", preview);
+    assert!(!preview.contains("https://docs.rs"), "{}", preview);
+}
+
+#[cfg(unix)]
+#[test]
+fn wait_passes_the_browser_exit_status_through() {
+    let dir = ConfigDir::new("wait");
+    dir.command(&["add-link", "docs", "https://docs.rs"]).assert().success();
+
+    dir.command(&["launch", "docs", "--wait", "--no-history", "--browser", "/bin/true"]).assert().success();
+
+    let browser = dir.0.join("browser.sh");
+    fs::write(&browser, "#!/bin/sh\nexit 42\n").unwrap();
+    fs::set_permissions(&browser, std::os::unix::fs::PermissionsExt::from_mode(0o755)).unwrap();
+    dir.command(&["launch", "docs", "--wait", "--no-history", "--browser", browser.to_str().unwrap()]).assert().code(42);
+}
+
+#[test]
+fn links_are_imported_from_stdin_in_one_go() {
+    let dir = ConfigDir::new("import");
+    dir.command(&["add-link", "gh", "https://github.com"]).assert().success();
+
+    let input = "# tag url\ndocs docs.rs\ngh https://gitlab.com\nlonely\nrust https://www.rust-lang.org\n";
+    let output = dir.command(&["import-links"]).write_stdin(input).assert().success();
+    let stderr = String::from_utf8_lossy(&output.get_output().stderr).into_owned();
+    assert!(stderr.contains("Line 3: Link tag 'gh' already exists"), "{}", stderr);
+    assert!(stderr.contains("Line 4: 'lonely' has no URL"), "{}", stderr);
+
+    let listed = dir.stdout(&["list-links", "--tag-only"]);
+    assert_eq!(listed.lines().collect::<Vec<_>>(), ["gh", "docs", "rust"]);
+    assert_eq!(dir.stdout(&["list-links", "gh", "--url-only"]).trim(), "https://github.com");
+
+    dir.command(&["import-links", "--force"]).write_stdin("gh https://gitlab.com\n").assert().success();
+    assert_eq!(dir.stdout(&["list-links", "gh", "--url-only"]).trim(), "https://gitlab.com");
+}
+
+#[test]
+fn imports_are_not_deduplicated_on_save() {
+    let dir = ConfigDir::new("import_dedupe");
+    dir.command(&["config", "dedupe_on_save", "true"]).assert().success();
+    dir.command(&["add-link", "gh", "https://github.com"]).assert().success();
+
+    let output = dir.command(&["import-links"]).write_stdin("hub https://github.com/\n").assert().success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout).into_owned();
+    assert!(!stdout.contains("duplicate link"), "{}", stdout);
+    let listed = dir.stdout(&["list-links", "--tag-only"]);
+    assert_eq!(listed.lines().collect::<Vec<_>>(), ["gh", "hub"]);
+}
+
+#[test]
+fn configs_from_a_newer_version_are_refused_untouched() {
+    let dir = ConfigDir::new("schema");
+    let future = "{\"version\": 99, \"links\": [{\"tag\": \"gh\", \"url\": \"https://github.com\"}]}";
+    fs::write(dir.0.join("links.json"), future).unwrap();
+
+    let output = dir.command(&["add-link", "docs", "https://docs.rs"]).assert().code(1);
+    let stderr = String::from_utf8_lossy(&output.get_output().stderr).into_owned();
+    assert!(stderr.contains("schema version 99"), "{}", stderr);
+    assert_eq!(fs::read_to_string(dir.0.join("links.json")).unwrap(), future);
+}
+
+#[test]
+fn print_url_writes_only_the_resolved_urls() {
+    let dir = ConfigDir::new("print_url");
+    dir.command(&["add-link", "search", "https://duckduckgo.com/?q={}"]).assert().success();
+    dir.command(&["add-link", "pair", "https://a.example", "https://b.example"]).assert().success();
+    dir.command(&["add-alias", "s", "search"]).assert().success();
+
+    assert_eq!(dir.stdout(&["launch", "s", "rust", "traits", "--print-url"]), "https://duckduckgo.com/?q=rust%20traits\n");
+    assert_eq!(dir.stdout(&["launch", "pair", "--print-url"]), "https://a.example\nhttps://b.example\n");
+    dir.command(&["launch", "nope", "--print-url"]).assert().code(5);
+    dir.command(&["launch", "s", "--print-url", "--dry-run"]).assert().code(2);
+}